@@ -3,10 +3,11 @@ use std::collections::HashMap;
 // mod super::token::TokenKeywords;
 use std::{io};
 
-use crate::ast_token::{get_token_keyword, Token, get_token_literal};
-use crate::ast_node::{ Expression, NumberLiteral, StringLiteral, Statement, IdentifierLiteral, ExpressionStatement, PropertyAccessExpression, BinaryExpression, ConditionalExpression, CallExpression, Keywords, Parameter, BlockStatement, ReturnStatement, Declaration, PropertyAssignment, ObjectLiteral, ElementAccessExpression, FunctionDeclaration, PostfixUnaryExpression, PrefixUnaryExpression, AssignExpression, GroupExpression, VariableDeclaration, VariableDeclarationStatement, VariableFlag, ClassDeclaration, ClassMethodDeclaration, ArrayLiteral, ComputedPropertyName, IfStatement, ForStatement, ForInStatement, ForOfStatement, BreakStatement, ContinueStatement, LabeledStatement, SwitchStatement, CaseClause, NewExpression, TryCatchStatement, CatchClause, ThrowStatement, TemplateLiteralExpression, SequenceExpression};
-use crate::ast_utils::{get_hex_number_value, chars_to_string, process_string_escapes};
+use crate::ast_token::{get_token_keyword, Token, get_token_literal, get_token_description};
+use crate::ast_node::{ Expression, NumberLiteral, StringLiteral, Statement, IdentifierLiteral, ExpressionStatement, PropertyAccessExpression, BinaryExpression, ConditionalExpression, CallExpression, Keywords, Parameter, BlockStatement, ReturnStatement, Declaration, PropertyAssignment, ObjectLiteral, ElementAccessExpression, FunctionDeclaration, PostfixUnaryExpression, PrefixUnaryExpression, AssignExpression, GroupExpression, VariableDeclaration, VariableDeclarationStatement, VariableFlag, ClassDeclaration, ClassMethodDeclaration, ArrayLiteral, ComputedPropertyName, IfStatement, ForStatement, ForInStatement, ForOfStatement, BreakStatement, ContinueStatement, LabeledStatement, SwitchStatement, CaseClause, NewExpression, TryCatchStatement, CatchClause, ThrowStatement, TemplateLiteralExpression, SequenceExpression, Span};
+use crate::ast_utils::{get_hex_number_value, chars_to_string, process_string_escapes, stack_address};
 use crate::bytecode::{ByteCode, EByteCodeop};
+use crate::value::Value;
 use crate::error::{JSIResult, JSIError, JSIErrorType};
 pub struct AST {
   strict: bool,
@@ -20,6 +21,14 @@ pub struct AST {
   code: Vec<char>,
   // 代码总字符数
   length: usize,
+  // 每个字符的字节偏移，最后一项为代码的总字节数
+  byte_offsets: Vec<usize>,
+  // 每一行第一个字符的索引
+  line_starts: Vec<usize>,
+  // 当前 token 开始的字符索引
+  token_start_index: usize,
+  // 前一个 token 结束的字符索引
+  prev_token_end_index: usize,
   // 当前标识符
   token: Token,
   // 当前字面量
@@ -40,12 +49,35 @@ pub struct AST {
   global_bc_index: usize,
   // Token 栈
   token_stack: Vec<Token>,
+  // 是否在语法错误后继续解析，用于一次报告多个错误
+  recover: bool,
+  // 继续解析时记录的语法错误
+  errors: Vec<JSIError>,
+  // 当前语句、表达式的嵌套深度，超过 max_nesting_depth 时报错，避免递归解析导致栈溢出
+  nesting_depth: usize,
+  max_nesting_depth: usize,
+  // 栈地址低于该值时报错，0 表示不检查，由 Context 根据剩余栈空间设置
+  stack_limit: usize,
 }
 
+// 默认的最大嵌套深度，每层嵌套需要经过多层解析函数，release 构建下约占用 10KB 栈空间
+pub const DEFAULT_MAX_NESTING_DEPTH: usize = 128;
+
 impl AST{
   pub fn new(code: String) -> AST{
     let chars: Vec<char> = code.chars().collect();
     let len = chars.len();
+    let mut byte_offsets: Vec<usize> = Vec::with_capacity(len + 1);
+    let mut line_starts: Vec<usize> = vec![0];
+    let mut byte_offset = 0;
+    for (index, ch) in chars.iter().enumerate() {
+      byte_offsets.push(byte_offset);
+      byte_offset += ch.len_utf8();
+      if *ch == '\n' {
+        line_starts.push(index + 1);
+      }
+    }
+    byte_offsets.push(byte_offset);
     AST {
       strict: true,
       char: ' ',
@@ -53,6 +85,10 @@ impl AST{
       cur_char_index: 0,
       code: chars,
       length: len,
+      byte_offsets,
+      line_starts,
+      token_start_index: 0,
+      prev_token_end_index: 0,
       token: Token::Identifier,
       literal: String::from(""),
       cur_expr: Expression::Unknown,
@@ -63,6 +99,11 @@ impl AST{
       bytecode: vec![],
       global_bc_index: 0,
       token_stack: vec![],
+      recover: false,
+      errors: vec![],
+      nesting_depth: 0,
+      max_nesting_depth: DEFAULT_MAX_NESTING_DEPTH,
+      stack_limit: 0,
     }
   }
 
@@ -70,10 +111,78 @@ impl AST{
       self.strict = strict;
     }
 
+  pub fn set_max_nesting_depth(&mut self, depth: usize) {
+    self.max_nesting_depth = depth;
+  }
+
+  pub fn set_stack_limit(&mut self, limit: usize) {
+    self.stack_limit = limit;
+  }
+
+  // 增加一层嵌套，超过最大深度或栈空间不足时抛出 RangeError
+  fn enter_nesting(&mut self) -> JSIResult<()> {
+    if self.nesting_depth >= self.max_nesting_depth || stack_address() < self.stack_limit {
+      return Err(JSIError::new(JSIErrorType::RangeError, String::from("Maximum call stack size exceeded"), 0, 0));
+    }
+    self.nesting_depth += 1;
+    Ok(())
+  }
+
+  // 进入一层嵌套解析
+  fn parse_nested<T>(&mut self, parse: fn(&mut AST) -> JSIResult<T>) -> JSIResult<T> {
+    self.enter_nesting()?;
+    let result = parse(self);
+    self.nesting_depth -= 1;
+    result
+  }
+
   // 解析生成 Program
   pub fn parse(&mut self) -> JSIResult<Program> {
     self.next();
-    return self.parse_program()
+    return self.parse_program().map_err(|mut err| {
+      self.fill_error_position(&mut err);
+      err
+    })
+  }
+
+  // 解析生成 Program，遇到语法错误时跳到下一个语句继续解析
+  // 返回解析成功的部分 Program 和所有的语法错误
+  pub fn parse_with_recovery(&mut self) -> (Program, Vec<JSIError>) {
+    self.recover = true;
+    self.next();
+    self.new_scope();
+    let mut body: Vec<Statement> = vec![];
+    loop {
+      // parse_statements 不会返回错误，错误都记录在 self.errors 中
+      if let Ok(statements) = self.parse_statements() {
+        body.extend(statements);
+      }
+      if self.token != Token::RightBrace {
+        break;
+      }
+      // 顶层多余的 }
+      let mut err = self.error_unexpected();
+      self.fill_error_position(&mut err);
+      self.errors.push(err);
+      self.next();
+    }
+    let declarations = self.scope.declarations.clone();
+    self.close_scope();
+    let program = Program {
+      body,
+      declarations,
+      bytecode: self.bytecode.clone(),
+    };
+    (program, std::mem::take(&mut self.errors))
+  }
+
+  // 没有指定位置的语法错误，使用出错时所在 token 的位置
+  fn fill_error_position(&self, err: &mut JSIError) {
+    if err.line == 0 {
+      let (line, column) = self.get_position(self.token_start_index.min(self.length));
+      err.line = line as i32;
+      err.column = column as i32;
+    }
   }
 
   // 解析生成 program
@@ -114,6 +223,12 @@ impl AST{
         self.next();
         continue;
       }
+      if self.recover {
+        if let Some(statement) = self.parse_statement_with_recovery() {
+          statements.push(statement);
+        }
+        continue;
+      }
       let statement = self.parse_statement()?;
       if let Statement::Unknown = statement  {
         return Err(JSIError::new(JSIErrorType::Unknown, format!("unknown statement ast: {:?}", statement), 0, 0));
@@ -123,9 +238,80 @@ impl AST{
     return Ok(statements);
   }
 
+  // 解析语句，出错时记录错误，恢复解析状态并跳到下一个语句
+  fn parse_statement_with_recovery(&mut self) -> Option<Statement> {
+    let start_index = self.token_start_index;
+    let scope_depth = self.get_scope_depth();
+    let declarations_len = self.scope.declarations.len();
+    let bytecode_len = self.bytecode.len();
+    let token_stack_len = self.token_stack.len();
+    let mut err = match self.parse_statement() {
+      Ok(Statement::Unknown) => self.error_expected(String::from("statement")),
+      Ok(statement) => return Some(statement),
+      Err(err) => err,
+    };
+    self.fill_error_position(&mut err);
+    self.errors.push(err);
+    // 丢弃出错语句中打开的作用域、声明和 bytecode
+    while self.get_scope_depth() > scope_depth {
+      self.close_scope();
+    }
+    self.scope.declarations.truncate(declarations_len);
+    self.bytecode.truncate(bytecode_len);
+    self.token_stack.truncate(token_stack_len);
+    self.not_declare_function_to_scope = false;
+    // 至少跳过一个 token，避免在同一个位置重复报错
+    if self.token_start_index == start_index && self.token != Token::EOF && self.token != Token::RightBrace {
+      self.next();
+    }
+    self.skip_to_statement_boundary();
+    None
+  }
+
+  // 跳到下一个语句的开始：分号之后，或者语句关键字、} 之前
+  // 出错语句中成对的 {} 会整体跳过
+  fn skip_to_statement_boundary(&mut self) {
+    let mut brace_depth = 0;
+    loop {
+      match self.token {
+        Token::EOF => return,
+        Token::LeftBrace => brace_depth += 1,
+        Token::RightBrace => {
+          if brace_depth == 0 {
+            return;
+          }
+          brace_depth -= 1;
+        },
+        Token::Semicolon if brace_depth == 0 => {
+          self.next();
+          return;
+        },
+        Token::Var | Token::Let | Token::Const | Token::Function | Token::Class | Token::If | Token::For | Token::While | Token::Do | Token::Return | Token::Try | Token::Throw | Token::Switch | Token::Break | Token::Continue if brace_depth == 0 => return,
+        _ => {},
+      }
+      self.next();
+    }
+  }
+
+  // 当前作用域的层级
+  fn get_scope_depth(&self) -> usize {
+    let mut depth = 0;
+    let mut scope = &self.scope;
+    while let Some(parent) = &scope.parent {
+      depth += 1;
+      scope = parent;
+    }
+    depth
+  }
+
   // 解析生成 statement
   fn parse_statement(&mut self) -> JSIResult<Statement> {
+    self.parse_nested(AST::parse_statement_content)
+  }
+
+  fn parse_statement_content(&mut self) -> JSIResult<Statement> {
     // println!("parse_statement: {:?} {:?}", self.token,  self.literal);
+    let start_index = self.token_start_index;
     let statment = match self.token {
         Token::Var | Token::Let | Token::Const => self.parse_variable_statement(),
         Token::If => self.parse_if_statement(),
@@ -146,7 +332,8 @@ impl AST{
             let expression = self.parse_expression()?;
             // Put back 'async' as prefix
             Ok(Statement::Expression(ExpressionStatement{
-              expression
+              expression,
+              span: self.create_span(start_index),
             }))
           }
         },
@@ -187,6 +374,7 @@ impl AST{
                return Ok(Statement::Label(LabeledStatement {
                 label: identifier,
                 statement: Box::new(statement),
+                span: self.create_span(start_index),
                }));
             }
           }
@@ -197,7 +385,8 @@ impl AST{
               },
               _ => {
                 Ok(Statement::Expression(ExpressionStatement{
-                  expression
+                  expression,
+                  span: self.create_span(start_index),
                 }))
               }
           }
@@ -216,6 +405,7 @@ impl AST{
 
   // 解析 let / var
   fn parse_variable_statement(&mut self) -> JSIResult<Statement> {
+    let start_index = self.token_start_index;
     let mut variable_flag = VariableFlag::Var;
     if self.token == Token::Let {
       self.check_token_and_next(Token::Let)?;
@@ -230,6 +420,7 @@ impl AST{
     let var_statement = VariableDeclarationStatement {
       list: self.parse_variable_declarations()?,
       flag: variable_flag,
+      span: self.create_span(start_index),
     };
     self.semicolon()?;
     return Ok(Statement::Var(var_statement));
@@ -253,29 +444,28 @@ impl AST{
   // 解析 block statement
   fn parse_block_statement(&mut self) -> JSIResult<Statement> {
     // 以左花括号开始
+    let start_index = self.token_start_index;
     self.check_token_and_next(Token::LeftBrace)?;
     let statements = self.parse_statements()?;
     self.check_token_and_next(Token::RightBrace)?;
     return Ok(Statement::Block(BlockStatement{
       statements,
+      span: self.create_span(start_index),
     }))
   }
 
   // 解析 if/else/else if
   fn parse_if_statement(&mut self)  -> JSIResult<Statement> {
+    let start_index = self.token_start_index;
     self.check_token_and_next(Token::If)?;
     self.check_token_and_next(Token::LeftParenthesis)?;
-    
-    
-
-    let start_bc_index = self.bytecode.len() - 1;
-    
     let else_label_index = self.global_bc_index + 1;
     self.global_bc_index = else_label_index.clone();
     let mut statement = IfStatement {
       condition: self.parse_expression()?,
       then_statement: Box::new(Statement::Unknown),
       else_statement: Box::new(Statement::Unknown),
+      span: Span::default(),
     };
     self.bytecode.push(ByteCode {
       op: EByteCodeop::OpIfFalse,
@@ -318,12 +508,14 @@ impl AST{
 
     let end_label_index = self.global_bc_index;
     self.bytecode[else_goto_index].args = vec![end_label_index.to_string()];
+    statement.span = self.create_span(start_index);
     return Ok(Statement::If(statement))
   }
 
 
   // 解析 switch case
   fn parse_switch_statement(&mut self)  -> JSIResult<Statement> {
+    let start_index = self.token_start_index;
     self.check_token_and_next(Token::Switch)?;
     self.check_token_and_next(Token::LeftParenthesis)?;
     let condition = self.parse_expression()?;
@@ -335,9 +527,11 @@ impl AST{
       if self.token == Token::EOF || self.token == Token::RightBrace {
         break;
       }
+      let clause_start_index = self.token_start_index;
       let mut clause = CaseClause {
         condition: None,
         statements: vec![],
+        span: Span::default(),
       };
       // parse case
       if self.token == Token::Default {
@@ -359,6 +553,7 @@ impl AST{
         let statement = self.parse_statement()?;
         clause.statements.push(statement);
       }
+      clause.span = self.create_span(clause_start_index);
       clauses.push(clause);
     }
 
@@ -367,12 +562,14 @@ impl AST{
     Ok(Statement::Switch(SwitchStatement {
       condition,
       clauses,
-      default_index
+      default_index,
+      span: self.create_span(start_index),
     }))
   }
 
   // 解析 for 循环
   fn parse_for_statement(&mut self)  -> JSIResult<Statement> {
+    let start_index = self.token_start_index;
     self.check_token_and_next(Token::For)?;
     self.check_token_and_next(Token::LeftParenthesis)?;
 
@@ -381,6 +578,9 @@ impl AST{
     let mut is_var = false;
     let mut var_flag = VariableFlag::Var;
     let mut variable_name = String::new();
+    let initializer_start_index = self.token_start_index;
+    let mut variable_start_index = self.token_start_index;
+    let variable_span;
 
     if self.token == Token::Var || self.token == Token::Let || self.token == Token::Const {
       is_var = true;
@@ -394,16 +594,20 @@ impl AST{
       // Expect an identifier
       self.check_token(Token::Identifier)?;
       variable_name = self.literal.clone();
+      variable_start_index = self.token_start_index;
       self.next();
+      variable_span = self.create_span(variable_start_index);
     } else if self.token == Token::Identifier {
       // Simple identifier without var/let/const
       variable_name = self.literal.clone();
       self.next();
+      variable_span = self.create_span(variable_start_index);
     } else {
       // This is a regular for loop - parse initializer
       let mut initializer = Statement::Unknown;
       if self.token != Token::Semicolon {
-        initializer = Statement::Expression(ExpressionStatement { expression: self.parse_expression()? });
+        let expression = self.parse_expression()?;
+        initializer = Statement::Expression(ExpressionStatement { span: expression.span(), expression });
       }
       self.check_token_and_next(Token::Semicolon)?;
 
@@ -433,6 +637,7 @@ impl AST{
         incrementor: incrementor,
         statement: Box::new(block),
         post_judgment: false,
+        span: self.create_span(start_index),
       };
       return Ok(Statement::For(statement));
     }
@@ -448,9 +653,10 @@ impl AST{
       let statement = ForInStatement {
         is_var,
         var_flag,
-        variable: Expression::Identifier(IdentifierLiteral { literal: variable_name }),
+        variable: Expression::Identifier(IdentifierLiteral { literal: variable_name, span: variable_span }),
         object,
         statement: Box::new(block),
+        span: self.create_span(start_index),
       };
       return Ok(Statement::ForIn(statement));
     } else if self.token == Token::Of {
@@ -463,9 +669,10 @@ impl AST{
       let statement = ForOfStatement {
         is_var,
         var_flag,
-        variable: Expression::Identifier(IdentifierLiteral { literal: variable_name }),
+        variable: Expression::Identifier(IdentifierLiteral { literal: variable_name, span: variable_span }),
         object,
         statement: Box::new(block),
+        span: self.create_span(start_index),
       };
       return Ok(Statement::ForOf(statement));
     } else {
@@ -477,34 +684,41 @@ impl AST{
         // We had var/let/const x, now check for initializer
         let mut var_decl = VariableDeclaration {
           name: variable_name.clone(),
-          initializer: Box::new(Expression::Keyword(Keywords::Undefined)),
+          initializer: Box::new(Expression::Keyword(Keywords::Undefined, Span::default())),
+          span: variable_span,
         };
 
         if self.token == Token::Assign {
           self.next();
           var_decl.initializer = Box::new(self.parse_expression()?);
+          var_decl.span = self.create_span(variable_start_index);
         }
 
         initializer = Statement::Var(VariableDeclarationStatement {
           list: vec![Expression::Var(var_decl)],
           flag: var_flag,
+          span: self.create_span(initializer_start_index),
         });
       } else {
         // We had just an identifier, it's an expression
         if self.token == Token::Assign {
-          let left = Expression::Identifier(IdentifierLiteral { literal: variable_name });
+          let left = Expression::Identifier(IdentifierLiteral { literal: variable_name, span: variable_span });
           self.next();
           let right = self.parse_expression()?;
+          let span = self.create_span(variable_start_index);
           initializer = Statement::Expression(ExpressionStatement {
             expression: Expression::Assign(AssignExpression {
               left: Box::new(left),
               operator: Token::Assign,
               right: Box::new(right),
+              span,
             }),
+            span,
           });
         } else {
           initializer = Statement::Expression(ExpressionStatement {
-            expression: Expression::Identifier(IdentifierLiteral { literal: variable_name }),
+            expression: Expression::Identifier(IdentifierLiteral { literal: variable_name, span: variable_span }),
+            span: variable_span,
           });
         }
       }
@@ -525,6 +739,7 @@ impl AST{
         incrementor: incrementor,
         statement: Box::new(block),
         post_judgment: false,
+        span: self.create_span(start_index),
       };
       return Ok(Statement::For(statement));
     }
@@ -532,6 +747,7 @@ impl AST{
 
   // 解析 while 循环
   fn parse_while_statement(&mut self)  -> JSIResult<Statement> {
+    let start_index = self.token_start_index;
     self.check_token_and_next(Token::While)?;
     self.check_token_and_next(Token::LeftParenthesis)?;
     self.not_declare_function_to_scope = true;
@@ -546,6 +762,7 @@ impl AST{
       incrementor: Expression::Unknown,
       statement: Box::new(block),
       post_judgment: false,
+      span: self.create_span(start_index),
     };
     return  Ok(Statement::For(statement));
   }
@@ -553,6 +770,7 @@ impl AST{
 
   // 解析 do while 循环
   fn parse_do_while_statement(&mut self)  -> JSIResult<Statement> {
+    let start_index = self.token_start_index;
     self.check_token_and_next(Token::Do)?;
     let block = self.parse_block_statement()?;
     self.check_token_and_next(Token::While)?;
@@ -567,12 +785,14 @@ impl AST{
       incrementor: Expression::Unknown,
       statement: Box::new(block),
       post_judgment: true,
+      span: self.create_span(start_index),
     };
     return  Ok(Statement::For(statement));
   }
 
 
   fn parse_break_statement(&mut self) -> JSIResult<Statement> {
+    let start_index = self.token_start_index;
     self.check_token_and_next(Token::Break)?;
     let mut semicolon = false;
     // break;
@@ -592,19 +812,23 @@ impl AST{
       }
       */
       return Ok(Statement::Break(BreakStatement {
-        label: None
+        label: None,
+        span: self.create_span(start_index),
       }));
     }
 
     self.check_token(Token::Identifier)?;
     let label = self.literal.clone();
+    let label_start_index = self.token_start_index;
     self.next();  // 跳过标签标识符
     return  Ok(Statement::Break(BreakStatement {
-      label: Some(IdentifierLiteral { literal: label })
+      label: Some(IdentifierLiteral { literal: label, span: self.create_span(label_start_index) }),
+      span: self.create_span(start_index),
     }));
   }
 
   fn parse_continue_statement(&mut self) -> JSIResult<Statement> {
+    let start_index = self.token_start_index;
     self.check_token_and_next(Token::Continue)?;
     let mut semicolon = false;
     // continue;
@@ -616,20 +840,24 @@ impl AST{
     // for() { continue }
     if semicolon || self.token == Token::RightBrace {
       return Ok(Statement::Continue(ContinueStatement {
-        label: None
+        label: None,
+        span: self.create_span(start_index),
       }));
     }
 
     self.check_token(Token::Identifier)?;
     let label = self.literal.clone();
+    let label_start_index = self.token_start_index;
     self.next();  // 跳过标签标识符
     return  Ok(Statement::Continue(ContinueStatement {
-      label: Some(IdentifierLiteral { literal: label })
+      label: Some(IdentifierLiteral { literal: label, span: self.create_span(label_start_index) }),
+      span: self.create_span(start_index),
     }));
   }
 
   // 解析 function statement
   fn parse_function(&mut self, variable_lifting: bool, is_async: bool) -> JSIResult<FunctionDeclaration> {
+    let start_index = self.token_start_index;
     // 如果是 function 关键字，则跳过
     if self.token == Token::Function {
       self.next();
@@ -638,10 +866,13 @@ impl AST{
     // 解析方法名
     let mut is_anonymous = true;
     let mut name = String::new();
+    let mut name_span = Span::default();
     if self.token == Token::Identifier {
       is_anonymous = false;
       name = self.literal.clone();
+      let name_start_index = self.token_start_index;
       self.next();
+      name_span = self.create_span(name_start_index);
     }
     let function_index = self.global_bc_index + 1;
     self.global_bc_index = function_index;
@@ -659,9 +890,11 @@ impl AST{
       if self.token == Token::Identifier {
         let literal = self.literal.clone();
         self.check_function_parameters_duplicate(&mut parameters_names, &literal)?;
+        let span = self.create_span_between(self.token_start_index, self.cur_char_index);
         parameters.push(Parameter{
-          name: IdentifierLiteral { literal: literal.clone() },
-          initializer: Box::new(Expression::Keyword(Keywords::Undefined)),
+          name: IdentifierLiteral { literal: literal.clone(), span },
+          initializer: Box::new(Expression::Keyword(Keywords::Undefined, Span::default())),
+          span,
         });
         self.bytecode.push(ByteCode {
           op: EByteCodeop::OpGetArg,
@@ -699,7 +932,7 @@ impl AST{
     let body_statement = self.parse_block_statement()?;
     let body = match body_statement {
       Statement::Block(block) => block,
-      _ => BlockStatement { statements: vec![] }
+      _ => BlockStatement { statements: vec![], span: Span::default() }
     };
     let declarations = self.scope.declarations.clone();
     self.close_scope();
@@ -707,11 +940,12 @@ impl AST{
       is_anonymous,
       is_arrow: false,
       is_async,
-      name: IdentifierLiteral { literal: name },
+      name: IdentifierLiteral { literal: name, span: name_span },
       parameters,
       body,
       declarations,
       bytecode: vec![],
+      span: self.create_span(start_index),
     };
     if variable_lifting && !is_anonymous && !self.not_declare_function_to_scope {
       self.scope.declare(Declaration::Function(func.clone()));
@@ -738,13 +972,13 @@ impl AST{
     return Ok(true);
   }
 
-  fn parse_arrow_function(&mut self, params: Expression) -> JSIResult<Expression> {
+  fn parse_arrow_function(&mut self, params: Expression, start_index: usize) -> JSIResult<Expression> {
     let mut parameters: Vec<Parameter> = vec![];
     let mut parameters_names: HashMap<String, bool> = HashMap::new();
     match  params {
       Expression::Identifier(iden) => {
         self.check_function_parameters_duplicate(&mut parameters_names, &iden.literal)?;
-        parameters.push(Parameter { name: iden.to_owned(), initializer: Box::new(Expression::Keyword(Keywords::Undefined)) });
+        parameters.push(Parameter { name: iden.to_owned(), initializer: Box::new(Expression::Keyword(Keywords::Undefined, Span::default())), span: iden.span });
       },
       // TODO: assign
       Expression::Sequence(sequence) => {
@@ -752,7 +986,7 @@ impl AST{
           match expr {
             Expression::Identifier(iden) => {
               self.check_function_parameters_duplicate(&mut parameters_names, &iden.literal)?;
-              parameters.push(Parameter { name: iden.to_owned(), initializer: Box::new(Expression::Keyword(Keywords::Undefined)) });
+              parameters.push(Parameter { name: iden.to_owned(), initializer: Box::new(Expression::Keyword(Keywords::Undefined, Span::default())), span: iden.span });
             },
             // TODO: assign
             _ => {}
@@ -797,7 +1031,7 @@ impl AST{
       let body_statement = self.parse_block_statement()?;
       let body = match body_statement {
         Statement::Block(block) => block,
-        _ => BlockStatement { statements: vec![] }
+        _ => BlockStatement { statements: vec![], span: Span::default() }
       };
       let declarations = self.scope.declarations.clone();
       self.close_scope();
@@ -811,15 +1045,17 @@ impl AST{
         is_anonymous: true,
         is_arrow: true,
         is_async,
-        name: IdentifierLiteral { literal: String::new() },
+        name: IdentifierLiteral { literal: String::new(), span: Span::default() },
         parameters,
         body,
         declarations,
         bytecode: vec![],
+        span: self.create_span(start_index),
       };
       Ok(Expression::Function(func))
     } else {
       let expr = self.parse_expression()?;
+      let expr_span = expr.span();
       // 生成 OpReturn (箭头函数表达式体需要返回值)
       self.bytecode.push(ByteCode {
         op: EByteCodeop::OpReturn,
@@ -836,13 +1072,14 @@ impl AST{
         is_anonymous: true,
         is_arrow: true,
         is_async,
-        name: IdentifierLiteral { literal: String::new() },
+        name: IdentifierLiteral { literal: String::new(), span: Span::default() },
         parameters,
         body: BlockStatement { statements: vec![
-          Statement::Return(ReturnStatement { expression: expr }),
-        ] },
+          Statement::Return(ReturnStatement { expression: expr, span: expr_span }),
+        ], span: expr_span },
         declarations: vec![],
         bytecode: vec![],
+        span: self.create_span(start_index),
       };
       Ok(Expression::Function(func))
     }
@@ -851,11 +1088,14 @@ impl AST{
 
   // 解析 class(ES2015)
   fn parse_class(&mut self) -> JSIResult<ClassDeclaration> {
+    let start_index = self.token_start_index;
     self.check_token_and_next(Token::Class)?;
     // class name
     self.check_token(Token::Identifier)?;
     let name = self.literal.clone();
+    let name_start_index = self.token_start_index;
     self.next();
+    let name_span = self.create_span(name_start_index);
     // extends
     if self.token == Token::Extends {
       // TODO: 解析 extends
@@ -890,6 +1130,7 @@ impl AST{
           members.push(Expression::ClassMethod(ClassMethodDeclaration {
             name: method.name.clone(),
             modifiers,
+            span: method.span,
             method: Box::new(method),
           }));
         } else {
@@ -903,36 +1144,42 @@ impl AST{
       }
     }
     Ok(ClassDeclaration {
-      name: IdentifierLiteral { literal: name },
+      name: IdentifierLiteral { literal: name, span: name_span },
       members,
       heritage: None,
+      span: self.create_span(start_index),
     })
   }
 
   fn parse_throw_statement(&mut self) -> JSIResult<Statement> {
+    let start_index = self.token_start_index;
     self.check_token_and_next(Token::Throw)?;
     let expression = self.parse_expression()?;
     Ok(Statement::Throw(ThrowStatement {
-      expression
+      expression,
+      span: self.create_span(start_index),
     }))
   }
 
   fn parse_try_catch_statment(&mut self) -> JSIResult<Statement> {
+    let start_index = self.token_start_index;
     self.check_token_and_next(Token::Try)?;
 
     let body_statement = self.parse_block_statement()?;
     let body = match body_statement {
       Statement::Block(block) => block,
-      _ => BlockStatement { statements: vec![] }
+      _ => BlockStatement { statements: vec![], span: Span::default() }
     };
 
     let mut try_statment = TryCatchStatement {
       body,
       catch: None,
-      finally: None
+      finally: None,
+      span: Span::default(),
     };
 
     if self.token == Token::Catch {
+      let catch_start_index = self.token_start_index;
       self.check_token_and_next(Token::Catch)?;
       let mut identifier = None;
       if self.token == Token::LeftParenthesis {
@@ -947,38 +1194,44 @@ impl AST{
       let body_statement = self.parse_block_statement()?;
       let body = match body_statement {
         Statement::Block(block) => block,
-        _ => BlockStatement { statements: vec![] }
+        _ => BlockStatement { statements: vec![], span: Span::default() }
       };
-      try_statment.catch = Some(CatchClause { declaration: identifier, body })
+      try_statment.catch = Some(CatchClause { declaration: identifier, body, span: self.create_span(catch_start_index) })
     }
 
     // TODO: finally
+    try_statment.span = self.create_span(start_index);
     Ok(Statement::Try(try_statment))
   }
 
   fn parse_return_statement(&mut self) -> JSIResult<Statement> {
+    let start_index = self.token_start_index;
     self.check_token_and_next(Token::Return)?;
-    let mut expression = Expression::Keyword(Keywords::Undefined);
+    let mut expression = Expression::Keyword(Keywords::Undefined, Span::default());
     if  !self.auto_semicolon_when_new_line && self.token != Token::Semicolon && self.token != Token::RightBrace && self.token != Token::EOF {
       expression = self.parse_expression()?
     }
+    let span = self.create_span(start_index);
     self.semicolon()?;
     self.bytecode.push(ByteCode { op: EByteCodeop::OpReturn, args: vec![], line: 0 });
     return Ok(Statement::Return(ReturnStatement{
-      expression
+      expression,
+      span,
     }));
   }
 
   // 解析变量定义 a = 123,b,c = true 
   fn parse_variable_declaration(&mut self) -> JSIResult<Expression> {
     if Token::Identifier != self.token {
-      return Err(self.error_unexpected());
+      return Err(self.error_expected(get_token_description(&Token::Identifier)));
     }
     let literal = self.literal.clone();
+    let start_index = self.token_start_index;
     self.next();
     let mut node = VariableDeclaration{
       name: literal,
-      initializer: Box::new(Expression::Keyword(Keywords::Undefined)),
+      initializer: Box::new(Expression::Keyword(Keywords::Undefined, Span::default())),
+      span: self.create_span(start_index),
     };
 
     self.bytecode.push(ByteCode {
@@ -1000,6 +1253,7 @@ impl AST{
         args: vec![node.name.clone()],
         line: 0,
       });
+      node.span = self.create_span(start_index);
     }
     return Ok(Expression::Var(node))
   }
@@ -1011,7 +1265,7 @@ impl AST{
   }
   fn check_token(&mut self, token: Token) -> JSIResult<bool> {
     if token != self.token {
-      return Err(self.error_unexpected());
+      return Err(self.error_expected(get_token_description(&token)));
     }
     return Ok(true);
   }
@@ -1028,6 +1282,7 @@ impl AST{
 
   // 获取下一个符号
   fn next(&mut self) {
+    self.prev_token_end_index = self.cur_char_index;
     let scan_res = self.scan();
     self.token = scan_res.0;
     self.literal = scan_res.1;
@@ -1065,6 +1320,7 @@ impl AST{
     self.auto_semicolon_when_new_line = false;
     loop {
       self.skip_white_space();
+      self.token_start_index = self.cur_char_index;
       if self.cur_char_index >= self.length {
         // 扫描结束了
         self.pre_token_need_semicolon = false;
@@ -1631,6 +1887,7 @@ impl AST{
 
   // 解析逗号运算符，虽然优先级最高，但是一般只在匹配到左括号/左中括号时调用
   fn parse_comma_expression(&mut self) -> JSIResult<Expression> {
+    let start_index = self.token_start_index;
     let left = self.parse_expression()?;
     if self.token == Token::Comma {
       let mut exprs: Vec<Expression> = vec![left];
@@ -1643,7 +1900,8 @@ impl AST{
         exprs.push(next_expr);
       }
       return Ok(Expression::Sequence(SequenceExpression {
-        expressions: exprs
+        expressions: exprs,
+        span: self.create_span(start_index),
       }));
     }
     return Ok(left);
@@ -1652,12 +1910,18 @@ impl AST{
   // 解析赋值运算符，优先级 2，从右到左
   // https://tc39.es/ecma262/multipage/ecmascript-language-expressions.html#sec-assignment-operators
   fn parse_assignment_expression(&mut self) -> JSIResult<Expression> {
+    self.parse_nested(AST::parse_assignment_expression_content)
+  }
+
+  fn parse_assignment_expression_content(&mut self) -> JSIResult<Expression> {
+    let start_index = self.token_start_index;
     let left = self.parse_conditional_expression()?;
     match self.token {
       Token::Assign | Token::AddAssign | Token::SubtractAssign | Token::MultiplyAssign | Token::SlashAssign | Token::RemainderAssign | Token::ShiftLeftAssign | Token::ShiftRightAssign | Token::UnsignedShiftRightAssign | Token::OrAssign | Token::AndAssign | Token::ExclusiveOrAssign | Token::LogicalAndAssign | Token::LogicalOrAssign | Token::ExponentiationAssign | Token::NullishCoalescingAssign =>  {
 
         if !left.is_assignment_target_type() {
-          return Err(JSIError::new(JSIErrorType::SyntaxError, String::from("Invalid left-hand side in assignment"), 0, 0))
+          let (line, column) = self.get_position(start_index);
+          return Err(JSIError::new(JSIErrorType::SyntaxError, String::from("Invalid left-hand side in assignment"), line as i32, column as i32))
         }
 
         // 跳过各种赋值运算符
@@ -1678,6 +1942,7 @@ impl AST{
           left: Box::new(left),
           operator: oper,
           right: Box::new(right),
+          span: self.create_span(start_index),
         }));
       },
       _ => Ok(left)
@@ -1687,6 +1952,7 @@ impl AST{
   // 解析三目运算符，优先级 3，从右到左
   // https://tc39.es/ecma262/multipage/ecmascript-language-expressions.html#sec-conditional-operator
   fn parse_conditional_expression(&mut self) -> JSIResult<Expression> {
+    let start_index = self.token_start_index;
     let left = self.parse_binary_logical_expression()?;
     if self.token == Token::QuestionMark {
       // 跳过 ?
@@ -1701,6 +1967,7 @@ impl AST{
         condition: Box::new(left),
         when_true: Box::new(when_true),
         when_false: Box::new(when_false),
+        span: self.create_span(start_index),
       }));
     }
     return Ok(left)
@@ -1828,15 +2095,17 @@ impl AST{
   // 幂运算 1**2 -- 优先级 14，从右到左
   // ref: https://tc39.es/ecma262/multipage/ecmascript-language-expressions.html#sec-unary-operators
   fn parse_exponentiation_expression(&mut self) -> JSIResult<Expression> {
+    let start_index = self.token_start_index;
     let left = self.parse_prefix_unary_expression()?;
     if self.token == Token::Exponentiation {
       let operator = self.token.clone();
       self.next();
-      let right = self.parse_exponentiation_expression()?;
+      let right = self.parse_nested(AST::parse_exponentiation_expression)?;
       Ok(Expression::Binary(BinaryExpression {
         left: Box::new(left),
         operator,
         right: Box::new(right),
+        span: self.create_span(start_index),
       }))
     } else {
       Ok(left)
@@ -1845,12 +2114,13 @@ impl AST{
   // 前置一元运算符  -- 优先级 15，从右到左
   // ref: https://tc39.es/ecma262/multipage/ecmascript-language-expressions.html#sec-unary-operators
   fn parse_prefix_unary_expression(&mut self) -> JSIResult<Expression> {
+    let start_index = self.token_start_index;
     match self.token {
       Token::Not | Token::BitwiseNot | Token::Plus | Token::Subtract => {
         let operator = self.token.clone();
         self.next();
         // 递归调用 parse_prefix_unary_expression 以支持 !!x 等嵌套
-        let operand = self.parse_prefix_unary_expression()?;
+        let operand = self.parse_nested(AST::parse_prefix_unary_expression)?;
         self.bytecode.push(ByteCode {
           op: EByteCodeop::OpPrefixUnary,
           args: vec![operator.to_string()],
@@ -1859,13 +2129,14 @@ impl AST{
         Ok(Expression::PrefixUnary(PrefixUnaryExpression {
           operator,
           operand: Box::new(operand),
+          span: self.create_span(start_index),
         }))
       },
       Token::Typeof | Token::Void | Token::Delete | Token::Await => {
         let operator = self.token.clone();
         self.next();
         // 递归调用 parse_prefix_unary_expression 以支持 typeof void 0 等嵌套
-        let operand = self.parse_prefix_unary_expression()?;
+        let operand = self.parse_nested(AST::parse_prefix_unary_expression)?;
         self.bytecode.push(ByteCode {
           op: EByteCodeop::OpPrefixUnary,
           args: vec![operator.to_string()],
@@ -1874,6 +2145,7 @@ impl AST{
         Ok(Expression::PrefixUnary(PrefixUnaryExpression {
           operator,
           operand: Box::new(operand),
+          span: self.create_span(start_index),
         }))
       },
       Token::Increment | Token::Decrement => {
@@ -1889,6 +2161,7 @@ impl AST{
         Ok(Expression::PrefixUnary(PrefixUnaryExpression {
           operator,
           operand: Box::new(operand),
+          span: self.create_span(start_index),
         }))
       },
       _ => self.parse_postfix_unary_expression()
@@ -1898,22 +2171,24 @@ impl AST{
   // 后置一元运算符 ++ -- 优先级 16
   // ref: https://tc39.es/ecma262/multipage/ecmascript-language-expressions.html#sec-update-expressions
   fn parse_postfix_unary_expression(&mut self) -> JSIResult<Expression> {
+    let start_index = self.token_start_index;
     let left = self.parse_left_hand_side_expression()?;
     if self.token == Token::Increment || self.token == Token::Decrement {
       if self.auto_semicolon_when_new_line {
         return Ok(left)
       }
-      let expr = Expression::PostfixUnary(PostfixUnaryExpression {
-        operator: self.token.clone(),
-        operand: Box::new(left),
-      });
+      let operator = self.token.clone();
       self.bytecode.push(ByteCode {
         op: EByteCodeop::OpPostfixUnary,
-        args: vec![self.token.to_string()],
+        args: vec![operator.to_string()],
         line: 0,
       });
       self.next();
-      Ok(expr)
+      Ok(Expression::PostfixUnary(PostfixUnaryExpression {
+        operator,
+        operand: Box::new(left),
+        span: self.create_span(start_index),
+      }))
     } else {
       Ok(left)
     }
@@ -1924,7 +2199,8 @@ impl AST{
   fn parse_left_hand_side_expression(&mut self) -> JSIResult<Expression> {
     // 记录当前 bytecode 长度，用于箭头函数参数清理
     let bytecode_len_before = self.bytecode.len();
-    let mut left = self.parse_group_expression()?;
+    let start_index = self.token_start_index;
+    let left = self.parse_group_expression()?;
     if self.token == Token::Assign && self.char == '>' {
       if self.auto_semicolon_when_new_line {
         return Err(JSIError::new(JSIErrorType::SyntaxError, format!("Unexpected token '=>'"), 0, 0));
@@ -1937,19 +2213,30 @@ impl AST{
       }
       self.next();
       self.next();
-      return self.parse_arrow_function(left);
+      return self.parse_arrow_function(left, start_index);
     }
+    // 属性访问、调用链同样计入嵌套深度
+    let depth = self.nesting_depth;
+    let result = self.parse_access_chain(left, start_index);
+    self.nesting_depth = depth;
+    result
+  }
+
+  fn parse_access_chain(&mut self, mut left: Expression, start_index: usize) -> JSIResult<Expression> {
     loop {
+      if let Token::Period | Token::LeftBracket | Token::LeftParenthesis | Token::New = self.token {
+        self.enter_nesting()?;
+      }
       self.cur_expr = left.clone();
       let new_left = match self.token {
-        Token::Period => self.parse_property_access_expression()?,
-        Token::LeftBracket => self.parse_element_access_expression()?,
+        Token::Period => self.parse_property_access_expression(start_index)?,
+        Token::LeftBracket => self.parse_element_access_expression(start_index)?,
         Token::LeftParenthesis => {
           let last_stack_token = self.token_stack.last();
           if let Some(Token::New) = last_stack_token {
             break;
           }
-          self.parse_call_expression()?
+          self.parse_call_expression(start_index)?
         },
         Token::New => self.parse_new_expression()?,
         // TODO: new
@@ -1962,17 +2249,18 @@ impl AST{
       left = new_left;
     }
     if let Expression::Unknown = left {
-      return Err(self.error_unexpected());
+      return Err(self.error_expected(String::from("expression")));
     }
     return Ok(left);
   }
   // 解析属性访问(.)语法 优先级 18
-  fn parse_property_access_expression(&mut self) -> JSIResult<Expression> {
+  fn parse_property_access_expression(&mut self, start_index: usize) -> JSIResult<Expression> {
     self.next();
     if self.token == Token::Number {
-      return Err(JSIError::new(JSIErrorType::SyntaxError, String::from("Unexpected number"), 0, 0))
+      return Err(self.error_expected(String::from("property name")))
     }
     let literal = self.literal.clone();
+    let name_start_index = self.token_start_index;
     self.next();
     self.bytecode.push(ByteCode{
       op: EByteCodeop::OpGetProperty,
@@ -1981,12 +2269,13 @@ impl AST{
     });
     return Ok(Expression::PropertyAccess(PropertyAccessExpression{
       expression: Box::new(self.cur_expr.clone()),
-      name: IdentifierLiteral { literal }
+      name: IdentifierLiteral { literal, span: self.create_span(name_start_index) },
+      span: self.create_span(start_index),
     }));
   }
 
   // 解析属性访问([)语法 优先级 18
-  fn parse_element_access_expression(&mut self) -> JSIResult<Expression> {
+  fn parse_element_access_expression(&mut self, start_index: usize) -> JSIResult<Expression> {
     let expression = Box::new(self.cur_expr.clone());
     self.check_token_and_next(Token::LeftBracket)?;
     let expr = self.parse_expression()?;
@@ -1994,30 +2283,34 @@ impl AST{
     return Ok(Expression::ElementAccess(ElementAccessExpression{
       expression,
       argument: Box::new(expr),
+      span: self.create_span(start_index),
     }));
   }
 
   // 解析属方法调用语法 优先级 18
   // ref: https://tc39.es/ecma262/multipage/ecmascript-language-expressions.html#sec-function-calls
-  fn parse_call_expression(&mut self) -> JSIResult<Expression> {
+  fn parse_call_expression(&mut self, start_index: usize) -> JSIResult<Expression> {
     // 1. 解析参数
     let expression = Box::new(self.cur_expr.clone());
     let arguments = self.parse_arguments()?;
     // CallExpression {}
     self.check_token_and_next(Token::RightParenthesis)?;
+    let span = self.create_span(start_index);
     self.bytecode.push(ByteCode{
       op: EByteCodeop::OpCall,
       args: vec![arguments.len().to_string()],
-      line: 0,
+      line: span.line,
     });
     return Ok(Expression::Call(CallExpression {
       expression,
-      arguments
+      arguments,
+      span,
     }));
   }
 
   // 解析 new 语法 优先级 18
   fn parse_new_expression(&mut self) -> JSIResult<Expression> {
+    let start_index = self.token_start_index;
     self.token_stack.push(self.token.clone());
     self.next();
     let  expression = self.parse_expression()?;
@@ -2027,15 +2320,23 @@ impl AST{
       args = self.parse_arguments()?;
       self.check_token_and_next(Token::RightParenthesis)?;
     }
+    let span = self.create_span(start_index);
+    self.bytecode.push(ByteCode{
+      op: EByteCodeop::OpNew,
+      args: vec![args.len().to_string()],
+      line: span.line,
+    });
     return Ok(Expression::New(NewExpression {
       expression: Box::new(expression),
-      arguments: args
+      arguments: args,
+      span,
     }))
   }
 
   // 解析分组表达式 优先级 19
   // ref: https://tc39.es/ecma262/multipage/ecmascript-language-expressions.html#sec-function-calls
   fn parse_group_expression(&mut self) -> JSIResult<Expression> {
+     let start_index = self.token_start_index;
      if self.token == Token::LeftParenthesis {
       self.next();
       let expr = self.parse_comma_expression()?;
@@ -2045,6 +2346,7 @@ impl AST{
       }
       return Ok(Expression::Group(GroupExpression {
         expression: Box::new(expr),
+        span: self.create_span(start_index),
       }))
      }
      self.parse_literal_expression()
@@ -2053,6 +2355,7 @@ impl AST{
   // 解析字面量 优先级 20 最后处理
   fn parse_literal_expression(&mut self) -> JSIResult<Expression> {
     // println!("parse_literal_expression {:?}", self.token);
+    let start_index = self.token_start_index;
     let literal = self.literal.clone();
     match self.token {
       Token::Identifier => {
@@ -2063,7 +2366,8 @@ impl AST{
           line: 0,
         });
         Ok(Expression::Identifier(IdentifierLiteral{
-          literal
+          literal,
+          span: self.create_span(start_index),
         }))
       },
       Token::Number => {
@@ -2077,6 +2381,7 @@ impl AST{
         Ok(Expression::Number(NumberLiteral {
           literal,
           value,
+          span: self.create_span(start_index),
         }))
       },
       Token::String => {
@@ -2088,17 +2393,32 @@ impl AST{
           String::from("")
         };
         // 处理字符串转义序列
-        let slice = process_string_escapes(&raw_slice);
+        let units = process_string_escapes(&raw_slice);
         self.next();
-        self.bytecode.push(ByteCode{
-          op: EByteCodeop::OpString,
-          args: vec![slice.clone()],
-          line: 0,
-        });
-        Ok(Expression::String(StringLiteral{
-          literal,
-          value: slice
-        }))
+        match String::from_utf16(&units) {
+          Ok(slice) => {
+            self.bytecode.push(ByteCode{
+              op: EByteCodeop::OpString,
+              args: vec![slice.clone()],
+              line: 0,
+            });
+            Ok(Expression::String(StringLiteral{
+              literal,
+              value: slice,
+              span: self.create_span(start_index),
+            }))
+          },
+          Err(_) => {
+            // 包含单独的代理项，以 UTF-16 编码单元存储
+            let units_arg = units.iter().map(|unit| unit.to_string()).collect::<Vec<String>>().join(",");
+            self.bytecode.push(ByteCode{
+              op: EByteCodeop::OpString,
+              args: vec![String::from_utf16_lossy(&units), units_arg],
+              line: 0,
+            });
+            Ok(Expression::Value(Box::new(Value::Utf16String(units)), self.create_span(start_index)))
+          },
+        }
       },
       Token::Backtick => {
         self.parse_template_litreal()
@@ -2110,7 +2430,7 @@ impl AST{
           args: vec![],
           line: 0,
         });
-        Ok(Expression::Keyword(Keywords::False))
+        Ok(Expression::Keyword(Keywords::False, self.create_span(start_index)))
       },
       Token::True => {
         self.next();
@@ -2119,7 +2439,7 @@ impl AST{
           args: vec![],
           line: 0,
         });
-        Ok(Expression::Keyword(Keywords::True))
+        Ok(Expression::Keyword(Keywords::True, self.create_span(start_index)))
       },
       Token::Null => {
         self.next();
@@ -2128,7 +2448,7 @@ impl AST{
           args: vec![],
           line: 0,
         });
-        Ok(Expression::Keyword(Keywords::Null))
+        Ok(Expression::Keyword(Keywords::Null, self.create_span(start_index)))
       },
       Token::Undefined => {
         self.next();
@@ -2137,11 +2457,11 @@ impl AST{
           args: vec![],
           line: 0,
         });
-        Ok(Expression::Keyword(Keywords::Undefined))
+        Ok(Expression::Keyword(Keywords::Undefined, self.create_span(start_index)))
       },
       Token::This => {
         self.next();
-        Ok(Expression::Keyword(Keywords::This))
+        Ok(Expression::Keyword(Keywords::This, self.create_span(start_index)))
       },
       Token::LeftBrace => {
         self.parse_object_literal()
@@ -2164,7 +2484,7 @@ impl AST{
             self.scope.is_async = true;
             self.next();
             self.next();
-            self.parse_arrow_function(expr)
+            self.parse_arrow_function(expr, start_index)
           } else {
             // Just 'async' identifier followed by expression
             Ok(expr)
@@ -2182,12 +2502,13 @@ impl AST{
 
   // 解析数组字面量
   fn parse_array_literal(&mut self) -> JSIResult<Expression> {
+    let start_index = self.token_start_index;
     self.check_token_and_next(Token::LeftBracket)?;
     let mut elements: Vec<Expression>= vec![];
     while self.token != Token::RightBracket && self.token != Token::EOF {
       // [,,1]
       if self.token == Token::Comma {
-        elements.push(Expression::Keyword(Keywords::Undefined));
+        elements.push(Expression::Keyword(Keywords::Undefined, Span::default()));
         self.next();
         continue;
       }
@@ -2205,17 +2526,20 @@ impl AST{
     });
 
     Ok(Expression::Array(ArrayLiteral {
-      elements
+      elements,
+      span: self.create_span(start_index),
     }))
   }
   // 解析对象字面量
   // https://tc39.es/ecma262/multipage/ecmascript-language-expressions.html#prod-ObjectLiteral
   fn parse_object_literal(&mut self) -> JSIResult<Expression> {
+    let start_index = self.token_start_index;
     self.check_token_and_next(Token::LeftBrace)?;
     let mut properties: Vec<PropertyAssignment>= vec![];
     let mut property_count = 0;
     while self.token != Token::RightBrace && self.token != Token::EOF {
       // 属性名
+      let property_start_index = self.token_start_index;
       let mut property_name = self.parse_object_property_name()?;
       if let Expression::Unknown = property_name {
         break;
@@ -2232,7 +2556,7 @@ impl AST{
         _ => {
           // Shorthand property names (ES2015)
           if let Expression::Identifier(property) = property_name.clone() {
-            Expression::Identifier(IdentifierLiteral { literal: property.literal } )
+            Expression::Identifier(IdentifierLiteral { literal: property.literal, span: property.span } )
           } else {
             // TODO: throw error
             Expression::Unknown
@@ -2266,12 +2590,14 @@ impl AST{
         property_name = Expression::String(StringLiteral {
           literal: property.literal.clone(),
           value: property.literal,
+          span: property.span,
         });
       }
 
       properties.push(PropertyAssignment {
         name: Box::new(property_name),
         initializer: Box::new(initializer),
+        span: self.create_span(property_start_index),
       });
       // 跳过逗号
       if self.token == Token::Comma {
@@ -2289,16 +2615,19 @@ impl AST{
 
     Ok(Expression::Object(ObjectLiteral {
       properties,
+      span: self.create_span(start_index),
     }))
   }
 
   fn parse_object_property_name(&mut self) -> JSIResult<Expression> {
+    let start_index = self.token_start_index;
     let property_name_literal = self.literal.clone();
     match self.token {
       Token::Identifier => {
         self.next();
         Ok(Expression::Identifier(IdentifierLiteral {
           literal: property_name_literal,
+          span: self.create_span(start_index),
         }))
       },
       Token::String => {
@@ -2308,19 +2637,20 @@ impl AST{
         Ok(Expression::String(StringLiteral {
           literal: property_name_literal,
           value: slice,
+          span: self.create_span(start_index),
         }))
       },
       Token::Number => {
         let number_value = self.parse_number_literal_expression()?;
         self.next();
-        Ok(Expression::Number(NumberLiteral { literal: property_name_literal, value: number_value }))
+        Ok(Expression::Number(NumberLiteral { literal: property_name_literal, value: number_value, span: self.create_span(start_index) }))
       },
       // Computed property names (ES2015)
       Token::LeftBracket => {
         self.next();
         let key = self.parse_expression()?;
         self.check_token_and_next(Token::RightBracket)?;
-        Ok(Expression::ComputedPropertyName(ComputedPropertyName { expression: Box::new(key) }))
+        Ok(Expression::ComputedPropertyName(ComputedPropertyName { expression: Box::new(key), span: self.create_span(start_index) }))
       },
       _ => {
         // TODO: Err
@@ -2329,20 +2659,35 @@ impl AST{
     }
   }
 
+  // 字符串模板中的字符串片段，需要处理转义序列
+  fn create_template_string_span(&self, literal: String, span: Span) -> Expression {
+    let units = process_string_escapes(&literal);
+    match String::from_utf16(&units) {
+      Ok(value) => Expression::String(StringLiteral { literal, value, span }),
+      Err(_) => Expression::Value(Box::new(Value::Utf16String(units)), span),
+    }
+  }
+
   // 解析字符串模板
   fn parse_template_litreal(&mut self) -> JSIResult<Expression> {
+    let start_index = self.token_start_index;
     let mut spans: Vec<Expression> = vec![];
     let mut pre_char_start_index = self.cur_char_index;
     while self.char != '`' {
-      // TODO: `\`${xxx}\``
-      if !self.read() {
-        break;
+      // 转义字符（如 \` 和 \$）原样保留，由 create_template_string_span 处理
+      if self.char == '\\' {
+        self.read();
+        if !self.read() {
+          break;
+        }
+        continue;
       }
       if self.char == '$' && self.next_is('{', false) {
         if pre_char_start_index != self.cur_char_index  {
           let literal = chars_to_string(&self.code, pre_char_start_index.clone(), self.cur_char_index);
+          let span = self.create_span_between(pre_char_start_index, self.cur_char_index);
           pre_char_start_index = self.cur_char_index;
-          spans.push(Expression::String(StringLiteral { literal: literal.clone(), value: literal }));
+          spans.push(self.create_template_string_span(literal, span));
         }
         // skip ‘$'
         self.read();
@@ -2353,18 +2698,24 @@ impl AST{
         let expr = self.parse_expression()?;
         spans.push(expr);
         pre_char_start_index = self.cur_char_index;
+        continue;
+      }
+      if !self.read() {
+        break;
       }
     }
     if pre_char_start_index != self.cur_char_index {
       let literal = chars_to_string(&self.code, pre_char_start_index.clone(), self.cur_char_index);
-      spans.push(Expression::String(StringLiteral { literal: literal.clone(), value: literal }));
+      let span = self.create_span_between(pre_char_start_index, self.cur_char_index);
+      spans.push(self.create_template_string_span(literal, span));
     }
     // skip '`'
     self.read();
     // TODO: read_check
     self.next();
     Ok(Expression::TemplateLiteral(TemplateLiteralExpression{
-      spans
+      spans,
+      span: self.create_span(start_index),
     }))
   }
 
@@ -2404,8 +2755,8 @@ impl AST{
         Ok(val) => Ok(val as f64),
         Err(_) => Err(JSIError::new(JSIErrorType::SyntaxError, String::from("Invalid octal number"), 0, 0))
       }
-    } else if literal.starts_with("0") && literal.len() > 1 {
-      // 传统八进制: 077 (ES1 格式)
+    } else if literal.starts_with("0") && literal.len() > 1 && literal.chars().all(|c| c.is_ascii_digit()) {
+      // 传统八进制: 077 (ES1 格式)，0.5、0e1 等仍然是十进制
       // 检查是否包含非八进制数字（8 或 9），如果有则作为十进制处理
       let has_non_octal = literal.chars().any(|c| c == '8' || c == '9');
       if has_non_octal {
@@ -2427,192 +2778,31 @@ impl AST{
   }
 
   // 解析左结合表达式
+  // 链上的每个运算符都会让 AST 深一层，计入嵌套深度，如 1+1+1... 执行时会逐层递归
   fn parse_left_associate_expression<F: Fn(&mut AST)-> JSIResult<Expression>>(&mut self, tokens: Vec<Token>, next: F) -> JSIResult<Expression> {
+    let depth = self.nesting_depth;
+    let result = self.parse_left_associate_chain(tokens, next);
+    self.nesting_depth = depth;
+    result
+  }
+
+  fn parse_left_associate_chain<F: Fn(&mut AST)-> JSIResult<Expression>>(&mut self, tokens: Vec<Token>, next: F) -> JSIResult<Expression> {
+    let start_index = self.token_start_index;
     let mut left = next(self)?;
     loop {
       // 向左结合
       if tokens.contains(&self.token) {
+        self.enter_nesting()?;
         let operator = self.token.clone();
         // 跳过当前的运算符
         self.next();
         let right = next(self)?;
-        match operator {
-          Token::Plus => {
-            self.bytecode.push(ByteCode{
-              op: EByteCodeop::OpAdd,
-              args: vec![],
-              line: 0,
-            });
-          },
-          Token::Subtract => {
-            self.bytecode.push(ByteCode{
-              op: EByteCodeop::OpSub,
-              args: vec![],
-              line: 0,
-            });
-          },
-          Token::Multiply => {
-            self.bytecode.push(ByteCode {
-              op: EByteCodeop::OpMul,
-              args: vec![],
-              line: 0,
-            });
-          },
-          Token::Slash => {
-            self.bytecode.push(ByteCode{
-              op: EByteCodeop::OpDiv,
-              args: vec![],
-              line: 0,
-            });
-          },
-          Token::Equal => {
-            self.bytecode.push(ByteCode{
-              op: EByteCodeop::OpEqual,
-              args: vec![],
-              line: 0,
-            });
-          },
-          Token::StrictEqual => {
-            self.bytecode.push(ByteCode{
-              op: EByteCodeop::OpStrictEqual,
-              args: vec![],
-              line: 0,
-            });
-          },
-          Token::NotEqual => {
-            self.bytecode.push(ByteCode{
-              op: EByteCodeop::OpNotEqual,
-              args: vec![],
-              line: 0,
-            });
-          },
-          Token::StrictNotEqual => {
-            self.bytecode.push(ByteCode{
-              op: EByteCodeop::OpStrictNotEqual,
-              args: vec![],
-              line: 0,
-            });
-          },
-          Token::Greater => {
-            self.bytecode.push(ByteCode{
-              op: EByteCodeop::OpGreaterThan,
-              args: vec![],
-              line: 0,
-            });
-          },
-          Token::GreaterOrEqual => {
-            self.bytecode.push(ByteCode{
-              op: EByteCodeop::OpGreaterThanOrEqual,
-              args: vec![],
-              line: 0,
-            });
-          },
-          Token::Less => {
-            self.bytecode.push(ByteCode{
-              op: EByteCodeop::OpLessThan,
-              args: vec![],
-              line: 0,
-            });
-          },
-          Token::LessOrEqual => {
-            self.bytecode.push(ByteCode{
-              op: EByteCodeop::OpLessThanOrEqual,
-              args: vec![],
-              line: 0,
-            });
-          },
-          Token::Instanceof => {
-            self.bytecode.push(ByteCode{
-              op: EByteCodeop::OpInstanceOf,
-              args: vec![],
-              line: 0,
-            });
-          },
-          Token::LogicalAnd => {
-            self.bytecode.push(ByteCode{
-              op: EByteCodeop::OpLogicalAnd,
-              args: vec![],
-              line: 0,
-            });
-          },
-          Token::LogicalOr => {
-            self.bytecode.push(ByteCode{
-              op: EByteCodeop::OpLogicalOr,
-              args: vec![],
-              line: 0,
-            });
-          },
-          Token::Remainder => {
-            self.bytecode.push(ByteCode{
-              op: EByteCodeop::OpRemainder,
-              args: vec![],
-              line: 0,
-            });
-          },
-          Token::ShiftLeft => {
-            self.bytecode.push(ByteCode{
-              op: EByteCodeop::OpShiftLeft,
-              args: vec![],
-              line: 0,
-            });
-          },
-          Token::ShiftRight => {
-            self.bytecode.push(ByteCode{
-              op: EByteCodeop::OpShiftRight,
-              args: vec![],
-              line: 0,
-            });
-          },
-          Token::UnsignedShiftRight => {
-            self.bytecode.push(ByteCode{
-              op: EByteCodeop::OpUnsignedShiftRight,
-              args: vec![],
-              line: 0,
-            });
-          },
-          Token::Or => {
-            self.bytecode.push(ByteCode{
-              op: EByteCodeop::OpBitwiseOr,
-              args: vec![],
-              line: 0,
-            });
-          },
-          Token::ExclusiveOr => {
-            self.bytecode.push(ByteCode{
-              op: EByteCodeop::OpBitwiseXor,
-              args: vec![],
-              line: 0,
-            });
-          },
-          Token::And => {
-            self.bytecode.push(ByteCode{
-              op: EByteCodeop::OpBitwiseAnd,
-              args: vec![],
-              line: 0,
-            });
-          },
-          Token::In => {
-            self.bytecode.push(ByteCode{
-              op: EByteCodeop::OpIn,
-              args: vec![],
-              line: 0,
-            });
-          },
-          Token::NullishCoalescing => {
-            self.bytecode.push(ByteCode{
-              op: EByteCodeop::OpNullishCoalescing,
-              args: vec![],
-              line: 0,
-            });
-          },
-          _ => {
-            println!("unknown left_associate_expression operator: {:?}", operator);
-          },
-        }
+        self.push_binary_bytecode(&operator);
         left = Expression::Binary(BinaryExpression{
           left: Box::new(left),
           operator,
-          right: Box::new(right)
+          right: Box::new(right),
+          span: self.create_span(start_index),
         });
       } else {
         break;
@@ -2621,6 +2811,183 @@ impl AST{
     return Ok(left);
   }
 
+  // 生成二元运算符的字节码，单独成函数以减小递归解析时的栈帧
+  fn push_binary_bytecode(&mut self, operator: &Token) {
+    match operator {
+      Token::Plus => {
+        self.bytecode.push(ByteCode{
+          op: EByteCodeop::OpAdd,
+          args: vec![],
+          line: 0,
+        });
+      },
+      Token::Subtract => {
+        self.bytecode.push(ByteCode{
+          op: EByteCodeop::OpSub,
+          args: vec![],
+          line: 0,
+        });
+      },
+      Token::Multiply => {
+        self.bytecode.push(ByteCode {
+          op: EByteCodeop::OpMul,
+          args: vec![],
+          line: 0,
+        });
+      },
+      Token::Slash => {
+        self.bytecode.push(ByteCode{
+          op: EByteCodeop::OpDiv,
+          args: vec![],
+          line: 0,
+        });
+      },
+      Token::Equal => {
+        self.bytecode.push(ByteCode{
+          op: EByteCodeop::OpEqual,
+          args: vec![],
+          line: 0,
+        });
+      },
+      Token::StrictEqual => {
+        self.bytecode.push(ByteCode{
+          op: EByteCodeop::OpStrictEqual,
+          args: vec![],
+          line: 0,
+        });
+      },
+      Token::NotEqual => {
+        self.bytecode.push(ByteCode{
+          op: EByteCodeop::OpNotEqual,
+          args: vec![],
+          line: 0,
+        });
+      },
+      Token::StrictNotEqual => {
+        self.bytecode.push(ByteCode{
+          op: EByteCodeop::OpStrictNotEqual,
+          args: vec![],
+          line: 0,
+        });
+      },
+      Token::Greater => {
+        self.bytecode.push(ByteCode{
+          op: EByteCodeop::OpGreaterThan,
+          args: vec![],
+          line: 0,
+        });
+      },
+      Token::GreaterOrEqual => {
+        self.bytecode.push(ByteCode{
+          op: EByteCodeop::OpGreaterThanOrEqual,
+          args: vec![],
+          line: 0,
+        });
+      },
+      Token::Less => {
+        self.bytecode.push(ByteCode{
+          op: EByteCodeop::OpLessThan,
+          args: vec![],
+          line: 0,
+        });
+      },
+      Token::LessOrEqual => {
+        self.bytecode.push(ByteCode{
+          op: EByteCodeop::OpLessThanOrEqual,
+          args: vec![],
+          line: 0,
+        });
+      },
+      Token::Instanceof => {
+        self.bytecode.push(ByteCode{
+          op: EByteCodeop::OpInstanceOf,
+          args: vec![],
+          line: 0,
+        });
+      },
+      Token::LogicalAnd => {
+        self.bytecode.push(ByteCode{
+          op: EByteCodeop::OpLogicalAnd,
+          args: vec![],
+          line: 0,
+        });
+      },
+      Token::LogicalOr => {
+        self.bytecode.push(ByteCode{
+          op: EByteCodeop::OpLogicalOr,
+          args: vec![],
+          line: 0,
+        });
+      },
+      Token::Remainder => {
+        self.bytecode.push(ByteCode{
+          op: EByteCodeop::OpRemainder,
+          args: vec![],
+          line: 0,
+        });
+      },
+      Token::ShiftLeft => {
+        self.bytecode.push(ByteCode{
+          op: EByteCodeop::OpShiftLeft,
+          args: vec![],
+          line: 0,
+        });
+      },
+      Token::ShiftRight => {
+        self.bytecode.push(ByteCode{
+          op: EByteCodeop::OpShiftRight,
+          args: vec![],
+          line: 0,
+        });
+      },
+      Token::UnsignedShiftRight => {
+        self.bytecode.push(ByteCode{
+          op: EByteCodeop::OpUnsignedShiftRight,
+          args: vec![],
+          line: 0,
+        });
+      },
+      Token::Or => {
+        self.bytecode.push(ByteCode{
+          op: EByteCodeop::OpBitwiseOr,
+          args: vec![],
+          line: 0,
+        });
+      },
+      Token::ExclusiveOr => {
+        self.bytecode.push(ByteCode{
+          op: EByteCodeop::OpBitwiseXor,
+          args: vec![],
+          line: 0,
+        });
+      },
+      Token::And => {
+        self.bytecode.push(ByteCode{
+          op: EByteCodeop::OpBitwiseAnd,
+          args: vec![],
+          line: 0,
+        });
+      },
+      Token::In => {
+        self.bytecode.push(ByteCode{
+          op: EByteCodeop::OpIn,
+          args: vec![],
+          line: 0,
+        });
+      },
+      Token::NullishCoalescing => {
+        self.bytecode.push(ByteCode{
+          op: EByteCodeop::OpNullishCoalescing,
+          args: vec![],
+          line: 0,
+        });
+      },
+      _ => {
+        println!("unknown left_associate_expression operator: {:?}", operator);
+      },
+    }
+  }
+
   // 跳过空白字符
   fn skip_white_space(&mut self) {
     loop {
@@ -2639,17 +3006,57 @@ impl AST{
     }
   }
 
+  // 生成从 start_index 到前一个 token 结束位置的 Span
+  fn create_span(&self, start_index: usize) -> Span {
+    self.create_span_between(start_index, self.prev_token_end_index)
+  }
+
+  // 生成字符索引区间 [start_index, end_index) 的 Span
+  fn create_span_between(&self, start_index: usize, end_index: usize) -> Span {
+    let start_index = start_index.min(self.length);
+    let end_index = end_index.clamp(start_index, self.length);
+    let (line, column) = self.get_position(start_index);
+    Span {
+      start: self.byte_offsets[start_index],
+      end: self.byte_offsets[end_index],
+      line,
+      column,
+    }
+  }
+
+  // 字符索引对应的行号和列号，从 1 开始
+  fn get_position(&self, index: usize) -> (usize, usize) {
+    let line_index = match self.line_starts.binary_search(&index) {
+      Ok(index) => index,
+      Err(index) => index - 1,
+    };
+    (line_index + 1, index - self.line_starts[line_index] + 1)
+  }
+
   fn error_unexpected(&self) -> JSIError {
-    // TODO: more unexpected error
     let message = match self.token {
       Token::Identifier => format!("Unexpected identifier '{}'", self.literal),
       Token::Number => String::from("Unexpected number"),
       Token::String => String::from("Unexpected string"),
-      _ => format!("Unexpected token {:?}", self.token),
+      Token::Backtick => String::from("Unexpected template string"),
+      Token::EOF => String::from("Unexpected end of input"),
+      _ => {
+        // 使用 token 在源码中的文本，如 Unexpected token ';'
+        let start_index = self.token_start_index.min(self.length);
+        let end_index = self.cur_char_index.clamp(start_index, self.length);
+        format!("Unexpected token '{}'", chars_to_string(&self.code, start_index, end_index))
+      },
     };
     // panic!("token:{:?}", self.literal);
-    // TODO: line column
-    JSIError::new(JSIErrorType::SyntaxError, message, 0, 0)
+    let (line, column) = self.get_position(self.token_start_index.min(self.length));
+    JSIError::new(JSIErrorType::SyntaxError, message, line as i32, column as i32)
+  }
+
+  // 当前 token 不是期望的内容，expected 用于错误提示，如 ')'、expression
+  fn error_expected(&self, expected: String) -> JSIError {
+    let mut error = self.error_unexpected();
+    error.expected = Some(expected);
+    error
   }
 
   fn error_common(&mut self, error_msg: &str) {
@@ -17,6 +17,7 @@ pub enum Statement {
   ForIn(ForInStatement),
   ForOf(ForOfStatement),
   Function(FunctionDeclaration),
+  HostFunction(HostFunction),
   If(IfStatement),
   Label(LabeledStatement),
   Return(ReturnStatement),
@@ -42,12 +43,39 @@ impl PartialEq for Statement {
   }
 }
 
+impl Statement {
+  // 语句在源码中的位置
+  pub fn span(&self) -> Span {
+    match self {
+      Statement::Block(block) => block.span,
+      Statement::Break(statement) => statement.span,
+      Statement::Class(class) => class.span,
+      Statement::Continue(statement) => statement.span,
+      Statement::Expression(statement) => statement.span,
+      Statement::For(statement) => statement.span,
+      Statement::ForIn(statement) => statement.span,
+      Statement::ForOf(statement) => statement.span,
+      Statement::Function(function) => function.span,
+      Statement::If(statement) => statement.span,
+      Statement::Label(statement) => statement.span,
+      Statement::Return(statement) => statement.span,
+      Statement::Switch(statement) => statement.span,
+      Statement::Throw(statement) => statement.span,
+      Statement::Try(statement) => statement.span,
+      Statement::Var(statement) => statement.span,
+      Statement::While(statement) => statement.span,
+      Statement::BuiltinFunction(_) | Statement::HostFunction(_) | Statement::Unknown => Span::default(),
+    }
+  }
+}
+
 impl fmt::Debug for Statement {
   fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
     let stype = match self {
       Statement::Block(_) => { "block"},
       Statement::Break(_) => { "break"},
       Statement::BuiltinFunction(_) => { "builtin function"},
+      Statement::HostFunction(_) => { "host function"},
       Statement::Class(_) => { "class"},
       Statement::Continue(_) => { "continue"},
       Statement::Expression(expr) => {
@@ -70,6 +98,16 @@ impl fmt::Debug for Statement {
   }
 }
 
+// 源码位置：start 和 end 为字节偏移，line 和 column 从 1 开始
+// 解析器生成的节点（如默认的 undefined 初始值）没有对应的源码，span 为 Span::default()
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct Span {
+  pub start: usize,
+  pub end: usize,
+  pub line: usize,
+  pub column: usize,
+}
+
 #[derive(Debug,Clone, PartialEq)]
 pub enum Expression {
   Var(VariableDeclaration),
@@ -85,7 +123,7 @@ pub enum Expression {
   Identifier(IdentifierLiteral),
   Number(NumberLiteral),
   String(StringLiteral),
-  Keyword(Keywords),
+  Keyword(Keywords, Span),
   Object(ObjectLiteral),
   Array(ArrayLiteral),
   Function(FunctionDeclaration),
@@ -99,10 +137,42 @@ pub enum Expression {
   Constructor(FunctionDeclaration),
   ClassMethod(ClassMethodDeclaration),
   // Expression::Value
-  Value(Box<Value>),
+  Value(Box<Value>, Span),
   Unknown,
 }
 impl Expression {
+  // 表达式在源码中的位置，解析器补充的节点和运行时生成的值为 Span::default()
+  pub fn span(&self) -> Span {
+    match self {
+      Expression::Var(expr) => expr.span,
+      Expression::Assign(expr) => expr.span,
+      Expression::Binary(expr) => expr.span,
+      Expression::Conditional(expr) => expr.span,
+      Expression::PropertyAccess(expr) => expr.span,
+      Expression::ElementAccess(expr) => expr.span,
+      Expression::Call(expr) => expr.span,
+      Expression::PrefixUnary(expr) => expr.span,
+      Expression::PostfixUnary(expr) => expr.span,
+      Expression::Group(expr) => expr.span,
+      Expression::Identifier(expr) => expr.span,
+      Expression::Number(expr) => expr.span,
+      Expression::String(expr) => expr.span,
+      Expression::Object(expr) => expr.span,
+      Expression::Array(expr) => expr.span,
+      Expression::Function(expr) => expr.span,
+      Expression::New(expr) => expr.span,
+      Expression::Sequence(expr) => expr.span,
+      Expression::TemplateLiteral(expr) => expr.span,
+      Expression::ComputedPropertyName(expr) => expr.span,
+      Expression::Class(expr) => expr.span,
+      Expression::Constructor(expr) => expr.span,
+      Expression::ClassMethod(expr) => expr.span,
+      Expression::Keyword(_, span) => *span,
+      Expression::Value(_, span) => *span,
+      Expression::Unknown => Span::default(),
+    }
+  }
+
   // https://tc39.es/ecma262/multipage/syntax-directed-operations.html#sec-static-semantics-assignmenttargettype
   pub fn is_assignment_target_type(&self) -> bool {
     match self {
@@ -154,6 +224,7 @@ pub enum VariableFlag {
 pub struct VariableDeclarationStatement {
   pub list: Vec<Expression>,
   pub flag: VariableFlag,
+  pub span: Span,
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -161,6 +232,7 @@ pub struct IfStatement {
   pub condition: Expression,
   pub then_statement: Box<Statement>,
   pub else_statement: Box<Statement>,
+  pub span: Span,
 }
 
 
@@ -168,13 +240,15 @@ pub struct IfStatement {
 pub struct SwitchStatement {
   pub condition: Expression,
    pub clauses: Vec<CaseClause>,
-   pub default_index: i32
+   pub default_index: i32,
+  pub span: Span,
 }
 
 #[derive(Debug, Clone, PartialEq)]
 pub struct CaseClause {
   pub condition: Option<Expression>,
-  pub statements: Vec<Statement>
+  pub statements: Vec<Statement>,
+  pub span: Span,
 }
 
 
@@ -182,6 +256,7 @@ pub struct CaseClause {
 pub struct LabeledStatement {
   pub label: IdentifierLiteral,
   pub statement: Box<Statement>,
+  pub span: Span,
 }
 
 
@@ -192,6 +267,7 @@ pub struct ForStatement {
   pub incrementor: Expression,
   pub statement: Box<Statement>,
   pub post_judgment: bool,
+  pub span: Span,
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -201,6 +277,7 @@ pub struct ForInStatement {
   pub variable: Expression,
   pub object: Expression,
   pub statement: Box<Statement>,
+  pub span: Span,
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -210,12 +287,14 @@ pub struct ForOfStatement {
   pub variable: Expression,
   pub object: Expression,
   pub statement: Box<Statement>,
+  pub span: Span,
 }
 
 
 #[derive(Debug, Clone, PartialEq)]
 pub struct ThrowStatement {
-  pub expression: Expression
+  pub expression: Expression,
+  pub span: Span,
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -223,12 +302,14 @@ pub struct TryCatchStatement {
   pub body: BlockStatement,
   pub catch: Option<CatchClause>,
   pub finally: Option<BlockStatement>,
+  pub span: Span,
 }
 
 #[derive(Debug, Clone, PartialEq)]
 pub struct CatchClause {
   pub declaration: Option<IdentifierLiteral>,
-  pub body: BlockStatement
+  pub body: BlockStatement,
+  pub span: Span,
 }
 
 
@@ -243,18 +324,21 @@ pub struct FunctionDeclaration {
   pub body: BlockStatement,
   pub declarations: Vec<Declaration>,
   pub bytecode: Vec<ByteCode>,
+  pub span: Span,
 }
 
 // ES2015 Computed Property Name
 #[derive(Debug, Clone, PartialEq)]
 pub struct ComputedPropertyName {
-  pub expression: Box<Expression>
+  pub expression: Box<Expression>,
+  pub span: Span,
 }
 
 #[derive(Debug, Clone, PartialEq)]
 pub struct NewExpression {
   pub expression: Box<Expression>,
   pub arguments: Vec<Expression>,
+  pub span: Span,
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -262,7 +346,8 @@ pub struct ClassDeclaration {
   pub name: IdentifierLiteral,
   pub members: Vec<Expression>,
   // 继承
-  pub heritage: Option<Box<ClassDeclaration>>
+  pub heritage: Option<Box<ClassDeclaration>>,
+  pub span: Span,
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -270,43 +355,51 @@ pub struct ClassMethodDeclaration {
   pub name: IdentifierLiteral,
   pub modifiers: Vec<Token>,
   pub method: Box<FunctionDeclaration>,
+  pub span: Span,
 }
 
 #[derive(Debug, Clone, PartialEq)]
 pub struct BlockStatement {
-  pub statements: Vec<Statement>
+  pub statements: Vec<Statement>,
+  pub span: Span,
 }
 
 #[derive(Debug, Clone, PartialEq)]
 pub struct ReturnStatement {
-  pub expression: Expression
+  pub expression: Expression,
+  pub span: Span,
 }
 
 #[derive(Debug, Clone, PartialEq)]
 pub struct BreakStatement {
-  pub label: Option<IdentifierLiteral>
+  pub label: Option<IdentifierLiteral>,
+  pub span: Span,
 }
 
 #[derive(Debug, Clone, PartialEq)]
 pub struct ContinueStatement {
-  pub label: Option<IdentifierLiteral>
+  pub label: Option<IdentifierLiteral>,
+  pub span: Span,
 }
 
 #[derive(Debug, Clone, PartialEq)]
 pub struct Parameter {
   pub name: IdentifierLiteral,
-  pub initializer: Box<Expression>
+  pub initializer: Box<Expression>,
+  pub span: Span,
 }
 
 #[derive(Debug, Clone, PartialEq)]
 pub struct ExpressionStatement {
-  pub expression: Expression
+  pub expression: Expression,
+  pub span: Span,
 }
 #[derive(Debug, Clone, PartialEq)]
 pub struct AssignExpression {
   pub left: Box<Expression>,
   pub operator: Token,
   pub right: Box<Expression>,
+  pub span: Span,
 }
 
 // 条件表达式
@@ -315,6 +408,7 @@ pub struct ConditionalExpression {
   pub condition: Box<Expression>,
   pub when_true: Box<Expression>,
   pub when_false: Box<Expression>,
+  pub span: Span,
 }
 
 // . 表达式
@@ -323,12 +417,14 @@ pub struct BinaryExpression {
   pub left: Box<Expression>,
   pub operator: Token,
   pub right: Box<Expression>,
+  pub span: Span,
 }
 // 方法调用表达式
 #[derive(Debug, Clone, PartialEq)]
 pub struct CallExpression {
   pub expression: Box<Expression>,
   pub arguments: Vec<Expression>,
+  pub span: Span,
 }
 
 // . 属性访问表达式
@@ -336,6 +432,7 @@ pub struct CallExpression {
 pub struct PropertyAccessExpression {
   pub expression: Box<Expression>,
   pub name: IdentifierLiteral,
+  pub span: Span,
 }
 
 
@@ -344,6 +441,7 @@ pub struct PropertyAccessExpression {
 pub struct ElementAccessExpression {
   pub expression: Box<Expression>,
   pub argument: Box<Expression>,
+  pub span: Span,
 }
 
 
@@ -352,6 +450,7 @@ pub struct ElementAccessExpression {
 pub struct PrefixUnaryExpression {
   pub operand: Box<Expression>,
   pub operator: Token,
+  pub span: Span,
 }
 
 // 后置一元运算符表达式 ++ --
@@ -359,69 +458,83 @@ pub struct PrefixUnaryExpression {
 pub struct PostfixUnaryExpression {
   pub operand: Box<Expression>,
   pub operator: Token,
+  pub span: Span,
 }
 
 // 字符串模板表达式
 #[derive(Debug, Clone, PartialEq)]
 pub struct SequenceExpression {
-  pub expressions: Vec<Expression>
+  pub expressions: Vec<Expression>,
+  pub span: Span,
 }
 
 // 字符串模板表达式
 #[derive(Debug, Clone, PartialEq)]
 pub struct TemplateLiteralExpression {
-  pub spans: Vec<Expression>
+  pub spans: Vec<Expression>,
+  pub span: Span,
 }
 
 #[derive(Debug, Clone, PartialEq)]
 pub struct GroupExpression {
-  pub expression: Box<Expression>
+  pub expression: Box<Expression>,
+  pub span: Span,
 }
 
 #[derive(Debug, Clone, PartialEq)]
 pub struct IdentifierLiteral {
   pub literal: String,
+  pub span: Span,
 }
 
 #[derive(Debug, Clone, PartialEq)]
-pub struct  NumberLiteral {
+pub struct NumberLiteral {
   pub literal: String,
   pub value: f64,
+  pub span: Span,
 }
 
 
 #[derive(Debug, Clone, PartialEq)]
-pub struct  StringLiteral {
+pub struct StringLiteral {
   pub literal: String,
-  pub value: String
+  pub value: String,
+  pub span: Span,
 }
 
 #[derive(Debug, Clone, PartialEq)]
-pub struct  ObjectLiteral {
-  pub properties: Vec<PropertyAssignment>
+pub struct ObjectLiteral {
+  pub properties: Vec<PropertyAssignment>,
+  pub span: Span,
 }
 
 #[derive(Debug, Clone, PartialEq)]
 pub struct ArrayLiteral {
-  pub elements: Vec<Expression>
+  pub elements: Vec<Expression>,
+  pub span: Span,
 }
 
 
 #[derive(Debug, Clone, PartialEq)]
-pub struct  PropertyAssignment {
+pub struct PropertyAssignment {
   pub name: Box<Expression>,
-  pub initializer: Box<Expression>
+  pub initializer: Box<Expression>,
+  pub span: Span,
 }
 
 #[derive(Debug, Clone, PartialEq)]
 pub struct VariableDeclaration {
   pub name: String,
-  pub initializer: Box<Expression>
+  pub initializer: Box<Expression>,
+  pub span: Span,
 }
 
 
 
 pub type BuiltinFunction = fn(&mut CallContext, Vec<Value>) -> JSIResult<Value>;
+// 宿主方法，由嵌入方注册的 Rust 闭包，可以捕获外部状态
+// 参数依次为调用上下文（包含 this）、实参列表和 new.target（普通调用时为 undefined）
+pub type HostFunction = Rc<dyn Fn(&mut CallContext, Vec<Value>, Value) -> JSIResult<Value>>;
 // 方法调用的上下文
 pub struct CallContext<'a> {
   // 全局对象，globalThis
@@ -451,6 +564,7 @@ pub enum ClassType {
   Null,
   //
   Promise,
+  Map,
   Error,
 }
 
@@ -465,6 +579,7 @@ impl  ClassType {
       Self::Number => String::from("Number"),
       Self::Null => String::from("Null"),
       Self::Promise => String::from("Promise"),
+      Self::Map => String::from("Map"),
       Self::Error => String::from("Error"),
     }
   }
@@ -214,4 +214,75 @@ pub fn get_token_literal(literal: &String) -> Token {
     "false" => Token::False,
     _ => Token::ILLEGAL
   }
-}
\ No newline at end of file
+}
+// 错误提示中使用的 token 描述，如 ')'、identifier
+pub fn get_token_description(token: &Token) -> String {
+  let punctuator = match token {
+    Token::Plus => "+",
+    Token::Subtract => "-",
+    Token::Multiply => "*",
+    Token::Slash => "/",
+    Token::Remainder => "%",
+    Token::And => "&",
+    Token::Or => "|",
+    Token::ExclusiveOr => "^",
+    Token::ShiftLeft => "<<",
+    Token::ShiftRight => ">>",
+    Token::UnsignedShiftRight => ">>>",
+    Token::Assign => "=",
+    Token::Not => "!",
+    Token::BitwiseNot => "~",
+    Token::Less => "<",
+    Token::Greater => ">",
+    Token::LeftParenthesis => "(",
+    Token::LeftBracket => "[",
+    Token::LeftBrace => "{",
+    Token::Comma => ",",
+    Token::Period => ".",
+    Token::RightParenthesis => ")",
+    Token::RightBracket => "]",
+    Token::RightBrace => "}",
+    Token::Semicolon => ";",
+    Token::Colon => ":",
+    Token::QuestionMark => "?",
+    Token::Backtick => "`",
+    Token::AndNot => "&^",
+    Token::AddAssign => "+=",
+    Token::SubtractAssign => "-=",
+    Token::MultiplyAssign => "*=",
+    Token::SlashAssign => "/=",
+    Token::RemainderAssign => "%=",
+    Token::AndAssign => "&=",
+    Token::OrAssign => "|=",
+    Token::ExclusiveOrAssign => "^=",
+    Token::ShiftLeftAssign => "<<=",
+    Token::ShiftRightAssign => ">>=",
+    Token::UnsignedShiftRightAssign => ">>>=",
+    Token::AndNotAssign => "&^=",
+    Token::LogicalAnd => "&&",
+    Token::LogicalAndAssign => "&&=",
+    Token::LogicalOr => "||",
+    Token::LogicalOrAssign => "||=",
+    Token::Increment => "++",
+    Token::Decrement => "--",
+    Token::Equal => "==",
+    Token::StrictEqual => "===",
+    Token::NotEqual => "!=",
+    Token::StrictNotEqual => "!==",
+    Token::LessOrEqual => "<=",
+    Token::GreaterOrEqual => ">=",
+    Token::Exponentiation => "**",
+    Token::ExponentiationAssign => "**=",
+    Token::NullishCoalescing => "??",
+    Token::NullishCoalescingAssign => "??=",
+    Token::OptionalChaining => "?.",
+    Token::Identifier => return String::from("identifier"),
+    Token::Number => return String::from("number"),
+    Token::String => return String::from("string"),
+    Token::EOF => return String::from("end of input"),
+    Token::ILLEGAL => return String::from("invalid token"),
+    // 关键字和字面量使用小写的名称，如 while、null
+    _ => return format!("'{}'", format!("{:?}", token).to_lowercase()),
+  };
+  format!("'{}'", punctuator)
+}
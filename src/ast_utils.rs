@@ -24,56 +24,82 @@ pub fn chars_to_string(chars: &Vec<char>, start: usize, end: usize) -> String {
   return chars[start..end].iter().collect()
 }
 
-// 处理字符串转义序列
-pub fn process_string_escapes(s: &str) -> String {
-  let mut result = String::new();
+// 处理字符串转义序列，返回 UTF-16 编码单元
+// \uD83D 这类单独的代理项无法用 UTF-8 表示，所以以编码单元的形式返回，由调用方决定如何存储
+pub fn process_string_escapes(s: &str) -> Vec<u16> {
+  let mut result: Vec<u16> = Vec::with_capacity(s.len());
   let mut chars = s.chars().peekable();
+  let mut buf = [0u16; 2];
 
   while let Some(c) = chars.next() {
     if c == '\\' {
       if let Some(next) = chars.next() {
         match next {
-          'n' => result.push('\n'),
-          't' => result.push('\t'),
-          'r' => result.push('\r'),
-          '\\' => result.push('\\'),
-          '\'' => result.push('\''),
-          '"' => result.push('"'),
-          '0' => result.push('\0'),
+          'n' => result.push(0x0A),
+          't' => result.push(0x09),
+          'r' => result.push(0x0D),
+          'b' => result.push(0x08),
+          'f' => result.push(0x0C),
+          'v' => result.push(0x0B),
+          '0' => result.push(0x00),
           'x' => {
             // \xHH 十六进制转义
             let hex1 = chars.next();
             let hex2 = chars.next();
             if let (Some(h1), Some(h2)) = (hex1, hex2) {
               let val = (get_hex_number_value(h1) << 4) + get_hex_number_value(h2);
-              if let Some(ch) = char::from_u32(val as u32) {
-                result.push(ch);
-              }
+              result.push(val as u16);
             }
           },
           'u' => {
-            // \uHHHH Unicode 转义
-            let mut val = 0;
-            for _ in 0..4 {
-              if let Some(h) = chars.next() {
-                val = (val << 4) + get_hex_number_value(h);
+            if chars.peek() == Some(&'{') {
+              // \u{HHHHHH} 码点转义
+              chars.next();
+              let mut val: u32 = 0;
+              for h in chars.by_ref() {
+                if h == '}' {
+                  break;
+                }
+                val = (val << 4) + get_hex_number_value(h) as u32;
+              }
+              if let Some(ch) = char::from_u32(val) {
+                result.extend_from_slice(ch.encode_utf16(&mut buf));
+              } else if val <= 0xFFFF {
+                result.push(val as u16);
+              }
+            } else {
+              // \uHHHH Unicode 转义，可能是单独的代理项
+              let mut val = 0;
+              for _ in 0..4 {
+                if let Some(h) = chars.next() {
+                  val = (val << 4) + get_hex_number_value(h);
+                }
               }
+              result.push(val as u16);
             }
-            if let Some(ch) = char::from_u32(val as u32) {
-              result.push(ch);
+          },
+          // 行继续符
+          '\n' | '\u{2028}' | '\u{2029}' => {},
+          '\r' => {
+            if chars.peek() == Some(&'\n') {
+              chars.next();
             }
           },
-          _ => {
-            // 未知的转义序列，保留原样
-            result.push('\\');
-            result.push(next);
-          }
+          // 其他字符（包括 \\、\'、\"）转义为自身
+          _ => result.extend_from_slice(next.encode_utf16(&mut buf)),
         }
       }
     } else {
-      result.push(c);
+      result.extend_from_slice(c.encode_utf16(&mut buf));
     }
   }
 
   result
 }
+
+// 当前的栈地址，用于估算已经使用的原生栈空间，栈向低地址增长
+#[inline(never)]
+pub fn stack_address() -> usize {
+  let marker = 0u8;
+  std::hint::black_box(&marker) as *const u8 as usize
+}
@@ -0,0 +1,73 @@
+// 创建受限的运行环境，用于执行插件等不受信任的代码
+// 可以移除或替换全局变量、冻结内置对象、禁止通过字符串生成代码
+use crate::{context::Context, value::Value, JSI};
+
+type GlobalFactory = Box<dyn FnOnce(&mut Context) -> Value>;
+
+#[derive(Default)]
+pub struct JSIBuilder {
+  omitted: Vec<String>,
+  globals: Vec<(String, GlobalFactory)>,
+  freeze_intrinsics: bool,
+  code_generation: Option<bool>,
+}
+
+impl JSIBuilder {
+  pub fn new() -> JSIBuilder {
+    JSIBuilder::default()
+  }
+
+  // 不安装某个全局变量，如 console、Function，内置方法内部仍然可以使用
+  pub fn without_global(mut self, name: &str) -> Self {
+    self.omitted.push(name.to_string());
+    self
+  }
+
+  // 安装或替换全局变量，值在创建 Context 之后生成，之后创建的领域共用这个值
+  pub fn with_global<F>(mut self, name: &str, factory: F) -> Self
+  where F: FnOnce(&mut Context) -> Value + 'static {
+    self.globals.push((name.to_string(), Box::new(factory)));
+    self
+  }
+
+  // 冻结内置对象及其原型，脚本不能修改如 Array.prototype
+  pub fn freeze_intrinsics(mut self, freeze: bool) -> Self {
+    self.freeze_intrinsics = freeze;
+    self
+  }
+
+  // 是否允许 Function 构造方法等通过字符串生成代码
+  // 未设置时，移除或替换了 Function 则不允许，避免通过 (function(){}).constructor 绕过
+  pub fn allow_code_generation(mut self, allow: bool) -> Self {
+    self.code_generation = Some(allow);
+    self
+  }
+
+  pub fn build(self) -> JSI {
+    let mut context = Context::new();
+    let function_replaced = self.omitted.iter().chain(self.globals.iter().map(|(name, _)| name)).any(|name| name == "Function");
+    // 先冻结内置对象，替换的全局变量由宿主提供，不冻结
+    context.sandbox = SandboxConfig {
+      omitted: self.omitted,
+      freeze_intrinsics: self.freeze_intrinsics,
+      globals: vec![],
+    };
+    context.apply_sandbox();
+    for (name, factory) in self.globals.into_iter() {
+      let value = factory(&mut context);
+      context.define_global_value(name.clone(), value.clone());
+      context.sandbox.globals.push((name, value));
+    }
+    context.code_generation = self.code_generation.unwrap_or(!function_replaced);
+    JSI { context }
+  }
+}
+
+// 沙箱配置保存在 Context 上，通过 create_realm 创建的领域同样生效
+#[derive(Default)]
+pub(crate) struct SandboxConfig {
+  pub omitted: Vec<String>,
+  pub freeze_intrinsics: bool,
+  // with_global 生成的值，各个领域共用
+  pub globals: Vec<(String, Value)>,
+}
@@ -1,14 +1,22 @@
 use std::cell::{RefCell};
+use std::cmp::Ordering;
+use std::mem::size_of;
 use std::{rc::Rc};
 use crate::constants::{PROTO_PROPERTY_NAME, GLOBAL_ARRAY_NAME};
 use crate::context::{Context};
 use crate::{value::{Value, INSTANTIATE_OBJECT_METHOD_NAME}, ast_node::{CallContext, ClassType}, error::{JSIResult, JSIError, JSIErrorType}};
+use crate::heap::{value_payload_size, PROPERTY_SIZE};
 
 use super::function::builtin_function;
+use super::map::{get_map_entries, is_map};
+use super::number::MAX_SAFE_INTEGER;
 use super::global::{get_global_object_prototype_by_name, get_global_object_by_name};
 use super::object::Object;
 use super::{object::{create_object, Property}};
 
+// 数组的最大长度 2^32 - 1
+const MAX_ARRAY_LENGTH: u64 = 4294967295;
+
 // Pre-generated string representations for small integers (0-999)
 // This avoids repeated to_string() allocations for common array indices
 fn index_to_string(index: i32) -> String {
@@ -90,6 +98,10 @@ fn get_array_indices_in_range(obj: &Object, start: u64, end: u64) -> Vec<u64> {
 
 pub fn create_array_from_values(ctx: &mut Context, values: Vec<Value>) -> Value {
   let new_array = create_array(ctx, 0);
+  // 超出堆内存限制时不写入元素，执行在下一次检查时终止
+  if ctx.check_allocation(values.len().saturating_mul(PROPERTY_SIZE)).is_err() {
+    return new_array;
+  }
   if let Value::Array(arr_obj) = &new_array {
 
     let mut arr = arr_obj.borrow_mut();
@@ -111,6 +123,10 @@ pub fn bind_global_array(ctx: &mut Context) {
   arr.set_inner_property_value(INSTANTIATE_OBJECT_METHOD_NAME.to_string(), create_function);
   let name = String::from("isArray");
   arr.property.insert(name.clone(), Property { enumerable: true, value: builtin_function(ctx, name, 1f64, array_static_is_array) });
+  let name = String::from("from");
+  arr.property.insert(name.clone(), Property { enumerable: true, value: builtin_function(ctx, name, 1f64, array_static_from) });
+  let name = String::from("of");
+  arr.property.insert(name.clone(), Property { enumerable: true, value: builtin_function(ctx, name, 0f64, array_static_of) });
 
   if let Some(prop)= &arr.prototype {
     let prototype_rc = Rc::clone(prop);
@@ -134,12 +150,30 @@ pub fn bind_global_array(ctx: &mut Context) {
     prototype.define_builtin_function_property(ctx, String::from("sort"),  1, array_sort);
     prototype.define_builtin_function_property(ctx, String::from("slice"),  2, array_slice);
     prototype.define_builtin_function_property(ctx, String::from("splice"),  2, array_splice);
+    prototype.define_builtin_function_property(ctx, String::from("reduce"),  1, array_reduce);
+    prototype.define_builtin_function_property(ctx, String::from("reduceRight"),  1, array_reduce_right);
+    prototype.define_builtin_function_property(ctx, String::from("some"),  1, array_some);
+    prototype.define_builtin_function_property(ctx, String::from("every"),  1, array_every);
+    prototype.define_builtin_function_property(ctx, String::from("flat"),  0, array_flat);
+    prototype.define_builtin_function_property(ctx, String::from("flatMap"),  1, array_flat_map);
+    prototype.define_builtin_function_property(ctx, String::from("at"),  1, array_at);
+    prototype.define_builtin_function_property(ctx, String::from("lastIndexOf"),  1, array_last_index_of);
+    prototype.define_builtin_function_property(ctx, String::from("findLast"),  1, array_find_last);
+    prototype.define_builtin_function_property(ctx, String::from("findLastIndex"),  1, array_find_last_index);
+    prototype.define_builtin_function_property(ctx, String::from("copyWithin"),  2, array_copy_within);
+    prototype.define_builtin_function_property(ctx, String::from("keys"),  0, array_keys);
+    prototype.define_builtin_function_property(ctx, String::from("values"),  0, array_values);
+    prototype.define_builtin_function_property(ctx, String::from("entries"),  0, array_entries);
+    prototype.define_builtin_function_property(ctx, String::from("toSorted"),  1, array_to_sorted);
+    prototype.define_builtin_function_property(ctx, String::from("toReversed"),  0, array_to_reversed);
+    prototype.define_builtin_function_property(ctx, String::from("toSpliced"),  2, array_to_spliced);
+    prototype.define_builtin_function_property(ctx, String::from("with"),  2, array_with);
   }
 }
 
 // Array.isArray
-fn array_static_is_array(call_ctx: &mut CallContext, _: Vec<Value>) -> JSIResult<Value> {
-  match &call_ctx.this {
+fn array_static_is_array(_: &mut CallContext, args: Vec<Value>) -> JSIResult<Value> {
+  match args.first().unwrap_or(&Value::Undefined) {
     Value::Array(array) => {
       let arrborrowed =  array.borrow();
       if let  ClassType::Array = arrborrowed.class_type {
@@ -228,27 +262,53 @@ fn array_concat(call_ctx: &mut CallContext, args: Vec<Value>) -> JSIResult<Value
 
 // Array.prototype.join
 fn array_join(call_ctx: &mut CallContext, args: Vec<Value>) -> JSIResult<Value> {
-  let mut join = ",";
-  if args.len() > 0 {
-    if let Value::String(join_param) = &args[0] {
-      join = join_param;
+  let join = match args.first() {
+    Some(separator @ (Value::String(_) | Value::Utf16String(_))) => separator.clone(),
+    _ => Value::String(String::from(",")),
+  };
+  // 含有单独代理项的元素保留 UTF-16 编码单元，拼接后才能组成完整的代理对
+  let mut item_list: Vec<Value> = vec![];
+  let mut length: usize = 0;
+  if let Value::Array(this_ref) = &call_ctx.this {
+    let this_ref = Rc::clone(this_ref);
+    let len = get_array_length(&this_ref);
+    for (count, index) in get_visit_indices(&this_ref, 0, len).into_iter().enumerate() {
+      call_ctx.ctx.check_limits_in_loop(count)?;
+      let value = this_ref.borrow().get_property_value(index.to_string());
+      let item = match value {
+        Value::Utf16String(_) => value,
+        _ => Value::String(value.to_string(call_ctx.ctx)),
+      };
+      length += value_payload_size(&item) + value_payload_size(&join);
+      // 结果会超出堆内存限制时不再继续生成元素的字符串
+      if call_ctx.ctx.heap.is_over_limit(length) {
+        break;
+      }
+      item_list.push(item);
     }
   }
-  let mut string_list: Vec<String> = vec![];
-  let iter = |_: i32, value: &Value, ctx: &mut Context| {
-    string_list.push(value.to_string(ctx));
-  };
-  let this_array_obj = match &call_ctx.this {
-    Value::Array(array) => {
-      Some(Rc::clone(array))
-    },
-    _ => None,
-  };
-  if let Some(this_ref) = this_array_obj {
-    array_iter_mut(call_ctx, &this_ref,  iter);
+  call_ctx.ctx.check_allocation(length)?;
+  let has_utf16 = matches!(join, Value::Utf16String(_)) || item_list.iter().any(|item| matches!(item, Value::Utf16String(_)));
+  if !has_utf16 {
+    let string_list: Vec<String> = item_list.into_iter().map(|item| match item {
+      Value::String(str) => str,
+      _ => String::new(),
+    }).collect();
+    let join = match join {
+      Value::String(join) => join,
+      _ => String::new(),
+    };
+    return Ok(Value::String(string_list.join(&join)))
   }
- 
-  Ok(Value::String(string_list.join(join)))
+  let join = join.to_utf16(call_ctx.ctx);
+  let mut units: Vec<u16> = vec![];
+  for (index, item) in item_list.iter().enumerate() {
+    if index > 0 {
+      units.extend_from_slice(&join);
+    }
+    units.extend(item.to_utf16(call_ctx.ctx));
+  }
+  Ok(Value::from_utf16(units))
 }
 
 
@@ -812,7 +872,11 @@ fn array_fill(call_ctx: &mut CallContext, args: Vec<Value>) -> JSIResult<Value>
 
     // Fill the array
     let mut this = this_ref.borrow_mut();
+    // 新增的元素超出堆内存限制时不再填充
+    let new_count = (start..end).filter(|index| !this.property.contains_key(&index.to_string())).count();
+    call_ctx.ctx.check_allocation(new_count * PROPERTY_SIZE + (end - start) as usize * value_payload_size(&fill_value))?;
     for index in start..end {
+      call_ctx.ctx.check_limits_in_loop((index - start) as usize)?;
       this.define_property(index_to_string(index), Property { enumerable: true, value: fill_value.clone() });
     }
   }
@@ -1172,6 +1236,7 @@ fn array_sort(call_ctx: &mut CallContext, args: Vec<Value>) -> JSIResult<Value>
         // Collect elements into a vector
         let mut elements: Vec<Value> = Vec::with_capacity(len_u64 as usize);
         for index in 0..len_u64 {
+          call_ctx.ctx.check_limits_in_loop(index as usize)?;
           elements.push(this.get_property_value(index.to_string()));
         }
 
@@ -1225,13 +1290,15 @@ fn array_sort(call_ctx: &mut CallContext, args: Vec<Value>) -> JSIResult<Value>
 
         // Write sorted elements back
         for (index, value) in elements.into_iter().enumerate() {
+          call_ctx.ctx.check_limits_in_loop(index)?;
           this.define_property(index.to_string(), Property { enumerable: true, value });
         }
       } else {
         // For sparse arrays, collect only actual existing elements with their indices
         let indices = get_array_indices(&this);
         let mut elements_with_indices: Vec<(u64, Value)> = Vec::new();
-        for index in indices.iter() {
+        for (count, index) in indices.iter().enumerate() {
+          call_ctx.ctx.check_limits_in_loop(count)?;
           if *index < len_u64 {
             let value = this.get_property_value(index.to_string());
             elements_with_indices.push((*index, value));
@@ -1260,6 +1327,7 @@ fn array_sort(call_ctx: &mut CallContext, args: Vec<Value>) -> JSIResult<Value>
 
         // Write sorted elements back at indices 0..n
         for (new_index, (_, value)) in elements_with_indices.into_iter().enumerate() {
+          call_ctx.ctx.check_limits_in_loop(new_index)?;
           this.define_property(new_index.to_string(), Property { enumerable: true, value });
         }
       }
@@ -1481,4 +1549,674 @@ fn array_splice(call_ctx: &mut CallContext, args: Vec<Value>) -> JSIResult<Value
   }
 
   Ok(create_array(call_ctx.ctx, 0))
-}
\ No newline at end of file
+}
+// 数组迭代器的内置属性
+const ARRAY_ITERATOR_ITERATED_OBJECT: &str = "[[IteratedArrayLike]]";
+const ARRAY_ITERATOR_NEXT_INDEX: &str = "[[ArrayLikeNextIndex]]";
+const ARRAY_ITERATOR_KIND: &str = "[[ArrayLikeIterationKind]]";
+
+// 获取 this 对应的对象，null 和 undefined 抛出 TypeError
+fn get_this_object(call_ctx: &mut CallContext, method: &str) -> JSIResult<Rc<RefCell<Object>>> {
+  if call_ctx.this.is_not_strict_null() {
+    return Err(JSIError::new(JSIErrorType::TypeError, format!("Array.prototype.{} called on null or undefined", method), 0, 0))
+  }
+  let this = call_ctx.this.clone();
+  Ok(this.to_object(call_ctx.ctx))
+}
+
+// LengthOfArrayLike：将 length 属性转换为 [0, 2^53 - 1] 范围内的整数
+fn get_length_of_array_like(ctx: &mut Context, obj: &Rc<RefCell<Object>>) -> JSIResult<u64> {
+  let length = obj.borrow().get_value(String::from("length"));
+  let len = length.to_number_or_throw(ctx)?;
+  if len.is_nan() || len <= 0f64 {
+    return Ok(0)
+  }
+  if len >= MAX_SAFE_INTEGER {
+    return Ok(MAX_SAFE_INTEGER as u64)
+  }
+  Ok(len.floor() as u64)
+}
+
+// 数组的 length 由引擎维护，总是 [0, 2^32 - 1] 范围内的数字
+fn get_array_length(arr: &Rc<RefCell<Object>>) -> u64 {
+  match arr.borrow().get_value(String::from("length")) {
+    Value::Number(len) if len > 0f64 => len as u64,
+    _ => 0,
+  }
+}
+
+// 将相对位置（负数从末尾开始计算）转换为 [0, len] 范围内的索引
+fn resolve_relative_index(relative: f64, len: u64) -> u64 {
+  if relative < 0f64 {
+    let index = len as f64 + relative;
+    if index < 0f64 { 0 } else { index as u64 }
+  } else if relative > len as f64 {
+    len
+  } else {
+    relative as u64
+  }
+}
+
+// 某个位置是否存在元素，用于跳过稀疏数组的空位
+fn has_element(obj: &Rc<RefCell<Object>>, index: u64) -> bool {
+  obj.borrow().property.contains_key(&index.to_string())
+}
+
+fn get_element(obj: &Rc<RefCell<Object>>, index: u64) -> Value {
+  obj.borrow().get_value(index.to_string())
+}
+
+// 按顺序读取 0..len 的元素，空位读取为 undefined，len 超过数组的最大长度时抛出 RangeError
+fn get_element_list(ctx: &mut Context, obj: &Rc<RefCell<Object>>, len: u64) -> JSIResult<Vec<Value>> {
+  if len > MAX_ARRAY_LENGTH {
+    return Err(JSIError::new(JSIErrorType::RangeError, String::from("Invalid array length"), 0, 0))
+  }
+  ctx.check_allocation((len as usize).saturating_mul(size_of::<Value>()))?;
+  let mut list: Vec<Value> = vec![];
+  for index in 0..len {
+    ctx.check_limits_in_loop(index as usize)?;
+    list.push(obj.borrow().get_value(index.to_string()));
+  }
+  Ok(list)
+}
+
+// 需要遍历的索引：长度较小时遍历全部位置，否则只遍历实际存在的索引
+fn get_visit_indices(obj: &Rc<RefCell<Object>>, start: u64, end: u64) -> Vec<u64> {
+  if end <= start {
+    return vec![]
+  }
+  if end - start <= 1000 {
+    (start..end).collect()
+  } else {
+    get_array_indices_in_range(&obj.borrow(), start, end)
+  }
+}
+
+fn get_callback_function(args: &[Value], method: &str) -> JSIResult<Rc<RefCell<Object>>> {
+  match args.first() {
+    Some(Value::Function(func)) => Ok(Rc::clone(func)),
+    _ => Err(JSIError::new(JSIErrorType::TypeError, format!("Array.prototype.{} callback must be a function", method), 0, 0)),
+  }
+}
+
+// 调用回调方法，返回回调的执行结果
+pub fn call_callback(call_ctx: &mut CallContext, callback: &Rc<RefCell<Object>>, this_arg: &Value, args: Vec<Value>) -> JSIResult<Value> {
+  let callback_args: Vec<crate::value::ValueInfo> = args.iter().map(|arg| arg.to_value_info()).collect();
+  let call_this = match this_arg {
+    Value::Undefined => None,
+    _ => Some(this_arg.clone()),
+  };
+  call_ctx.ctx.call_function_with_bytecode(Rc::clone(callback), call_this, None, callback_args)?;
+  Ok(call_ctx.ctx.pop_stack_value())
+}
+
+// Array.from
+// Array.from(items[, mapFn[, thisArg]])
+fn array_static_from(call_ctx: &mut CallContext, args: Vec<Value>) -> JSIResult<Value> {
+  let items = args.first().cloned().unwrap_or(Value::Undefined);
+  let map_fn = match args.get(1) {
+    None | Some(Value::Undefined) => None,
+    Some(Value::Function(func)) => Some(Rc::clone(func)),
+    Some(_) => return Err(JSIError::new(JSIErrorType::TypeError, String::from("Array.from: when provided, the second argument must be a function"), 0, 0)),
+  };
+  let this_arg = args.get(2).cloned().unwrap_or(Value::Undefined);
+  if items.is_not_strict_null() {
+    return Err(JSIError::new(JSIErrorType::TypeError, format!("{} is not iterable", items.type_of()), 0, 0))
+  }
+
+  let values = match get_iterable_values(call_ctx.ctx, &items) {
+    Some(values) => values,
+    None => {
+      // 数组类对象 { length: 2, 0: 'a', 1: 'b' }
+      let obj = items.to_object(call_ctx.ctx);
+      let len = get_length_of_array_like(call_ctx.ctx, &obj)?;
+      get_element_list(call_ctx.ctx, &obj, len)?
+    }
+  };
+
+  let values = if let Some(map_fn) = map_fn {
+    let mut mapped: Vec<Value> = Vec::with_capacity(values.len());
+    for (index, value) in values.into_iter().enumerate() {
+      mapped.push(call_callback(call_ctx, &map_fn, &this_arg, vec![value, Value::Number(index as f64)])?);
+    }
+    mapped
+  } else {
+    values
+  };
+  Ok(create_array_from_values(call_ctx.ctx, values))
+}
+
+// Array.of
+// Array.of(element0[, element1[, ...[, elementN]]])
+fn array_static_of(call_ctx: &mut CallContext, args: Vec<Value>) -> JSIResult<Value> {
+  Ok(create_array_from_values(call_ctx.ctx, args))
+}
+
+// Array.prototype.reduce
+// arr.reduce(callback[, initialValue])
+fn array_reduce(call_ctx: &mut CallContext, args: Vec<Value>) -> JSIResult<Value> {
+  reduce_array_like(call_ctx, args, false)
+}
+
+// Array.prototype.reduceRight
+// arr.reduceRight(callback[, initialValue])
+fn array_reduce_right(call_ctx: &mut CallContext, args: Vec<Value>) -> JSIResult<Value> {
+  reduce_array_like(call_ctx, args, true)
+}
+
+fn reduce_array_like(call_ctx: &mut CallContext, args: Vec<Value>, from_right: bool) -> JSIResult<Value> {
+  let method = if from_right { "reduceRight" } else { "reduce" };
+  let this_obj = get_this_object(call_ctx, method)?;
+  let len = get_length_of_array_like(call_ctx.ctx, &this_obj)?;
+  let callback = get_callback_function(&args, method)?;
+  let this_value = call_ctx.this.clone();
+
+  let mut indices = get_visit_indices(&this_obj, 0, len);
+  if from_right {
+    indices.reverse();
+  }
+  // 回调中可能删除元素，所以在访问时再判断元素是否存在
+  let mut indices = indices.into_iter().filter(|index| has_element(&this_obj, *index));
+  let mut accumulator = if args.len() > 1 {
+    args[1].clone()
+  } else {
+    match indices.next() {
+      Some(index) => get_element(&this_obj, index),
+      None => return Err(JSIError::new(JSIErrorType::TypeError, String::from("Reduce of empty array with no initial value"), 0, 0)),
+    }
+  };
+  for index in indices {
+    let element = get_element(&this_obj, index);
+    accumulator = call_callback(call_ctx, &callback, &Value::Undefined, vec![accumulator, element, Value::Number(index as f64), this_value.clone()])?;
+  }
+  Ok(accumulator)
+}
+
+// Array.prototype.some
+// arr.some(callback[, thisArg])
+fn array_some(call_ctx: &mut CallContext, args: Vec<Value>) -> JSIResult<Value> {
+  let this_obj = get_this_object(call_ctx, "some")?;
+  let len = get_length_of_array_like(call_ctx.ctx, &this_obj)?;
+  let callback = get_callback_function(&args, "some")?;
+  let this_arg = args.get(1).cloned().unwrap_or(Value::Undefined);
+  let this_value = call_ctx.this.clone();
+  for index in get_visit_indices(&this_obj, 0, len) {
+    if !has_element(&this_obj, index) {
+      continue;
+    }
+    let element = get_element(&this_obj, index);
+    let result = call_callback(call_ctx, &callback, &this_arg, vec![element, Value::Number(index as f64), this_value.clone()])?;
+    if result.to_boolean(call_ctx.ctx) {
+      return Ok(Value::Boolean(true));
+    }
+  }
+  Ok(Value::Boolean(false))
+}
+
+// Array.prototype.every
+// arr.every(callback[, thisArg])
+fn array_every(call_ctx: &mut CallContext, args: Vec<Value>) -> JSIResult<Value> {
+  let this_obj = get_this_object(call_ctx, "every")?;
+  let len = get_length_of_array_like(call_ctx.ctx, &this_obj)?;
+  let callback = get_callback_function(&args, "every")?;
+  let this_arg = args.get(1).cloned().unwrap_or(Value::Undefined);
+  let this_value = call_ctx.this.clone();
+  for index in get_visit_indices(&this_obj, 0, len) {
+    if !has_element(&this_obj, index) {
+      continue;
+    }
+    let element = get_element(&this_obj, index);
+    let result = call_callback(call_ctx, &callback, &this_arg, vec![element, Value::Number(index as f64), this_value.clone()])?;
+    if !result.to_boolean(call_ctx.ctx) {
+      return Ok(Value::Boolean(false));
+    }
+  }
+  Ok(Value::Boolean(true))
+}
+
+// 将 source 中的元素展开到 list 中，depth 为展开的深度
+// 嵌套过深或数组引用自身时抛出 RangeError，不会耗尽原生栈
+fn flatten_into_list(ctx: &mut Context, list: &mut Vec<Value>, source: &Rc<RefCell<Object>>, source_len: u64, depth: f64) -> JSIResult<()> {
+  ctx.check_stack()?;
+  for (count, index) in get_visit_indices(source, 0, source_len).into_iter().enumerate() {
+    ctx.check_limits_in_loop(count)?;
+    if !has_element(source, index) {
+      continue;
+    }
+    let element = get_element(source, index);
+    if depth > 0f64 {
+      if let Value::Array(inner) = &element {
+        let inner_len = get_array_length(inner);
+        flatten_into_list(ctx, list, inner, inner_len, depth - 1f64)?;
+        continue;
+      }
+    }
+    list.push(element);
+  }
+  Ok(())
+}
+
+// Array.prototype.flat
+// arr.flat([depth])
+fn array_flat(call_ctx: &mut CallContext, args: Vec<Value>) -> JSIResult<Value> {
+  let this_obj = get_this_object(call_ctx, "flat")?;
+  let len = get_length_of_array_like(call_ctx.ctx, &this_obj)?;
+  let depth = match args.first() {
+    None | Some(Value::Undefined) => 1f64,
+    Some(depth) => depth.to_integer_or_infinity(call_ctx.ctx)?.max(0f64),
+  };
+  let mut list: Vec<Value> = vec![];
+  flatten_into_list(call_ctx.ctx, &mut list, &this_obj, len, depth)?;
+  Ok(create_array_from_values(call_ctx.ctx, list))
+}
+
+// Array.prototype.flatMap
+// arr.flatMap(callback[, thisArg])
+fn array_flat_map(call_ctx: &mut CallContext, args: Vec<Value>) -> JSIResult<Value> {
+  let this_obj = get_this_object(call_ctx, "flatMap")?;
+  let len = get_length_of_array_like(call_ctx.ctx, &this_obj)?;
+  let callback = get_callback_function(&args, "flatMap")?;
+  let this_arg = args.get(1).cloned().unwrap_or(Value::Undefined);
+  let this_value = call_ctx.this.clone();
+  let mut list: Vec<Value> = vec![];
+  for index in get_visit_indices(&this_obj, 0, len) {
+    if !has_element(&this_obj, index) {
+      continue;
+    }
+    let element = get_element(&this_obj, index);
+    let mapped = call_callback(call_ctx, &callback, &this_arg, vec![element, Value::Number(index as f64), this_value.clone()])?;
+    if let Value::Array(inner) = &mapped {
+      let inner_len = get_array_length(inner);
+      flatten_into_list(call_ctx.ctx, &mut list, inner, inner_len, 0f64)?;
+    } else {
+      list.push(mapped);
+    }
+  }
+  Ok(create_array_from_values(call_ctx.ctx, list))
+}
+
+// Array.prototype.at
+// arr.at(index)
+fn array_at(call_ctx: &mut CallContext, args: Vec<Value>) -> JSIResult<Value> {
+  let this_obj = get_this_object(call_ctx, "at")?;
+  let len = get_length_of_array_like(call_ctx.ctx, &this_obj)?;
+  let relative = args.first().unwrap_or(&Value::Undefined).to_integer_or_infinity(call_ctx.ctx)?;
+  let index = if relative >= 0f64 { relative } else { len as f64 + relative };
+  if index < 0f64 || index >= len as f64 {
+    return Ok(Value::Undefined);
+  }
+  Ok(get_element(&this_obj, index as u64))
+}
+
+// Array.prototype.lastIndexOf
+// arr.lastIndexOf(searchElement[, fromIndex])
+fn array_last_index_of(call_ctx: &mut CallContext, args: Vec<Value>) -> JSIResult<Value> {
+  let this_obj = get_this_object(call_ctx, "lastIndexOf")?;
+  let len = get_length_of_array_like(call_ctx.ctx, &this_obj)?;
+  if len == 0 {
+    return Ok(Value::Number(-1f64));
+  }
+  let search_element = args.first().cloned().unwrap_or(Value::Undefined);
+  let from_index = if args.len() > 1 {
+    args[1].to_integer_or_infinity(call_ctx.ctx)?
+  } else {
+    (len - 1) as f64
+  };
+  let start = if from_index >= 0f64 {
+    from_index.min((len - 1) as f64)
+  } else {
+    len as f64 + from_index
+  };
+  if start < 0f64 {
+    return Ok(Value::Number(-1f64));
+  }
+  let mut indices = get_visit_indices(&this_obj, 0, start as u64 + 1);
+  indices.reverse();
+  for index in indices {
+    if !has_element(&this_obj, index) {
+      continue;
+    }
+    let element = get_element(&this_obj, index);
+    if element.is_equal_to(call_ctx.ctx, &search_element, true) {
+      return Ok(Value::Number(index as f64));
+    }
+  }
+  Ok(Value::Number(-1f64))
+}
+
+// 从末尾开始查找满足条件的元素，返回 (索引, 元素)
+fn find_last_element(call_ctx: &mut CallContext, args: Vec<Value>, method: &str) -> JSIResult<Option<(u64, Value)>> {
+  let this_obj = get_this_object(call_ctx, method)?;
+  let len = get_length_of_array_like(call_ctx.ctx, &this_obj)?;
+  let callback = get_callback_function(&args, method)?;
+  let this_arg = args.get(1).cloned().unwrap_or(Value::Undefined);
+  let this_value = call_ctx.this.clone();
+  // 空位当作 undefined 处理，因此需要遍历全部位置
+  for index in (0..len).rev() {
+    let element = get_element(&this_obj, index);
+    let result = call_callback(call_ctx, &callback, &this_arg, vec![element.clone(), Value::Number(index as f64), this_value.clone()])?;
+    if result.to_boolean(call_ctx.ctx) {
+      return Ok(Some((index, element)));
+    }
+  }
+  Ok(None)
+}
+
+// Array.prototype.findLast
+// arr.findLast(callback[, thisArg])
+fn array_find_last(call_ctx: &mut CallContext, args: Vec<Value>) -> JSIResult<Value> {
+  match find_last_element(call_ctx, args, "findLast")? {
+    Some((_, element)) => Ok(element),
+    None => Ok(Value::Undefined),
+  }
+}
+
+// Array.prototype.findLastIndex
+// arr.findLastIndex(callback[, thisArg])
+fn array_find_last_index(call_ctx: &mut CallContext, args: Vec<Value>) -> JSIResult<Value> {
+  match find_last_element(call_ctx, args, "findLastIndex")? {
+    Some((index, _)) => Ok(Value::Number(index as f64)),
+    None => Ok(Value::Number(-1f64)),
+  }
+}
+
+// Array.prototype.copyWithin
+// arr.copyWithin(target[, start[, end]])
+fn array_copy_within(call_ctx: &mut CallContext, args: Vec<Value>) -> JSIResult<Value> {
+  let this_obj = get_this_object(call_ctx, "copyWithin")?;
+  let len = get_length_of_array_like(call_ctx.ctx, &this_obj)?;
+  let target = args.first().unwrap_or(&Value::Undefined).to_integer_or_infinity(call_ctx.ctx)?;
+  let mut to = resolve_relative_index(target, len);
+  let start = args.get(1).unwrap_or(&Value::Undefined).to_integer_or_infinity(call_ctx.ctx)?;
+  let mut from = resolve_relative_index(start, len);
+  let end = match args.get(2) {
+    None | Some(Value::Undefined) => len,
+    Some(end) => {
+      let end = end.to_integer_or_infinity(call_ctx.ctx)?;
+      resolve_relative_index(end, len)
+    },
+  };
+  let mut count = if end > from { (end - from).min(len - to) } else { 0 };
+  // 区间重叠时从后往前复制
+  let backwards = from < to && to < from + count;
+  if backwards {
+    from = from + count - 1;
+    to = to + count - 1;
+  }
+  let mut this = this_obj.borrow_mut();
+  while count > 0 {
+    let from_key = from.to_string();
+    if this.property.contains_key(&from_key) {
+      let value = this.get_value(from_key);
+      this.define_property(to.to_string(), Property { enumerable: true, value });
+    } else {
+      this.delete_property(to.to_string());
+    }
+    if backwards {
+      from = from.saturating_sub(1);
+      to = to.saturating_sub(1);
+    } else {
+      from += 1;
+      to += 1;
+    }
+    count -= 1;
+  }
+  Ok(call_ctx.this.clone())
+}
+
+// 创建数组迭代器，kind 为 keys、values 或 entries
+fn create_array_iterator(call_ctx: &mut CallContext, kind: &str) -> JSIResult<Value> {
+  let this_obj = get_this_object(call_ctx, kind)?;
+  let iterated = match &call_ctx.this {
+    Value::Array(_) | Value::Object(_) => call_ctx.this.clone(),
+    _ => Value::Object(this_obj),
+  };
+  let iterator = create_object(call_ctx.ctx, ClassType::Object, None);
+  let next_fun = builtin_function(call_ctx.ctx, String::from("next"), 0f64, array_iterator_next);
+  {
+    let mut iterator_mut = iterator.borrow_mut();
+    iterator_mut.set_inner_property_value(ARRAY_ITERATOR_ITERATED_OBJECT.to_string(), iterated);
+    iterator_mut.set_inner_property_value(ARRAY_ITERATOR_NEXT_INDEX.to_string(), Value::Number(0f64));
+    iterator_mut.set_inner_property_value(ARRAY_ITERATOR_KIND.to_string(), Value::String(kind.to_string()));
+    iterator_mut.define_property(String::from("next"), Property { enumerable: false, value: next_fun });
+  }
+  Ok(Value::Object(iterator))
+}
+
+// 数组迭代器前进一步，迭代结束时返回 None
+fn array_iterator_step(ctx: &mut Context, iterator: &Rc<RefCell<Object>>) -> JSIResult<Option<Value>> {
+  let (iterated, index, kind) = {
+    let iterator = iterator.borrow();
+    let iterated = iterator.get_inner_property_value(ARRAY_ITERATOR_ITERATED_OBJECT.to_string()).unwrap_or(Value::Undefined);
+    let index = match iterator.get_inner_property_value(ARRAY_ITERATOR_NEXT_INDEX.to_string()) {
+      Some(Value::Number(index)) => index as u64,
+      _ => 0,
+    };
+    let kind = iterator.get_inner_property_value(ARRAY_ITERATOR_KIND.to_string()).unwrap_or(Value::Undefined);
+    (iterated, index, kind)
+  };
+  if let Value::Undefined = iterated {
+    return Ok(None)
+  }
+  let iterated_obj = iterated.to_object(ctx);
+  let len = get_length_of_array_like(ctx, &iterated_obj)?;
+  let mut iterator_mut = iterator.borrow_mut();
+  if index >= len {
+    // 迭代完成之后不再关联原数组
+    iterator_mut.set_inner_property_value(ARRAY_ITERATOR_ITERATED_OBJECT.to_string(), Value::Undefined);
+    return Ok(None)
+  }
+  iterator_mut.set_inner_property_value(ARRAY_ITERATOR_NEXT_INDEX.to_string(), Value::Number((index + 1) as f64));
+  drop(iterator_mut);
+  let value = match kind {
+    Value::String(kind) if kind == "keys" => Value::Number(index as f64),
+    Value::String(kind) if kind == "entries" => {
+      let element = get_element(&iterated_obj, index);
+      create_array_from_values(ctx, vec![Value::Number(index as f64), element])
+    },
+    _ => get_element(&iterated_obj, index),
+  };
+  Ok(Some(value))
+}
+
+// 数组迭代器的 next 方法
+fn array_iterator_next(call_ctx: &mut CallContext, _: Vec<Value>) -> JSIResult<Value> {
+  let iterator = match &call_ctx.this {
+    Value::Object(obj) if is_array_iterator(&call_ctx.this) => Rc::clone(obj),
+    _ => return Err(JSIError::new(JSIErrorType::TypeError, String::from("next method called on incompatible receiver"), 0, 0)),
+  };
+  let (value, done) = match array_iterator_step(call_ctx.ctx, &iterator)? {
+    Some(value) => (value, false),
+    None => (Value::Undefined, true),
+  };
+  let result = create_object(call_ctx.ctx, ClassType::Object, None);
+  {
+    let mut result_mut = result.borrow_mut();
+    result_mut.define_property(String::from("value"), Property { enumerable: true, value });
+    result_mut.define_property(String::from("done"), Property { enumerable: true, value: Value::Boolean(done) });
+  }
+  Ok(Value::Object(result))
+}
+
+// 是否为数组迭代器（arr.keys()、arr.values()、arr.entries() 的返回值）
+pub fn is_array_iterator(value: &Value) -> bool {
+  match value {
+    Value::Object(obj) => obj.borrow().get_inner_property_value(ARRAY_ITERATOR_KIND.to_string()).is_some(),
+    _ => false,
+  }
+}
+
+// 获取可迭代对象的值列表，支持数组、字符串、Map 以及数组迭代器，其他值返回 None
+pub fn get_iterable_values(ctx: &mut Context, value: &Value) -> Option<Vec<Value>> {
+  match value {
+    Value::Array(arr) => {
+      let len = get_array_length(arr);
+      Some((0..len).map(|index| get_element(arr, index)).collect())
+    },
+    Value::String(_) | Value::Utf16String(_) | Value::StringObj(_) => {
+      // 字符串按照码点迭代，单独的代理项单独作为一项
+      let units = value.to_utf16(ctx);
+      Some(char::decode_utf16(units).map(|item| match item {
+        Ok(ch) => Value::String(ch.to_string()),
+        Err(err) => Value::Utf16String(vec![err.unpaired_surrogate()]),
+      }).collect())
+    },
+    Value::Object(obj) if is_map(value) => {
+      let mut entries: Vec<Value> = vec![];
+      for (key, value) in get_map_entries(obj) {
+        entries.push(create_array_from_values(ctx, vec![key, value]));
+      }
+      Some(entries)
+    },
+    Value::Object(obj) => {
+      if !is_array_iterator(value) {
+        return None
+      }
+      // 这里无法抛出错误，迭代过程中出错时结束迭代
+      let mut values: Vec<Value> = vec![];
+      while let Ok(Some(value)) = array_iterator_step(ctx, obj) {
+        values.push(value);
+      }
+      Some(values)
+    },
+    _ => None,
+  }
+}
+
+// Array.prototype.keys
+fn array_keys(call_ctx: &mut CallContext, _: Vec<Value>) -> JSIResult<Value> {
+  create_array_iterator(call_ctx, "keys")
+}
+
+// Array.prototype.values
+fn array_values(call_ctx: &mut CallContext, _: Vec<Value>) -> JSIResult<Value> {
+  create_array_iterator(call_ctx, "values")
+}
+
+// Array.prototype.entries
+fn array_entries(call_ctx: &mut CallContext, _: Vec<Value>) -> JSIResult<Value> {
+  create_array_iterator(call_ctx, "entries")
+}
+
+// SortCompare：有 comparefn 时按照 comparefn 的结果，否则按照字符串的 UTF-16 编码比较
+fn sort_compare(call_ctx: &mut CallContext, left: &Value, right: &Value, comparefn: &Option<Rc<RefCell<Object>>>) -> JSIResult<Ordering> {
+  if let Some(comparefn) = comparefn {
+    let result = call_callback(call_ctx, comparefn, &Value::Undefined, vec![left.clone(), right.clone()])?;
+    let result = result.to_number(call_ctx.ctx).unwrap_or(f64::NAN);
+    if result < 0f64 {
+      return Ok(Ordering::Less)
+    }
+    if result > 0f64 {
+      return Ok(Ordering::Greater)
+    }
+    return Ok(Ordering::Equal)
+  }
+  let left = left.to_string(call_ctx.ctx);
+  let right = right.to_string(call_ctx.ctx);
+  Ok(left.encode_utf16().cmp(right.encode_utf16()))
+}
+
+// 稳定的归并排序，comparefn 可能抛出异常，所以不使用 slice::sort_by
+fn merge_sort_values(call_ctx: &mut CallContext, mut values: Vec<Value>, comparefn: &Option<Rc<RefCell<Object>>>) -> JSIResult<Vec<Value>> {
+  if values.len() <= 1 {
+    return Ok(values)
+  }
+  let right = values.split_off(values.len() / 2);
+  let left = merge_sort_values(call_ctx, values, comparefn)?;
+  let right = merge_sort_values(call_ctx, right, comparefn)?;
+  let mut sorted: Vec<Value> = Vec::with_capacity(left.len() + right.len());
+  let mut left = left.into_iter().peekable();
+  let mut right = right.into_iter().peekable();
+  while let (Some(left_value), Some(right_value)) = (left.peek(), right.peek()) {
+    if sort_compare(call_ctx, right_value, left_value, comparefn)? == Ordering::Less {
+      sorted.push(right.next().unwrap());
+    } else {
+      sorted.push(left.next().unwrap());
+    }
+  }
+  sorted.extend(left);
+  sorted.extend(right);
+  Ok(sorted)
+}
+
+// 排序值列表，undefined 始终排在最后
+fn sort_values(call_ctx: &mut CallContext, values: Vec<Value>, comparefn: &Option<Rc<RefCell<Object>>>) -> JSIResult<Vec<Value>> {
+  let (defined, undefined): (Vec<Value>, Vec<Value>) = values.into_iter().partition(|value| !matches!(value, Value::Undefined));
+  let mut sorted = merge_sort_values(call_ctx, defined, comparefn)?;
+  sorted.extend(undefined);
+  Ok(sorted)
+}
+
+// Array.prototype.toSorted
+// arr.toSorted([compareFn])
+fn array_to_sorted(call_ctx: &mut CallContext, args: Vec<Value>) -> JSIResult<Value> {
+  let comparefn = match args.first() {
+    None | Some(Value::Undefined) => None,
+    Some(Value::Function(func)) => Some(Rc::clone(func)),
+    Some(_) => return Err(JSIError::new(JSIErrorType::TypeError, String::from("The comparison function must be either a function or undefined"), 0, 0)),
+  };
+  let this_obj = get_this_object(call_ctx, "toSorted")?;
+  let len = get_length_of_array_like(call_ctx.ctx, &this_obj)?;
+  let values = get_element_list(call_ctx.ctx, &this_obj, len)?;
+  let sorted = sort_values(call_ctx, values, &comparefn)?;
+  Ok(create_array_from_values(call_ctx.ctx, sorted))
+}
+
+// Array.prototype.toReversed
+// arr.toReversed()
+fn array_to_reversed(call_ctx: &mut CallContext, _: Vec<Value>) -> JSIResult<Value> {
+  let this_obj = get_this_object(call_ctx, "toReversed")?;
+  let len = get_length_of_array_like(call_ctx.ctx, &this_obj)?;
+  let mut values = get_element_list(call_ctx.ctx, &this_obj, len)?;
+  values.reverse();
+  Ok(create_array_from_values(call_ctx.ctx, values))
+}
+
+// Array.prototype.toSpliced
+// arr.toSpliced(start[, skipCount[, item1[, item2[, ...]]]])
+fn array_to_spliced(call_ctx: &mut CallContext, args: Vec<Value>) -> JSIResult<Value> {
+  let this_obj = get_this_object(call_ctx, "toSpliced")?;
+  let len = get_length_of_array_like(call_ctx.ctx, &this_obj)?;
+  let start = args.first().unwrap_or(&Value::Undefined).to_integer_or_infinity(call_ctx.ctx)?;
+  let actual_start = resolve_relative_index(start, len);
+  let skip_count = match args.len() {
+    0 => 0,
+    1 => len - actual_start,
+    _ => {
+      let skip = args[1].to_integer_or_infinity(call_ctx.ctx)?.max(0f64);
+      skip.min((len - actual_start) as f64) as u64
+    },
+  };
+  let items: Vec<Value> = if args.len() > 2 { args[2..].to_vec() } else { vec![] };
+  let new_len = len - skip_count + items.len() as u64;
+  if new_len as f64 > MAX_SAFE_INTEGER {
+    return Err(JSIError::new(JSIErrorType::TypeError, String::from("Invalid array length"), 0, 0));
+  }
+  if new_len > MAX_ARRAY_LENGTH {
+    return Err(JSIError::new(JSIErrorType::RangeError, String::from("Invalid array length"), 0, 0));
+  }
+  let mut values = get_element_list(call_ctx.ctx, &this_obj, actual_start)?;
+  values.extend(items);
+  {
+    let this = this_obj.borrow();
+    for index in (actual_start + skip_count)..len {
+      values.push(this.get_value(index.to_string()));
+    }
+  }
+  Ok(create_array_from_values(call_ctx.ctx, values))
+}
+
+// Array.prototype.with
+// arr.with(index, value)
+fn array_with(call_ctx: &mut CallContext, args: Vec<Value>) -> JSIResult<Value> {
+  let this_obj = get_this_object(call_ctx, "with")?;
+  let len = get_length_of_array_like(call_ctx.ctx, &this_obj)?;
+  let relative = args.first().unwrap_or(&Value::Undefined).to_integer_or_infinity(call_ctx.ctx)?;
+  let index = if relative >= 0f64 { relative } else { len as f64 + relative };
+  if index < 0f64 || index >= len as f64 {
+    return Err(JSIError::new(JSIErrorType::RangeError, format!("Invalid index : {}", relative), 0, 0));
+  }
+  let mut values = get_element_list(call_ctx.ctx, &this_obj, len)?;
+  values[index as usize] = args.get(1).cloned().unwrap_or(Value::Undefined);
+  Ok(create_array_from_values(call_ctx.ctx, values))
+}
@@ -1,25 +1,384 @@
-use std::{rc::Rc, cell::RefCell};
+use std::{rc::Rc, cell::RefCell, collections::HashMap, time::Instant};
+
+use crate::{context::{Context}, ast_node::{BuiltinFunction, ClassType, CallContext}, error::{JSIResult, format_error_stack}};
+use super::{array::get_iterable_values, number::number_to_string, object::{create_object, Property, Object}, function::builtin_function};
+use crate::{value::{Value}, inspect::{inspect, InspectOptions}};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConsoleLevel {
+  Log,
+  Info,
+  Debug,
+  Warn,
+  Error,
+  Trace,
+}
+
+// console 的输出目标，由宿主提供，如写入日志或者在测试中收集输出
+pub trait ConsoleSink {
+  fn write(&mut self, level: ConsoleLevel, message: &str);
+}
+
+impl<F: FnMut(ConsoleLevel, &str)> ConsoleSink for F {
+  fn write(&mut self, level: ConsoleLevel, message: &str) {
+    self(level, message)
+  }
+}
+
+// 默认输出，warn、error、trace 输出到 stderr，其他输出到 stdout
+pub struct StdioSink;
+
+impl ConsoleSink for StdioSink {
+  fn write(&mut self, level: ConsoleLevel, message: &str) {
+    match level {
+      ConsoleLevel::Warn | ConsoleLevel::Error | ConsoleLevel::Trace => eprintln!("{}", message),
+      _ => println!("{}", message),
+    }
+  }
+}
+
+// console 的状态，同一个 Context 中所有领域的 console 共享
+pub struct ConsoleState {
+  sink: Box<dyn ConsoleSink>,
+  // console.group 的层级，每层缩进两个空格
+  group_depth: usize,
+  timers: HashMap<String, Instant>,
+  counts: HashMap<String, usize>,
+}
+
+impl Default for ConsoleState {
+  fn default() -> Self {
+    ConsoleState {
+      sink: Box::new(StdioSink),
+      group_depth: 0,
+      timers: HashMap::new(),
+      counts: HashMap::new(),
+    }
+  }
+}
+
+impl ConsoleState {
+  pub fn set_sink(&mut self, sink: Box<dyn ConsoleSink>) {
+    self.sink = sink;
+  }
+
+  fn write(&mut self, level: ConsoleLevel, message: &str) {
+    if self.group_depth == 0 {
+      self.sink.write(level, message);
+      return;
+    }
+    let indent = "  ".repeat(self.group_depth);
+    let message = message.split('\n').map(|line| format!("{}{}", indent, line)).collect::<Vec<String>>().join("\n");
+    self.sink.write(level, &message);
+  }
+}
 
-use crate::{context::{Context}, ast_node::{ClassType, CallContext}, error::JSIResult};
-use super::{object::{create_object, Property, Object},function::builtin_function};
-use crate::{value::{Value}};
 pub fn create_console(ctx: &mut Context) -> Rc<RefCell<Object>> {
   let console_obj = create_object(ctx, ClassType::Object, None);
   let console_rc = Rc::clone(&console_obj);
   let mut console = console_rc.borrow_mut();
-  // console.log
-  let name = String::from("log");
-  console.property.insert(name.clone(), Property { enumerable: true, value: builtin_function(ctx, name, 0f64, console_log) });
+  let methods: [(&str, BuiltinFunction); 14] = [
+    ("log", console_log),
+    ("info", console_info),
+    ("debug", console_debug),
+    ("warn", console_warn),
+    ("error", console_error),
+    ("trace", console_trace),
+    ("assert", console_assert),
+    ("table", console_table),
+    ("group", console_group),
+    ("groupEnd", console_group_end),
+    ("time", console_time),
+    ("timeEnd", console_time_end),
+    ("count", console_count),
+    ("countReset", console_count_reset),
+  ];
+  for (name, fun) in methods.iter() {
+    let name = name.to_string();
+    console.property.insert(name.clone(), Property { enumerable: true, value: builtin_function(ctx, name, 0f64, *fun) });
+  }
   console_obj
 }
 
+fn write(ctx: &mut Context, level: ConsoleLevel, message: &str) {
+  ctx.console.write(level, message);
+}
+
+// 格式化参数，第一个参数是字符串时支持 %s %d %i %f %o %O %c %%
+pub fn format_args(ctx: &mut Context, args: &[Value]) -> String {
+  let mut parts: Vec<String> = vec![];
+  let mut rest = args;
+  if let Some(Value::String(format)) = args.first() {
+    rest = &args[1..];
+    let mut result = String::new();
+    let mut chars = format.chars().peekable();
+    while let Some(char) = chars.next() {
+      if char != '%' {
+        result.push(char);
+        continue;
+      }
+      let specifier = match chars.peek() {
+        Some(specifier) => *specifier,
+        None => {
+          result.push(char);
+          break;
+        },
+      };
+      if specifier == '%' {
+        chars.next();
+        result.push('%');
+        continue;
+      }
+      if !"sdifoOc".contains(specifier) || rest.is_empty() {
+        result.push(char);
+        continue;
+      }
+      chars.next();
+      let value = rest[0].clone();
+      rest = &rest[1..];
+      match specifier {
+        's' => result.push_str(&value.to_string(ctx)),
+        'd' | 'i' | 'f' => {
+          let number = match &value {
+            Value::Object(_) | Value::Array(_) | Value::Function(_) | Value::Promise(_) => None,
+            _ => value.to_number(ctx),
+          };
+          let number = match number {
+            Some(number) if specifier == 'i' => number.trunc(),
+            Some(number) => number,
+            None => f64::NAN,
+          };
+          result.push_str(&number_to_string(number));
+        },
+        'o' | 'O' => result.push_str(&inspect(&value, &InspectOptions::default())),
+        // %c 是浏览器中的样式，直接忽略
+        _ => {},
+      }
+    }
+    parts.push(result);
+  }
+  for value in rest.iter() {
+    parts.push(format_value(ctx, value));
+  }
+  parts.join(" ")
+}
+
+// 字符串直接输出，其他值通过 inspect 格式化
+fn format_value(ctx: &mut Context, value: &Value) -> String {
+  match value {
+    Value::String(_) | Value::Utf16String(_) => value.to_string(ctx),
+    _ => inspect(value, &InspectOptions::default()),
+  }
+}
+
+fn console_write(call_ctx: &mut CallContext, level: ConsoleLevel, args: Vec<Value>) -> JSIResult<Value> {
+  let message = format_args(call_ctx.ctx, &args);
+  write(call_ctx.ctx, level, &message);
+  Ok(Value::Undefined)
+}
 
 // console.log
 fn console_log(call_ctx: &mut CallContext, args: Vec<Value>) -> JSIResult<Value> {
-  let mut strs: Vec<String> = vec![];
-  for arg in args.iter() {
-    strs.push(arg.to_string(call_ctx.ctx));
+  console_write(call_ctx, ConsoleLevel::Log, args)
+}
+
+// console.info
+fn console_info(call_ctx: &mut CallContext, args: Vec<Value>) -> JSIResult<Value> {
+  console_write(call_ctx, ConsoleLevel::Info, args)
+}
+
+// console.debug
+fn console_debug(call_ctx: &mut CallContext, args: Vec<Value>) -> JSIResult<Value> {
+  console_write(call_ctx, ConsoleLevel::Debug, args)
+}
+
+// console.warn
+fn console_warn(call_ctx: &mut CallContext, args: Vec<Value>) -> JSIResult<Value> {
+  console_write(call_ctx, ConsoleLevel::Warn, args)
+}
+
+// console.error
+fn console_error(call_ctx: &mut CallContext, args: Vec<Value>) -> JSIResult<Value> {
+  console_write(call_ctx, ConsoleLevel::Error, args)
+}
+
+// console.trace，输出 Trace: message 以及当前的调用栈
+fn console_trace(call_ctx: &mut CallContext, args: Vec<Value>) -> JSIResult<Value> {
+  let message = format_args(call_ctx.ctx, &args);
+  let stack = format_error_stack("Trace", &message, &call_ctx.ctx.get_call_stack());
+  write(call_ctx.ctx, ConsoleLevel::Trace, &stack);
+  Ok(Value::Undefined)
+}
+
+// console.assert(condition, ...data)
+fn console_assert(call_ctx: &mut CallContext, args: Vec<Value>) -> JSIResult<Value> {
+  let condition = args.first().map(|value| value.to_boolean(call_ctx.ctx)).unwrap_or(false);
+  if condition {
+    return Ok(Value::Undefined);
   }
-  println!("{}", strs.join(" "));
+  let message = format_args(call_ctx.ctx, args.get(1..).unwrap_or(&[]));
+  let message = if message.is_empty() {
+    String::from("Assertion failed")
+  } else {
+    format!("Assertion failed: {}", message)
+  };
+  write(call_ctx.ctx, ConsoleLevel::Error, &message);
   Ok(Value::Undefined)
-}
\ No newline at end of file
+}
+
+// console.table(data, columns)，数据不是对象时和 console.log 相同
+fn console_table(call_ctx: &mut CallContext, args: Vec<Value>) -> JSIResult<Value> {
+  let data = args.first().cloned().unwrap_or(Value::Undefined);
+  let rows: Vec<(String, Value)> = match &data {
+    Value::Array(_) => get_iterable_values(call_ctx.ctx, &data).unwrap_or_default().into_iter().enumerate().map(|(index, value)| (index.to_string(), value)).collect(),
+    Value::Object(object) => {
+      let object = object.borrow();
+      object.property_list.iter().filter_map(|key| {
+        object.property.get(key).filter(|property| property.enumerable).map(|property| (key.clone(), property.value.clone()))
+      }).collect()
+    },
+    _ => return console_write(call_ctx, ConsoleLevel::Log, args),
+  };
+  let filter: Option<Vec<String>> = match args.get(1) {
+    Some(columns @ Value::Array(_)) => Some(get_iterable_values(call_ctx.ctx, columns).unwrap_or_default().iter().map(|column| column.to_string(call_ctx.ctx)).collect()),
+    _ => None,
+  };
+
+  // 对象的属性作为列，基本类型的值放在 Values 列
+  let mut columns: Vec<String> = vec![];
+  let mut has_values = false;
+  let mut cells: Vec<HashMap<String, String>> = vec![];
+  for (_, value) in rows.iter() {
+    let mut row = HashMap::new();
+    let object = match value {
+      Value::Object(object) | Value::Array(object) => Some(Rc::clone(object)),
+      _ => None,
+    };
+    match object {
+      Some(object) => {
+        let entries: Vec<(String, Value)> = {
+          let object = object.borrow();
+          object.property_list.iter().filter_map(|key| {
+            object.property.get(key).filter(|property| property.enumerable).map(|property| (key.clone(), property.value.clone()))
+          }).collect()
+        };
+        for (key, value) in entries.into_iter() {
+          if !columns.contains(&key) {
+            columns.push(key.clone());
+          }
+          row.insert(key, format_table_cell(&value));
+        }
+      },
+      None => {
+        has_values = true;
+        row.insert(String::new(), format_table_cell(value));
+      },
+    }
+    cells.push(row);
+  }
+  if let Some(filter) = filter {
+    columns = filter;
+  }
+
+  let mut header = vec![String::from("(index)")];
+  header.extend(columns.iter().cloned());
+  if has_values {
+    header.push(String::from("Values"));
+  }
+  let mut table: Vec<Vec<String>> = vec![];
+  for ((index, _), row) in rows.iter().zip(cells.iter()) {
+    let mut line = vec![index.clone()];
+    for column in columns.iter() {
+      line.push(row.get(column).cloned().unwrap_or_default());
+    }
+    if has_values {
+      line.push(row.get("").cloned().unwrap_or_default());
+    }
+    table.push(line);
+  }
+  let widths: Vec<usize> = (0..header.len()).map(|column| {
+    table.iter().map(|line| line[column].chars().count()).chain([header[column].chars().count()]).max().unwrap_or(0) + 2
+  }).collect();
+  let border = |left: &str, middle: &str, right: &str| {
+    format!("{}{}{}", left, widths.iter().map(|width| "─".repeat(*width)).collect::<Vec<String>>().join(middle), right)
+  };
+  let format_line = |line: &Vec<String>| {
+    let cells: Vec<String> = line.iter().zip(widths.iter()).map(|(cell, width)| format!(" {}{}", cell, " ".repeat(width - 1 - cell.chars().count()))).collect();
+    format!("│{}│", cells.join("│"))
+  };
+  let mut output = vec![border("┌", "┬", "┐"), format_line(&header), border("├", "┼", "┤")];
+  output.extend(table.iter().map(format_line));
+  output.push(border("└", "┴", "┘"));
+  write(call_ctx.ctx, ConsoleLevel::Log, &output.join("\n"));
+  Ok(Value::Undefined)
+}
+
+fn format_table_cell(value: &Value) -> String {
+  inspect(value, &InspectOptions { depth: 0, ..InspectOptions::default() })
+}
+
+// console.group(...label)，之后的输出增加缩进
+fn console_group(call_ctx: &mut CallContext, args: Vec<Value>) -> JSIResult<Value> {
+  if !args.is_empty() {
+    console_write(call_ctx, ConsoleLevel::Log, args)?;
+  }
+  call_ctx.ctx.console.group_depth += 1;
+  Ok(Value::Undefined)
+}
+
+// console.groupEnd
+fn console_group_end(call_ctx: &mut CallContext, _: Vec<Value>) -> JSIResult<Value> {
+  let console = &mut call_ctx.ctx.console;
+  console.group_depth = console.group_depth.saturating_sub(1);
+  Ok(Value::Undefined)
+}
+
+fn get_label(ctx: &mut Context, args: &[Value]) -> String {
+  match args.first() {
+    None | Some(Value::Undefined) => String::from("default"),
+    Some(label) => label.to_string(ctx),
+  }
+}
+
+// console.time(label)
+fn console_time(call_ctx: &mut CallContext, args: Vec<Value>) -> JSIResult<Value> {
+  let label = get_label(call_ctx.ctx, &args);
+  if call_ctx.ctx.console.timers.contains_key(&label) {
+    write(call_ctx.ctx, ConsoleLevel::Warn, &format!("Warning: Label '{}' already exists for console.time()", label));
+    return Ok(Value::Undefined);
+  }
+  call_ctx.ctx.console.timers.insert(label, Instant::now());
+  Ok(Value::Undefined)
+}
+
+// console.timeEnd(label)，输出 label: 1.234ms
+fn console_time_end(call_ctx: &mut CallContext, args: Vec<Value>) -> JSIResult<Value> {
+  let label = get_label(call_ctx.ctx, &args);
+  match call_ctx.ctx.console.timers.remove(&label) {
+    Some(start) => {
+      let elapsed = start.elapsed().as_secs_f64() * 1000f64;
+      write(call_ctx.ctx, ConsoleLevel::Log, &format!("{}: {:.3}ms", label, elapsed));
+    },
+    None => {
+      write(call_ctx.ctx, ConsoleLevel::Warn, &format!("Warning: No such label '{}' for console.timeEnd()", label));
+    },
+  }
+  Ok(Value::Undefined)
+}
+
+// console.count(label)，输出 label: 调用次数
+fn console_count(call_ctx: &mut CallContext, args: Vec<Value>) -> JSIResult<Value> {
+  let label = get_label(call_ctx.ctx, &args);
+  let count = call_ctx.ctx.console.counts.entry(label.clone()).or_insert(0);
+  *count += 1;
+  let message = format!("{}: {}", label, count);
+  write(call_ctx.ctx, ConsoleLevel::Log, &message);
+  Ok(Value::Undefined)
+}
+
+// console.countReset(label)
+fn console_count_reset(call_ctx: &mut CallContext, args: Vec<Value>) -> JSIResult<Value> {
+  let label = get_label(call_ctx.ctx, &args);
+  call_ctx.ctx.console.counts.remove(&label);
+  Ok(Value::Undefined)
+}
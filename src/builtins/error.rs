@@ -1,8 +1,10 @@
 use std::{rc::Rc};
-use crate::constants::{PROTO_PROPERTY_NAME, GLOBAL_TYPE_ERROR_NAME};
+use crate::constants::{PROTO_PROPERTY_NAME, GLOBAL_AGGREGATE_ERROR_NAME};
 use crate::context::{Context};
+use crate::error::{format_error_stack, JSIError, JSIErrorType};
 use crate::{value::{Value, INSTANTIATE_OBJECT_METHOD_NAME}, ast_node::{ClassType, CallContext}, constants::GLOBAL_ERROR_NAME, error::JSIResult};
 
+use super::array::{create_array_from_values, get_iterable_values};
 use super::global::{get_global_object_prototype_by_name, get_global_object_by_name};
 use super::{object::{create_object, Property},function::builtin_function};
 
@@ -20,42 +22,120 @@ use super::{object::{create_object, Property},function::builtin_function};
 
   error_mut.constructor = Some(Rc::downgrade(&global_error));
 
-  let msg =  init.to_string(ctx);
-
-  error_mut.define_property(String::from("message"),  Property { enumerable: true, value: Value::String(msg)});
+  // 没有传入 message 时使用原型上的空字符串
+  let mut msg = String::new();
+  if !matches!(init, Value::Undefined) {
+    msg =  init.to_string(ctx);
+    error_mut.define_property(String::from("message"),  Property { enumerable: false, value: Value::String(msg.clone())});
+  }
+  // 创建时的调用栈
+  let stack = format_error_stack(error_type, &msg, &ctx.get_call_stack());
+  error_mut.define_property(String::from("stack"),  Property { enumerable: false, value: Value::String(stack)});
   Value::Object(error)
 }
 
 pub fn bind_global_error(ctx: &mut Context, error_type: &str) {
   // Error
-  let create_function = builtin_function(ctx, error_type.to_string(), 1f64, create);
+  let create_function = if error_type == GLOBAL_AGGREGATE_ERROR_NAME {
+    builtin_function(ctx, error_type.to_string(), 2f64, create_aggregate)
+  } else {
+    builtin_function(ctx, error_type.to_string(), 1f64, create)
+  };
+  let to_string_function = builtin_function(ctx, String::from("toString"), 0f64, to_string);
 
   let error_rc = get_global_object_by_name(ctx, error_type);
   let mut error = (*error_rc).borrow_mut();
   error.set_inner_property_value(INSTANTIATE_OBJECT_METHOD_NAME.to_string(), create_function);
-  if error_type != GLOBAL_ERROR_NAME {
-    return
-  }
-  if let Some(prop)= &error.prototype {
-    let prototype_rc = Rc::clone(prop);
-    let mut prototype = prototype_rc.borrow_mut();
+  let prototype_rc = match &error.prototype {
+    Some(prop) => Rc::clone(prop),
+    None => return,
+  };
+  let mut prototype = prototype_rc.borrow_mut();
+  // Error.prototype.name、Error.prototype.message
+  prototype.define_property(String::from("name"), Property { enumerable: false, value: Value::String(error_type.to_string()) });
+  prototype.define_property(String::from("message"), Property { enumerable: false, value: Value::String(String::new()) });
+  if error_type == GLOBAL_ERROR_NAME {
+    // Error.prototype.toString
     let name = String::from("toString");
-    prototype.define_property(name.clone(), Property { enumerable: true, value: builtin_function(ctx, name, 0f64, to_string) });
+    prototype.define_property(name, Property { enumerable: true, value: to_string_function });
+    return
   }
+  // 其他错误类型继承 Error：RangeError.__proto__ === Error，RangeError.prototype.__proto__ === Error.prototype
+  let global_error = get_global_object_by_name(ctx, GLOBAL_ERROR_NAME);
+  let global_error_prototype = get_global_object_prototype_by_name(ctx, GLOBAL_ERROR_NAME);
+  error.set_inner_property_value(PROTO_PROPERTY_NAME.to_string(), Value::RefObject(Rc::downgrade(&global_error)));
+  prototype.set_inner_property_value(PROTO_PROPERTY_NAME.to_string(), Value::RefObject(Rc::downgrade(&global_error_prototype)));
 }
 
 // 创建实例化对象
 fn create(call_ctx: &mut CallContext, args: Vec<Value>) -> JSIResult<Value> {
-  let mut param = Value::Undefined;
-  if args.len() > 0 {
-    param = args[0].clone();
+  let message = args.first().cloned().unwrap_or(Value::Undefined);
+  let error = create_error(call_ctx.ctx, message, call_ctx.func_name.as_str());
+  install_error_cause(call_ctx.ctx, &error, args.get(1));
+  Ok(error)
+}
+
+// new AggregateError(errors, message, options)
+fn create_aggregate(call_ctx: &mut CallContext, args: Vec<Value>) -> JSIResult<Value> {
+  let errors = args.first().cloned().unwrap_or(Value::Undefined);
+  let message = args.get(1).cloned().unwrap_or(Value::Undefined);
+  let error = create_error(call_ctx.ctx, message, GLOBAL_AGGREGATE_ERROR_NAME);
+  install_error_cause(call_ctx.ctx, &error, args.get(2));
+  let errors = match get_iterable_values(call_ctx.ctx, &errors) {
+    Some(errors) => errors,
+    None => return Err(JSIError::new(JSIErrorType::TypeError, format!("{} is not iterable", errors.type_of()), 0, 0)),
+  };
+  let errors = create_array_from_values(call_ctx.ctx, errors);
+  if let Value::Object(error_obj) = &error {
+    error_obj.borrow_mut().define_property(String::from("errors"), Property { enumerable: false, value: errors });
+  }
+  Ok(error)
+}
+
+// InstallErrorCause：options 中存在 cause 属性时，设置 error.cause
+fn install_error_cause(ctx: &mut Context, error: &Value, options: Option<&Value>) {
+  let options = match options {
+    Some(options) if options.is_object() => options,
+    _ => return,
+  };
+  let options_obj = options.to_object(ctx);
+  let has_cause = {
+    let options_obj = options_obj.borrow();
+    options_obj.property.contains_key("cause") || !matches!(options_obj.get_value(String::from("cause")), Value::Undefined)
+  };
+  if !has_cause {
+    return
+  }
+  let cause = options_obj.borrow().get_value(String::from("cause"));
+  if let Value::Object(error_obj) = error {
+    error_obj.borrow_mut().define_property(String::from("cause"), Property { enumerable: false, value: cause });
   }
-  Ok(create_error(call_ctx.ctx, param, call_ctx.func_name.as_str()))
 }
 
 // Error.prototype.toString
-fn to_string(_: &mut CallContext, _: Vec<Value>) -> JSIResult<Value> {
-  // let this = call_ctx.this;
-  // TODO:
-  Ok(Value::String(String::from("err")))
-}
\ No newline at end of file
+fn to_string(call_ctx: &mut CallContext, _: Vec<Value>) -> JSIResult<Value> {
+  if !call_ctx.this.is_object() {
+    let this = call_ctx.this.to_string(call_ctx.ctx);
+    return Err(JSIError::new(JSIErrorType::TypeError, format!("Method Error.prototype.toString called on incompatible receiver {}", this), 0, 0))
+  }
+  let this_obj = call_ctx.this.to_object(call_ctx.ctx);
+  let (name, message) = {
+    let this = this_obj.borrow();
+    (this.get_value(String::from("name")), this.get_value(String::from("message")))
+  };
+  let name = match name {
+    Value::Undefined => String::from("Error"),
+    _ => name.to_string(call_ctx.ctx),
+  };
+  let message = match message {
+    Value::Undefined => String::new(),
+    _ => message.to_string(call_ctx.ctx),
+  };
+  if name.is_empty() {
+    return Ok(Value::String(message))
+  }
+  if message.is_empty() {
+    return Ok(Value::String(name))
+  }
+  Ok(Value::String(format!("{}: {}", name, message)))
+}
@@ -1,6 +1,6 @@
 use std::{rc::{Rc, Weak}, cell::RefCell};
-use crate::{ast_node::{BlockStatement, IdentifierLiteral, Parameter}, bytecode::ByteCode, constants::{GLOBAL_FUNCTION_NAME, PROTO_PROPERTY_NAME}, context::Context, error::{JSIError, JSIErrorType}};
-use crate::{ast_node::{Statement, FunctionDeclaration, BuiltinFunction, ClassType, CallContext}, value::{Value, INSTANTIATE_OBJECT_METHOD_NAME}, scope::Scope, error::JSIResult};
+use crate::{ast_node::{BlockStatement, IdentifierLiteral, Parameter, Span}, bytecode::ByteCode, constants::{GLOBAL_FUNCTION_NAME, PROTO_PROPERTY_NAME}, context::Context, error::{JSIError, JSIErrorType}};
+use crate::{ast_node::{Statement, FunctionDeclaration, BuiltinFunction, HostFunction, ClassType, CallContext}, value::{Value, INSTANTIATE_OBJECT_METHOD_NAME}, scope::Scope, error::JSIResult};
 
 use super::{object::{create_object, Property, Object}, global::{get_global_object_prototype_by_name, get_global_object_by_name}, array::create_list_from_array_list};
 
@@ -33,7 +33,8 @@ use super::{object::{create_object, Property, Object}, global::{get_global_objec
   function_mut.set_inner_property_value(String::from("bytecode"), Value::ByteCode(function_declaration.bytecode.clone()));
 
   // function prototype
-  let prototype =  Rc::new(RefCell::new(Object::new(ClassType::Object, None)));
+  let prototype =  Rc::new(RefCell::new(Object::new(&ctx.object_ids, ClassType::Object, None)));
+  ctx.heap.track_object(&prototype);
   let prototype_clone = Rc::clone(&prototype);
   let mut prototype_mut = prototype_clone.borrow_mut();
   // function.prototype.constructor 指向自己
@@ -53,13 +54,16 @@ pub fn create_function_with_bytecode(ctx: &mut Context, name: String, parameters
     is_async: false,
     name: IdentifierLiteral {
       literal: name,
+      span: Span::default(),
     },
     parameters,
     body: BlockStatement {
       statements: vec![],
+      span: Span::default(),
     },
     declarations: vec![],
-    bytecode
+    bytecode,
+    span: Span::default(),
   };
   create_function(ctx, &function_declaration, define_scope)
 }
@@ -88,6 +92,40 @@ pub fn builtin_function(ctx: &mut Context, name: String, length: f64, fun: Built
   Value::Function(function)
 }
 
+// 构建宿主方法，和内置方法不同，宿主方法可以通过 new 调用
+pub fn host_function(ctx: &mut Context, name: String, length: f64, fun: HostFunction) -> Value {
+  let global_function = get_global_object_by_name(ctx, GLOBAL_FUNCTION_NAME);
+  let function = create_object(ctx, ClassType::Function, Some(Box::new(Statement::HostFunction(fun))));
+  let function_clone = Rc::clone(&function);
+  let mut function_mut = (*function_clone).borrow_mut();
+  // 绑定 fun.constructor = global.Function
+  function_mut.constructor = Some(Rc::downgrade(&global_function));
+  // fun.name
+  function_mut.define_property(String::from("name"), Property {
+    enumerable: false,
+    value: Value::String(name),
+  });
+  // fun.length
+  function_mut.define_property(String::from("length"), Property {
+    enumerable: false,
+    value: Value::Number(length)
+  });
+
+  let global_prototype = get_global_object_prototype_by_name(ctx, GLOBAL_FUNCTION_NAME);
+  function_mut.set_inner_property_value(PROTO_PROPERTY_NAME.to_string(), Value::RefObject(Rc::downgrade(&global_prototype)));
+
+  // function prototype
+  let prototype =  Rc::new(RefCell::new(Object::new(&ctx.object_ids, ClassType::Object, None)));
+  ctx.heap.track_object(&prototype);
+  // function.prototype.constructor 指向自己
+  prototype.borrow_mut().define_property(String::from("constructor"), Property {
+    enumerable: false,
+    value: Value::RefObject(Rc::downgrade(&function)),
+  });
+  function_mut.prototype = Some(prototype);
+  Value::Function(function)
+}
+
 
 pub fn bind_global_function(ctx: &mut Context) {
 
@@ -125,17 +163,23 @@ fn function_create(call_ctx: &mut CallContext, args: Vec<Value>) -> JSIResult<Va
       is_async: false,
       name: IdentifierLiteral {
         literal: String::from("anonymous"),
+        span: Span::default(),
       },
       parameters: vec![],
       body: BlockStatement {
         statements: vec![],
+        span: Span::default(),
       },
       declarations: vec![],
-      bytecode: vec![]
+      bytecode: vec![],
+      span: Span::default(),
     };
     return Ok(create_function(call_ctx.ctx, &function_declaration, Rc::clone(&call_ctx.ctx.cur_scope)));
   }
 
+  if !call_ctx.ctx.code_generation {
+    return Err(JSIError::new(JSIErrorType::EvalError, String::from("Code generation from strings disallowed for this context"), 0, 0));
+  }
   // Extract parameter names and body
   let body = args[args.len() - 1].to_string(call_ctx.ctx);
   let param_names: Vec<String> = if args.len() > 1 {
@@ -203,9 +247,11 @@ fn function_bind(ctx: &mut CallContext, args: Vec<Value>) -> JSIResult<Value> {
   }
   if let Value::Function(function_object) = &ctx.this {
     let fun_obj = function_object.borrow();
-    let mut new_fun = fun_obj.force_copy();
+    let mut new_fun = fun_obj.force_copy(&ctx.ctx.object_ids);
     new_fun.set_inner_property_value(String::from("this"), this);
-    Ok(Value::Function(Rc::new(RefCell::new(new_fun))))
+    let new_fun = Rc::new(RefCell::new(new_fun));
+    ctx.ctx.heap.track_object(&new_fun);
+    Ok(Value::Function(new_fun))
   } else {
     Err(JSIError::new(JSIErrorType::TypeError, format!("Bind must be called on a function
     "), 0, 0))
@@ -3,7 +3,7 @@ use std::rc::{Rc};
 
 use crate::ast_node::{ClassType, CallContext};
 use crate::builtins::promise::bind_global_promise;
-use crate::constants::{GLOBAL_OBJECT_NAME_LIST, GLOBAL_OBJECT_NAME, PROTO_PROPERTY_NAME, GLOBAL_ERROR_NAME, GLOBAL_TYPE_ERROR_NAME};
+use crate::constants::{GLOBAL_OBJECT_NAME_LIST, GLOBAL_OBJECT_NAME, PROTO_PROPERTY_NAME, GLOBAL_ERROR_NAME, GLOBAL_NATIVE_ERROR_NAME_LIST};
 use crate::value::Value;
 use crate::context::{Context};
 use crate::error::{JSIResult, JSIError, JSIErrorType};
@@ -11,21 +11,22 @@ use super::array::bind_global_array;
 use super::boolean::{bind_global_boolean};
 use super::error::{bind_global_error};
 use super::function::{bind_global_function, builtin_function};
+use super::map::bind_global_map;
 use super::number::bind_global_number;
-use super::object::{Object, Property, bind_global_object};
+use super::object::{Object, ObjectIds, Property, bind_global_object};
 use super::string::bind_global_string;
 
 pub const IS_GLOABL_OBJECT: &str = "isGlobal";
 
-pub fn new_global_object() -> Rc<RefCell<Object>> {
-  let object = Rc::new(RefCell::new(Object::new(ClassType::Object, None)));
+pub fn new_global_object(object_ids: &ObjectIds) -> Rc<RefCell<Object>> {
+  let object = Rc::new(RefCell::new(Object::new(object_ids, ClassType::Object, None)));
   let object_clone = Rc::clone(&object);
   let mut object_mut = (*object_clone).borrow_mut();
 
   // 创建原型对象 prototype
   // Object.prototype 是所有对象的原型
   // 原型上面的方法，通过 bind_global_object 挂载
-  let prototype =  Rc::new(RefCell::new(Object::new(ClassType::Object, None)));
+  let prototype =  Rc::new(RefCell::new(Object::new(object_ids, ClassType::Object, None)));
   let prototype_clone = Rc::clone(&prototype);
   let mut prototype_mut = prototype_clone.borrow_mut();
   prototype_mut.define_property(String::from("constructor"), Property {
@@ -37,15 +38,15 @@ pub fn new_global_object() -> Rc<RefCell<Object>> {
 }
 
 // 全局对象
-pub fn new_global_this() -> Rc<RefCell<Object>> {
+pub fn new_global_this(object_ids: &ObjectIds) -> Rc<RefCell<Object>> {
   // 先创建全局 Object，以及 Object.prototype
-  let first_obj = new_global_object();
+  let first_obj = new_global_object(object_ids);
   let first_obj_clone = Rc::clone(&first_obj);
   let mut first_obj_borrow = (*first_obj_clone).borrow_mut();
   first_obj_borrow.set_inner_property_value(IS_GLOABL_OBJECT.to_string(), Value::Boolean(true));
   first_obj_borrow.set_inner_property_value(String::from("name"), Value::String(GLOBAL_OBJECT_NAME.to_string()));
   // native function
-  let native_function = new_global_object();
+  let native_function = new_global_object(object_ids);
   {
     let native_function_rc = Rc::clone(&native_function);
     let mut native_borrow = native_function_rc.borrow_mut();
@@ -59,7 +60,7 @@ pub fn new_global_this() -> Rc<RefCell<Object>> {
   }
   
   // Global
-  let global = new_global_object();
+  let global = new_global_object(object_ids);
   let global_clone = Rc::clone(&global);
   {
     let mut global_obj = global_clone.borrow_mut();
@@ -69,7 +70,7 @@ pub fn new_global_this() -> Rc<RefCell<Object>> {
       if name == &GLOBAL_OBJECT_NAME {
         continue;
       }
-      let object = new_global_object();
+      let object = new_global_object(object_ids);
       let object_rc = Rc::clone(&object);
       let mut object_borrow = object_rc.borrow_mut();
       // 绑定当前对象的原型
@@ -105,10 +106,8 @@ pub fn bind_global(ctx: &mut Context) {
 
   // 绑定 Promise 的 静态方法 和 原型链方法
   bind_global_promise(ctx);
-  // 绑定  Error 的 静态方法 和 原型链方法
-  bind_global_error(ctx, GLOBAL_ERROR_NAME);
-  bind_global_error(ctx, GLOBAL_TYPE_ERROR_NAME);
-
+  // 绑定 Map 的 静态方法 和 原型链方法
+  bind_global_map(ctx);
   let obj_rc = get_global_object(ctx, GLOBAL_OBJECT_NAME.to_string());
   let obj_rc =  obj_rc.borrow();
   let obj_prototype_rc = &obj_rc.prototype;
@@ -131,9 +130,19 @@ pub fn bind_global(ctx: &mut Context) {
       }
     }
   }
+  drop(obj_rc);
+
+  // 绑定  Error 的 静态方法 和 原型链方法，其他错误类型的原型链指向 Error.prototype
+  bind_global_error(ctx, GLOBAL_ERROR_NAME);
+  for name in GLOBAL_NATIVE_ERROR_NAME_LIST.iter() {
+    bind_global_error(ctx, name);
+  }
 }
 
 pub fn get_global_object(ctx: &mut Context, name: String) -> Rc<RefCell<Object>> {
+  if let Some(intrinsic) = ctx.get_intrinsic(&name) {
+    return intrinsic;
+  }
 
   let value = {
     let clone_global_mut = ctx.global.borrow_mut();
@@ -145,6 +154,9 @@ pub fn get_global_object(ctx: &mut Context, name: String) -> Rc<RefCell<Object>>
 }
 
 pub fn get_global_object_by_name(ctx: &mut Context, name: &str) -> Rc<RefCell<Object>> {
+  if let Some(intrinsic) = ctx.get_intrinsic(name) {
+    return intrinsic;
+  }
   let value = {
     let clone_global_mut = ctx.global.borrow_mut();
     clone_global_mut.get_value(name.to_string().clone())
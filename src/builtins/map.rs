@@ -0,0 +1,404 @@
+use std::cell::{RefCell};
+use std::collections::HashMap;
+use std::mem::size_of;
+use std::rc::Rc;
+use crate::ast_node::{CallContext, ClassType};
+use crate::constants::{GLOBAL_MAP_NAME, PROTO_PROPERTY_NAME};
+use crate::context::{Context};
+use crate::error::{JSIResult, JSIError, JSIErrorType};
+use crate::heap::value_payload_size;
+use crate::value::{Value, INSTANTIATE_OBJECT_METHOD_NAME};
+
+use super::array::{call_callback, create_array_from_values, get_iterable_values};
+use super::function::builtin_function;
+use super::global::{get_global_object_by_name, get_global_object_prototype_by_name};
+use super::object::{create_object, group_by, Accessor, Object, Property};
+
+// Map 的键，按照 SameValueZero 比较：NaN 等于 NaN，-0 等于 +0，对象按照引用比较
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+enum MapKey {
+  String(String),
+  // 包含单独代理项的字符串
+  Utf16String(Vec<u16>),
+  // 数字的二进制表示，NaN 和 -0 已经统一
+  Number(u64),
+  Boolean(bool),
+  Null,
+  Undefined,
+  Object(usize),
+}
+
+impl MapKey {
+  fn from_value(value: &Value) -> MapKey {
+    match value {
+      Value::String(string) => MapKey::String(string.clone()),
+      Value::Utf16String(units) => match String::from_utf16(units) {
+        Ok(string) => MapKey::String(string),
+        Err(_) => MapKey::Utf16String(units.clone()),
+      },
+      Value::NAN => MapKey::Number(f64::NAN.to_bits()),
+      Value::Number(number) if number.is_nan() => MapKey::Number(f64::NAN.to_bits()),
+      // -0 + 0 为 +0
+      Value::Number(number) => MapKey::Number((number + 0f64).to_bits()),
+      Value::Boolean(boolean) => MapKey::Boolean(*boolean),
+      Value::Null => MapKey::Null,
+      Value::Undefined => MapKey::Undefined,
+      _ => match value.to_weak_rc_object() {
+        Some(object) => MapKey::Object(object.as_ptr() as *const () as usize),
+        None => MapKey::Undefined,
+      },
+    }
+  }
+}
+
+// 每个键值对额外占用的内存
+const MAP_ENTRY_SIZE: usize = size_of::<Option<(Value, Value)>>() + size_of::<MapKey>() + size_of::<usize>();
+
+// Map 的数据 [[MapData]]，按照插入顺序保存键值对，同时通过键的索引查找
+#[derive(Debug, Clone, Default)]
+pub struct MapData {
+  index: HashMap<MapKey, usize>,
+  // 删除的键值对置为 None，迭代时跳过，过多时整理
+  entries: Vec<Option<(Value, Value)>>,
+}
+
+impl MapData {
+  pub fn len(&self) -> usize {
+    self.index.len()
+  }
+
+  pub fn is_empty(&self) -> bool {
+    self.index.is_empty()
+  }
+
+  pub fn get(&self, key: &Value) -> Option<&Value> {
+    let index = self.index.get(&MapKey::from_value(key))?;
+    self.entries[*index].as_ref().map(|(_, value)| value)
+  }
+
+  pub fn has(&self, key: &Value) -> bool {
+    self.index.contains_key(&MapKey::from_value(key))
+  }
+
+  // 设置键值，已存在的键只更新值，返回被替换的值
+  pub fn set(&mut self, key: Value, value: Value) -> Option<Value> {
+    let map_key = MapKey::from_value(&key);
+    if let Some(index) = self.index.get(&map_key) {
+      if let Some(entry) = &mut self.entries[*index] {
+        return Some(std::mem::replace(&mut entry.1, value));
+      }
+    }
+    // -0 作为键时统一为 +0
+    let key = match key {
+      Value::Number(num) => Value::Number(num + 0f64),
+      _ => key,
+    };
+    self.index.insert(map_key, self.entries.len());
+    self.entries.push(Some((key, value)));
+    None
+  }
+
+  // 删除键值，返回被删除的键值对
+  pub fn delete(&mut self, key: &Value) -> Option<(Value, Value)> {
+    let index = self.index.remove(&MapKey::from_value(key))?;
+    let removed = self.entries[index].take();
+    if self.entries.len() > 32 && self.index.len() < self.entries.len() / 2 {
+      self.compact();
+    }
+    removed
+  }
+
+  pub fn clear(&mut self) -> Vec<(Value, Value)> {
+    self.index.clear();
+    std::mem::take(&mut self.entries).into_iter().flatten().collect()
+  }
+
+  // 按照插入顺序遍历
+  pub fn iter(&self) -> impl Iterator<Item = &(Value, Value)> {
+    self.entries.iter().flatten()
+  }
+
+  // 去掉已删除的位置，重建索引
+  fn compact(&mut self) {
+    self.entries.retain(|entry| entry.is_some());
+    for (position, entry) in self.entries.iter().enumerate() {
+      if let Some((key, _)) = entry {
+        self.index.insert(MapKey::from_value(key), position);
+      }
+    }
+  }
+}
+
+// 创建 Map 实例
+pub fn create_map(ctx: &mut Context) -> Rc<RefCell<Object>> {
+  let global_map = get_global_object_by_name(ctx, GLOBAL_MAP_NAME);
+  let map_proto = get_global_object_prototype_by_name(ctx, GLOBAL_MAP_NAME);
+  let map = create_object(ctx, ClassType::Map, None);
+  {
+    let mut map_mut = map.borrow_mut();
+    map_mut.constructor = Some(Rc::downgrade(&global_map));
+    map_mut.set_inner_property_value(PROTO_PROPERTY_NAME.to_string(), Value::RefObject(Rc::downgrade(&map_proto)));
+    map_mut.map_data = Some(MapData::default());
+  }
+  map
+}
+
+// 是否为 Map 实例
+pub fn is_map(value: &Value) -> bool {
+  match value {
+    Value::Object(obj) => obj.borrow().map_data.is_some(),
+    _ => false,
+  }
+}
+
+// 获取 Map 中的全部键值对
+pub fn get_map_entries(map: &Rc<RefCell<Object>>) -> Vec<(Value, Value)> {
+  match &map.borrow().map_data {
+    Some(data) => data.iter().cloned().collect(),
+    None => vec![],
+  }
+}
+
+// 设置 Map 的键值，已存在的键只更新值
+pub fn map_set_value(map: &Rc<RefCell<Object>>, key: Value, value: Value) {
+  let mut map_mut = map.borrow_mut();
+  let object = &mut *map_mut;
+  if let Some(data) = &mut object.map_data {
+    let payload = value_payload_size(&key) + value_payload_size(&value);
+    match data.set(key, value.clone()) {
+      Some(old) => {
+        if let Some(allocation) = &mut object.heap_allocation {
+          allocation.replace_payload(Some(&old), &value);
+        }
+      },
+      None => {
+        if let Some(allocation) = &mut object.heap_allocation {
+          allocation.grow(MAP_ENTRY_SIZE + payload);
+        }
+      },
+    }
+  }
+}
+
+// 删除 Map 的键值对，归还占用的内存
+fn map_delete_entries(map: &Rc<RefCell<Object>>, removed: Vec<(Value, Value)>) {
+  let mut map_mut = map.borrow_mut();
+  if let Some(allocation) = &mut map_mut.heap_allocation {
+    for (key, value) in removed.iter() {
+      allocation.shrink(MAP_ENTRY_SIZE + value_payload_size(key) + value_payload_size(value));
+    }
+  }
+}
+
+pub fn bind_global_map(ctx: &mut Context) {
+  let create_function = builtin_function(ctx, INSTANTIATE_OBJECT_METHOD_NAME.to_string(), 0f64, create);
+  let group_by_name = String::from("groupBy");
+  let group_by_fun = builtin_function(ctx, group_by_name.clone(), 2f64, map_static_group_by);
+
+  let map_rc = get_global_object_by_name(ctx, GLOBAL_MAP_NAME);
+  let mut map = (*map_rc).borrow_mut();
+  map.set_inner_property_value(INSTANTIATE_OBJECT_METHOD_NAME.to_string(), create_function);
+
+  // Map.groupBy
+  map.property.insert(group_by_name, Property { enumerable: true, value: group_by_fun });
+
+  if let Some(prop) = &map.prototype {
+    let prototype_rc = Rc::clone(prop);
+    let mut prototype = prototype_rc.borrow_mut();
+    // Map.prototype.get
+    let name = String::from("get");
+    prototype.define_property(name.clone(), Property { enumerable: true, value: builtin_function(ctx, name, 1f64, map_get) });
+    // Map.prototype.set
+    let name = String::from("set");
+    prototype.define_property(name.clone(), Property { enumerable: true, value: builtin_function(ctx, name, 2f64, map_set) });
+    // Map.prototype.has
+    let name = String::from("has");
+    prototype.define_property(name.clone(), Property { enumerable: true, value: builtin_function(ctx, name, 1f64, map_has) });
+    // Map.prototype.delete
+    let name = String::from("delete");
+    prototype.define_property(name.clone(), Property { enumerable: true, value: builtin_function(ctx, name, 1f64, map_delete) });
+    // Map.prototype.clear
+    let name = String::from("clear");
+    prototype.define_property(name.clone(), Property { enumerable: true, value: builtin_function(ctx, name, 0f64, map_clear) });
+    // Map.prototype.forEach
+    let name = String::from("forEach");
+    prototype.define_property(name.clone(), Property { enumerable: true, value: builtin_function(ctx, name, 1f64, map_for_each) });
+    // Map.prototype.keys
+    let name = String::from("keys");
+    prototype.define_property(name.clone(), Property { enumerable: true, value: builtin_function(ctx, name, 0f64, map_keys) });
+    // Map.prototype.values
+    let name = String::from("values");
+    prototype.define_property(name.clone(), Property { enumerable: true, value: builtin_function(ctx, name, 0f64, map_values) });
+    // Map.prototype.entries
+    let name = String::from("entries");
+    prototype.define_property(name.clone(), Property { enumerable: true, value: builtin_function(ctx, name, 0f64, map_entries) });
+    // get Map.prototype.size
+    let name = String::from("size");
+    let getter = builtin_function(ctx, String::from("get size"), 0f64, map_size);
+    prototype.define_accessor(name, Accessor { get: Some(getter), set: None, enumerable: false });
+  }
+}
+
+// 实例化方法 new Map(iterable)
+fn create(call_ctx: &mut CallContext, args: Vec<Value>) -> JSIResult<Value> {
+  let map = create_map(call_ctx.ctx);
+  let iterable = args.first().cloned().unwrap_or(Value::Undefined);
+  if iterable.is_not_strict_null() {
+    return Ok(Value::Object(map))
+  }
+  let entries = match get_iterable_values(call_ctx.ctx, &iterable) {
+    Some(entries) => entries,
+    None => return Err(JSIError::new(JSIErrorType::TypeError, format!("{} is not iterable", iterable.type_of()), 0, 0)),
+  };
+  for entry in entries {
+    if !entry.is_object() {
+      return Err(JSIError::new(JSIErrorType::TypeError, format!("Iterator value {} is not an entry object", entry.to_string(call_ctx.ctx)), 0, 0))
+    }
+    let (key, value) = {
+      let entry_obj = entry.to_object(call_ctx.ctx);
+      let entry_obj = entry_obj.borrow();
+      (entry_obj.get_value(String::from("0")), entry_obj.get_value(String::from("1")))
+    };
+    map_set_value(&map, key, value);
+  }
+  Ok(Value::Object(map))
+}
+
+// 获取 this 对应的 Map，不是 Map 时抛出 TypeError
+fn get_this_map(call_ctx: &mut CallContext, method: &str) -> JSIResult<Rc<RefCell<Object>>> {
+  match &call_ctx.this {
+    Value::Object(obj) if is_map(&call_ctx.this) => Ok(Rc::clone(obj)),
+    _ => Err(JSIError::new(JSIErrorType::TypeError, format!("Method Map.prototype.{} called on incompatible receiver {}", method, call_ctx.this.to_string(call_ctx.ctx)), 0, 0)),
+  }
+}
+
+// Map.groupBy(items, callback)
+fn map_static_group_by(call_ctx: &mut CallContext, args: Vec<Value>) -> JSIResult<Value> {
+  let groups = group_by(call_ctx, args, false)?;
+  let map = create_map(call_ctx.ctx);
+  for (key, items) in groups {
+    let items = create_array_from_values(call_ctx.ctx, items);
+    map_set_value(&map, key, items);
+  }
+  Ok(Value::Object(map))
+}
+
+// Map.prototype.get
+fn map_get(call_ctx: &mut CallContext, args: Vec<Value>) -> JSIResult<Value> {
+  let map = get_this_map(call_ctx, "get")?;
+  let key = args.first().cloned().unwrap_or(Value::Undefined);
+  let value = match &map.borrow().map_data {
+    Some(data) => data.get(&key).cloned(),
+    None => None,
+  };
+  Ok(value.unwrap_or(Value::Undefined))
+}
+
+// Map.prototype.set
+fn map_set(call_ctx: &mut CallContext, args: Vec<Value>) -> JSIResult<Value> {
+  let map = get_this_map(call_ctx, "set")?;
+  let key = args.first().cloned().unwrap_or(Value::Undefined);
+  let value = args.get(1).cloned().unwrap_or(Value::Undefined);
+  map_set_value(&map, key, value);
+  Ok(call_ctx.this.clone())
+}
+
+// Map.prototype.has
+fn map_has(call_ctx: &mut CallContext, args: Vec<Value>) -> JSIResult<Value> {
+  let map = get_this_map(call_ctx, "has")?;
+  let key = args.first().cloned().unwrap_or(Value::Undefined);
+  let has = match &map.borrow().map_data {
+    Some(data) => data.has(&key),
+    None => false,
+  };
+  Ok(Value::Boolean(has))
+}
+
+// Map.prototype.delete
+fn map_delete(call_ctx: &mut CallContext, args: Vec<Value>) -> JSIResult<Value> {
+  let map = get_this_map(call_ctx, "delete")?;
+  let key = args.first().cloned().unwrap_or(Value::Undefined);
+  let removed = match &mut map.borrow_mut().map_data {
+    Some(data) => data.delete(&key),
+    None => None,
+  };
+  match removed {
+    Some(entry) => {
+      map_delete_entries(&map, vec![entry]);
+      Ok(Value::Boolean(true))
+    },
+    None => Ok(Value::Boolean(false)),
+  }
+}
+
+// Map.prototype.clear
+fn map_clear(call_ctx: &mut CallContext, _: Vec<Value>) -> JSIResult<Value> {
+  let map = get_this_map(call_ctx, "clear")?;
+  let removed = match &mut map.borrow_mut().map_data {
+    Some(data) => data.clear(),
+    None => vec![],
+  };
+  map_delete_entries(&map, removed);
+  Ok(Value::Undefined)
+}
+
+// get Map.prototype.size
+fn map_size(call_ctx: &mut CallContext, _: Vec<Value>) -> JSIResult<Value> {
+  let map = get_this_map(call_ctx, "size")?;
+  let size = map.borrow().map_data.as_ref().map_or(0, |data| data.len());
+  Ok(Value::Number(size as f64))
+}
+
+// Map.prototype.forEach(callback[, thisArg])
+fn map_for_each(call_ctx: &mut CallContext, args: Vec<Value>) -> JSIResult<Value> {
+  let map = get_this_map(call_ctx, "forEach")?;
+  let callback = match args.first() {
+    Some(Value::Function(callback)) => Rc::clone(callback),
+    other => {
+      let callback = other.cloned().unwrap_or(Value::Undefined);
+      return Err(JSIError::new(JSIErrorType::TypeError, format!("{} is not a function", callback.to_string(call_ctx.ctx)), 0, 0))
+    },
+  };
+  let this_arg = args.get(1).cloned().unwrap_or(Value::Undefined);
+  let map_value = call_ctx.this.clone();
+  for (key, value) in get_map_entries(&map) {
+    call_callback(call_ctx, &callback, &this_arg, vec![value, key, map_value.clone()])?;
+  }
+  Ok(Value::Undefined)
+}
+
+// 基于当前数据的快照创建迭代器
+fn create_map_iterator(call_ctx: &mut CallContext, list: Vec<Value>) -> JSIResult<Value> {
+  let array = create_array_from_values(call_ctx.ctx, list);
+  let mut array_call_ctx = CallContext {
+    ctx: call_ctx.ctx,
+    this: array,
+    reference: None,
+    func_name: String::from("values"),
+  };
+  Object::call(&mut array_call_ctx, String::from("values"), vec![])
+}
+
+// Map.prototype.keys
+fn map_keys(call_ctx: &mut CallContext, _: Vec<Value>) -> JSIResult<Value> {
+  let map = get_this_map(call_ctx, "keys")?;
+  let keys = get_map_entries(&map).into_iter().map(|(key, _)| key).collect();
+  create_map_iterator(call_ctx, keys)
+}
+
+// Map.prototype.values
+fn map_values(call_ctx: &mut CallContext, _: Vec<Value>) -> JSIResult<Value> {
+  let map = get_this_map(call_ctx, "values")?;
+  let values = get_map_entries(&map).into_iter().map(|(_, value)| value).collect();
+  create_map_iterator(call_ctx, values)
+}
+
+// Map.prototype.entries
+fn map_entries(call_ctx: &mut CallContext, _: Vec<Value>) -> JSIResult<Value> {
+  let map = get_this_map(call_ctx, "entries")?;
+  let mut entries: Vec<Value> = vec![];
+  for (key, value) in get_map_entries(&map) {
+    entries.push(create_array_from_values(call_ctx.ctx, vec![key, value]));
+  }
+  create_map_iterator(call_ctx, entries)
+}
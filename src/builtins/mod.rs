@@ -5,6 +5,7 @@ pub mod string;
 pub mod number;
 pub mod boolean;
 pub mod promise;
+pub mod map;
 pub mod error;
 pub mod global;
 pub mod console;
\ No newline at end of file
@@ -7,6 +7,9 @@ use crate::{value::{Value, INSTANTIATE_OBJECT_METHOD_NAME}, ast_node::{ClassType
 use super::global::{get_global_object_prototype_by_name, get_global_object_by_name};
 use super::{object::{create_object, Property}, function::builtin_function};
 
+// Number.MAX_SAFE_INTEGER
+pub const MAX_SAFE_INTEGER: f64 = 9007199254740991f64;
+
  pub fn create_number(ctx: &mut Context, init: Value) -> Value {
   let global_number = get_global_object_by_name(ctx, GLOBAL_NUMBER_NAME);
   let number = create_object(ctx, ClassType::Number, None);
@@ -25,6 +28,35 @@ pub fn bind_global_number(ctx: &mut Context) {
   let mut number = (*number_rc).borrow_mut();
   let create_function = builtin_function(ctx, INSTANTIATE_OBJECT_METHOD_NAME.to_string(), 1f64, create);
   number.set_inner_property_value(INSTANTIATE_OBJECT_METHOD_NAME.to_string(), create_function);
+  // Number 的静态方法
+  let name = String::from("isInteger");
+  number.property.insert(name.clone(), Property { enumerable: true, value: builtin_function(ctx, name, 1f64, number_static_is_integer) });
+  let name = String::from("isSafeInteger");
+  number.property.insert(name.clone(), Property { enumerable: true, value: builtin_function(ctx, name, 1f64, number_static_is_safe_integer) });
+  let name = String::from("isFinite");
+  number.property.insert(name.clone(), Property { enumerable: true, value: builtin_function(ctx, name, 1f64, number_static_is_finite) });
+  let name = String::from("isNaN");
+  number.property.insert(name.clone(), Property { enumerable: true, value: builtin_function(ctx, name, 1f64, number_static_is_nan) });
+  // Number.parseFloat 和 Number.parseInt 与全局的 parseFloat、parseInt 是同一个函数
+  for name in ["parseFloat", "parseInt"] {
+    let value = ctx.global.borrow().get_property_value(name.to_string());
+    number.property.insert(name.to_string(), Property { enumerable: true, value });
+  }
+  // Number 的静态属性
+  let constants = [
+    ("EPSILON", Value::Number(f64::EPSILON)),
+    ("MAX_SAFE_INTEGER", Value::Number(MAX_SAFE_INTEGER)),
+    ("MIN_SAFE_INTEGER", Value::Number(-MAX_SAFE_INTEGER)),
+    ("MAX_VALUE", Value::Number(f64::MAX)),
+    // 最小的正数（非规格化数）
+    ("MIN_VALUE", Value::Number(5e-324)),
+    ("POSITIVE_INFINITY", Value::Number(f64::INFINITY)),
+    ("NEGATIVE_INFINITY", Value::Number(f64::NEG_INFINITY)),
+    ("NaN", Value::NAN),
+  ];
+  for (name, value) in constants {
+    number.property.insert(name.to_string(), Property { enumerable: false, value });
+  }
   if let Some(prop)= &number.prototype {
     let prototype_rc = Rc::clone(prop);
     let mut prototype = (*prototype_rc).borrow_mut();
@@ -32,73 +64,333 @@ pub fn bind_global_number(ctx: &mut Context) {
     prototype.define_property(name.clone(), Property { enumerable: true, value: builtin_function(ctx, name, 0f64, to_string) });
     let name = String::from("valueOf");
     prototype.define_property(name.clone(), Property { enumerable: true, value: builtin_function(ctx, name, 1f64, value_of) });
+    let name = String::from("toFixed");
+    prototype.define_property(name.clone(), Property { enumerable: true, value: builtin_function(ctx, name, 1f64, to_fixed) });
+    let name = String::from("toExponential");
+    prototype.define_property(name.clone(), Property { enumerable: true, value: builtin_function(ctx, name, 1f64, to_exponential) });
+    let name = String::from("toPrecision");
+    prototype.define_property(name.clone(), Property { enumerable: true, value: builtin_function(ctx, name, 1f64, to_precision) });
   }
 }
 
 // Number.prototype.toString
 fn to_string(call_ctx: &mut CallContext, args: Vec<Value>) -> JSIResult<Value> {
-  let value = value_of(call_ctx, vec![])?;
+  let num = this_number_value(call_ctx, "toString")?;
 
   // 获取 radix 参数
-  let radix: u32 = if args.len() > 0 {
-    if let Some(r) = args[0].to_number(call_ctx.ctx) {
-      let r_int = r as i32;
-      if r_int < 2 || r_int > 36 {
-        return Err(JSIError::new(JSIErrorType::RangeError, String::from("toString() radix argument must be between 2 and 36"), 0, 0));
-      }
-      r_int as u32
-    } else {
-      10
-    }
-  } else {
-    10
+  let radix = match args.first() {
+    None | Some(Value::Undefined) => 10f64,
+    Some(radix) => radix.to_integer_or_infinity(call_ctx.ctx)?,
   };
+  if !(2f64..=36f64).contains(&radix) {
+    return Err(JSIError::new(JSIErrorType::RangeError, String::from("toString() radix argument must be between 2 and 36"), 0, 0));
+  }
+  if radix == 10f64 {
+    return Ok(Value::String(number_to_string(num)))
+  }
+  if num.is_nan() || num.is_infinite() {
+    return Ok(Value::String(number_to_string(num)))
+  }
+  Ok(Value::String(number_to_radix_string(num, radix as u32)))
+}
 
-  if let Value::Number(num) = value {
-    if radix == 10 {
-      Ok(Value::String(num.to_string()))
+// Number::toString(x)，使用最短的可以准确还原的十进制表示
+pub fn number_to_string(num: f64) -> String {
+  if num.is_nan() {
+    return String::from("NaN")
+  }
+  if num == 0f64 {
+    return String::from("0")
+  }
+  if num.is_infinite() {
+    return String::from(if num > 0f64 { "Infinity" } else { "-Infinity" })
+  }
+  if num < 0f64 {
+    return format!("-{}", number_to_string(-num))
+  }
+  // {:e} 输出最短的有效数字，例如 1.2345e-7
+  let exp_str = format!("{:e}", num);
+  let (mantissa, exponent) = exp_str.split_once('e').unwrap();
+  let digits: String = mantissa.chars().filter(|c| *c != '.').collect();
+  let k = digits.len() as i32;
+  // num = 0.digits × 10^n
+  let n = exponent.parse::<i32>().unwrap() + 1;
+  if k <= n && n <= 21 {
+    format!("{}{}", digits, "0".repeat((n - k) as usize))
+  } else if 0 < n && n <= 21 {
+    format!("{}.{}", &digits[..n as usize], &digits[n as usize..])
+  } else if -6 < n && n <= 0 {
+    format!("0.{}{}", "0".repeat((-n) as usize), digits)
+  } else {
+    let e = n - 1;
+    let sign = if e < 0 { "-" } else { "+" };
+    if k == 1 {
+      format!("{}e{}{}", digits, sign, e.abs())
     } else {
-      // 将数字转换为指定进制
-      if num.is_nan() {
-        Ok(Value::String(String::from("NaN")))
-      } else if num.is_infinite() {
-        if num > 0.0 {
-          Ok(Value::String(String::from("Infinity")))
-        } else {
-          Ok(Value::String(String::from("-Infinity")))
+      format!("{}.{}e{}{}", &digits[..1], &digits[1..], sign, e.abs())
+    }
+  }
+}
+
+// 转换为指定进制的字符串，小数部分输出到可以区分相邻浮点数的精度为止
+fn number_to_radix_string(num: f64, radix: u32) -> String {
+  let digit_chars: Vec<char> = "0123456789abcdefghijklmnopqrstuvwxyz".chars().collect();
+  let negative = num < 0f64;
+  let value = num.abs();
+  let mut integer = value.floor();
+  let mut fraction = value - integer;
+
+  // 小数部分
+  let mut fraction_digits: Vec<u32> = vec![];
+  // 相邻浮点数间隔的一半，小于它的差异没有意义
+  let mut delta = (0.5 * (f64::from_bits(value.to_bits() + 1) - value)).max(f64::from_bits(1));
+  if fraction >= delta {
+    loop {
+      fraction *= radix as f64;
+      delta *= radix as f64;
+      let digit = fraction.floor() as u32;
+      fraction_digits.push(digit);
+      fraction -= digit as f64;
+      if (fraction > 0.5 || (fraction == 0.5 && (digit & 1) == 1)) && fraction + delta > 1f64 {
+        // 向上进位
+        loop {
+          match fraction_digits.pop() {
+            Some(last) if last + 1 < radix => {
+              fraction_digits.push(last + 1);
+              break;
+            },
+            Some(_) => {},
+            None => {
+              integer += 1f64;
+              break;
+            },
+          }
         }
+        break;
+      }
+      if fraction < delta {
+        break;
+      }
+    }
+  }
+
+  // 整数部分
+  let mut integer_digits: Vec<char> = vec![];
+  loop {
+    let remainder = integer % radix as f64;
+    integer_digits.push(digit_chars[remainder as usize]);
+    integer = (integer - remainder) / radix as f64;
+    if integer <= 0f64 {
+      break;
+    }
+  }
+  integer_digits.reverse();
+
+  let mut result = String::new();
+  if negative {
+    result.push('-');
+  }
+  result.extend(integer_digits);
+  if !fraction_digits.is_empty() {
+    result.push('.');
+    result.extend(fraction_digits.iter().map(|digit| digit_chars[*digit as usize]));
+  }
+  result
+}
+
+// 浮点数的精确十进制展开，返回 (所有数字, 小数点前的数字个数)
+fn exact_decimal_digits(num: f64) -> (Vec<u8>, usize) {
+  // f64 最多有 1074 位小数，指定足够的精度时格式化结果是精确的
+  let exact = format!("{:.1100}", num.abs());
+  let (integer, fraction) = exact.split_once('.').unwrap();
+  let digits: Vec<u8> = integer.bytes().chain(fraction.bytes()).map(|byte| byte - b'0').collect();
+  (digits, integer.len())
+}
+
+// 保留 digits 的前 len 位，第 len + 1 位四舍五入（恰好一半时向上），返回是否进位产生了新的最高位
+fn round_digits(digits: &[u8], len: usize) -> (Vec<u8>, bool) {
+  let mut result: Vec<u8> = digits.iter().take(len).copied().collect();
+  result.resize(len, 0);
+  if digits.get(len).is_some_and(|digit| *digit >= 5) {
+    for digit in result.iter_mut().rev() {
+      if *digit == 9 {
+        *digit = 0;
       } else {
-        // 处理整数部分
-        let int_part = num.trunc() as i64;
-        let int_str = if int_part < 0 {
-          format!("-{}", format_int_radix(-int_part, radix))
-        } else {
-          format_int_radix(int_part, radix)
-        };
-        Ok(Value::String(int_str))
+        *digit += 1;
+        return (result, false)
       }
     }
+    result.insert(0, 1);
+    return (result, true)
+  }
+  (result, false)
+}
+
+// 取 precision 位有效数字，返回 (数字, 指数 e)，即 num ≈ d.ddd × 10^e
+fn round_to_precision(num: f64, precision: usize) -> (Vec<u8>, i32) {
+  let (digits, point) = exact_decimal_digits(num);
+  let first = digits.iter().position(|digit| *digit != 0).unwrap_or(0);
+  let mut e = point as i32 - first as i32 - 1;
+  let (mut rounded, carry) = round_digits(&digits[first..], precision);
+  if carry {
+    rounded.truncate(precision);
+    e += 1;
+  }
+  (rounded, e)
+}
+
+fn digits_to_string(digits: &[u8]) -> String {
+  digits.iter().map(|digit| (b'0' + digit) as char).collect()
+}
+
+// 获取 fractionDigits 等参数，必须在 [0, 100] 范围内
+fn get_digits_argument(call_ctx: &mut CallContext, value: &Value, min: f64, method: &str) -> JSIResult<f64> {
+  let digits = value.to_integer_or_infinity(call_ctx.ctx)?;
+  if !(min..=100f64).contains(&digits) {
+    return Err(JSIError::new(JSIErrorType::RangeError, format!("{}() argument must be between {} and 100", method, min), 0, 0))
+  }
+  Ok(digits)
+}
+
+// Number.prototype.toFixed
+// num.toFixed([fractionDigits])
+fn to_fixed(call_ctx: &mut CallContext, args: Vec<Value>) -> JSIResult<Value> {
+  let num = this_number_value(call_ctx, "toFixed")?;
+  let fraction_digits = get_digits_argument(call_ctx, args.first().unwrap_or(&Value::Undefined), 0f64, "toFixed")? as usize;
+  if !num.is_finite() || num.abs() >= 1e21 {
+    return Ok(Value::String(number_to_string(num)))
+  }
+  let (digits, point) = exact_decimal_digits(num);
+  let (rounded, carry) = round_digits(&digits, point + fraction_digits);
+  let point = if carry { point + 1 } else { point };
+  let integer = digits_to_string(&rounded[..point]);
+  let integer = integer.trim_start_matches('0');
+  let integer = if integer.is_empty() { "0" } else { integer };
+  let sign = if num < 0f64 { "-" } else { "" };
+  if fraction_digits == 0 {
+    return Ok(Value::String(format!("{}{}", sign, integer)))
+  }
+  Ok(Value::String(format!("{}{}.{}", sign, integer, digits_to_string(&rounded[point..]))))
+}
+
+// Number.prototype.toExponential
+// num.toExponential([fractionDigits])
+fn to_exponential(call_ctx: &mut CallContext, args: Vec<Value>) -> JSIResult<Value> {
+  let num = this_number_value(call_ctx, "toExponential")?;
+  let fraction_digits = args.first().cloned().unwrap_or(Value::Undefined);
+  let fraction_digits = match fraction_digits {
+    Value::Undefined => None,
+    value => Some(value.to_integer_or_infinity(call_ctx.ctx)?),
+  };
+  if !num.is_finite() {
+    return Ok(Value::String(number_to_string(num)))
+  }
+  if let Some(fraction_digits) = fraction_digits {
+    if !(0f64..=100f64).contains(&fraction_digits) {
+      return Err(JSIError::new(JSIErrorType::RangeError, String::from("toExponential() argument must be between 0 and 100"), 0, 0))
+    }
+  }
+  let sign = if num < 0f64 { "-" } else { "" };
+  let (digits, e) = if num == 0f64 {
+    (vec![0; fraction_digits.unwrap_or(0f64) as usize + 1], 0)
   } else {
-    Ok(Value::String(value.to_string(call_ctx.ctx)))
+    match fraction_digits {
+      Some(fraction_digits) => round_to_precision(num, fraction_digits as usize + 1),
+      None => {
+        // 未指定时使用最短的有效数字
+        let exp_str = format!("{:e}", num.abs());
+        let (mantissa, exponent) = exp_str.split_once('e').unwrap();
+        let digits: Vec<u8> = mantissa.bytes().filter(|byte| *byte != b'.').map(|byte| byte - b'0').collect();
+        (digits, exponent.parse::<i32>().unwrap())
+      },
+    }
+  };
+  let mantissa = if digits.len() > 1 {
+    format!("{}.{}", digits[0], digits_to_string(&digits[1..]))
+  } else {
+    digits_to_string(&digits)
+  };
+  let exponent_sign = if e < 0 { "-" } else { "+" };
+  Ok(Value::String(format!("{}{}e{}{}", sign, mantissa, exponent_sign, e.abs())))
+}
+
+// Number.prototype.toPrecision
+// num.toPrecision([precision])
+fn to_precision(call_ctx: &mut CallContext, args: Vec<Value>) -> JSIResult<Value> {
+  let num = this_number_value(call_ctx, "toPrecision")?;
+  let precision = match args.first() {
+    None | Some(Value::Undefined) => return Ok(Value::String(number_to_string(num))),
+    Some(precision) => precision.to_integer_or_infinity(call_ctx.ctx)?,
+  };
+  if !num.is_finite() {
+    return Ok(Value::String(number_to_string(num)))
+  }
+  if !(1f64..=100f64).contains(&precision) {
+    return Err(JSIError::new(JSIErrorType::RangeError, String::from("toPrecision() argument must be between 1 and 100"), 0, 0))
   }
+  let precision = precision as usize;
+  let sign = if num < 0f64 { "-" } else { "" };
+  let (digits, e) = if num == 0f64 { (vec![0; precision], 0) } else { round_to_precision(num, precision) };
+  let digits_str = digits_to_string(&digits);
+  let result = if e < -6 || e >= precision as i32 {
+    // 指数形式
+    let exponent_sign = if e < 0 { "-" } else { "+" };
+    if precision == 1 {
+      format!("{}e{}{}", digits_str, exponent_sign, e.abs())
+    } else {
+      format!("{}.{}e{}{}", &digits_str[..1], &digits_str[1..], exponent_sign, e.abs())
+    }
+  } else if e == precision as i32 - 1 {
+    digits_str
+  } else if e >= 0 {
+    format!("{}.{}", &digits_str[..e as usize + 1], &digits_str[e as usize + 1..])
+  } else {
+    format!("0.{}{}", "0".repeat((-(e + 1)) as usize), digits_str)
+  };
+  Ok(Value::String(format!("{}{}", sign, result)))
 }
 
-// 辅助函数：将整数转换为指定进制的字符串
-fn format_int_radix(n: i64, radix: u32) -> String {
-  if n == 0 {
-    return String::from("0");
+// thisNumberValue，this 不是 Number 时抛出 TypeError
+fn this_number_value(call_ctx: &mut CallContext, method: &str) -> JSIResult<f64> {
+  if let Value::NAN = &call_ctx.this {
+    return Ok(f64::NAN)
   }
-  let digits = "0123456789abcdefghijklmnopqrstuvwxyz";
-  let mut result = String::new();
-  let mut num = n;
-  while num > 0 {
-    let digit = (num % radix as i64) as usize;
-    result.push(digits.chars().nth(digit).unwrap());
-    num /= radix as i64;
+  match value_of(call_ctx, vec![]) {
+    Ok(Value::Number(num)) => Ok(num),
+    _ => Err(JSIError::new(JSIErrorType::TypeError, format!("Number.prototype.{} requires that 'this' be a Number", method), 0, 0)),
   }
-  result.chars().rev().collect()
 }
 
+// 获取参数的数值，参数不是 Number 类型时返回 None（Number 的静态方法不做类型转换）
+fn get_number_argument(args: &[Value]) -> Option<f64> {
+  match args.first() {
+    Some(Value::Number(num)) => Some(*num),
+    Some(Value::NAN) => Some(f64::NAN),
+    _ => None,
+  }
+}
+
+// Number.isInteger
+fn number_static_is_integer(_: &mut CallContext, args: Vec<Value>) -> JSIResult<Value> {
+  let result = get_number_argument(&args).is_some_and(|num| num.is_finite() && num.trunc() == num);
+  Ok(Value::Boolean(result))
+}
+
+// Number.isSafeInteger
+fn number_static_is_safe_integer(_: &mut CallContext, args: Vec<Value>) -> JSIResult<Value> {
+  let result = get_number_argument(&args).is_some_and(|num| num.is_finite() && num.trunc() == num && num.abs() <= MAX_SAFE_INTEGER);
+  Ok(Value::Boolean(result))
+}
+
+// Number.isFinite
+fn number_static_is_finite(_: &mut CallContext, args: Vec<Value>) -> JSIResult<Value> {
+  Ok(Value::Boolean(get_number_argument(&args).is_some_and(|num| num.is_finite())))
+}
+
+// Number.isNaN
+fn number_static_is_nan(_: &mut CallContext, args: Vec<Value>) -> JSIResult<Value> {
+  Ok(Value::Boolean(get_number_argument(&args).is_some_and(|num| num.is_nan())))
+}
 
 // Number.prototype.valueOf
 fn value_of(call_ctx: &mut CallContext, _: Vec<Value>) -> JSIResult<Value> {
@@ -1,19 +1,34 @@
+use std::any::Any;
 use std::borrow::BorrowMut;
-use std::cell::{RefCell};
+use std::fmt;
+use std::cell::{Cell, RefCell};
 use std::collections::HashMap;
 use std::rc::{Rc, Weak};
 use crate::context::{Context};
-use super::array::create_array;
-use std::sync::atomic::{AtomicUsize, Ordering};
+use super::array::{call_callback, create_array_from_values, get_iterable_values};
 // use super::array::new_array;
 use super::function::builtin_function;
+use super::map::MapData;
 use super::global::{get_global_object, get_global_object_prototype_by_name, get_global_object_by_name};
 use crate::ast_node::{Statement, CallContext, ClassType, BuiltinFunction};
 use crate::constants::{GLOBAL_OBJECT_NAME, PROTO_PROPERTY_NAME};
-use crate::error::{JSIResult, JSIError};
+use crate::error::{JSIResult, JSIError, JSIErrorType};
 use crate::value::{Value, INSTANTIATE_OBJECT_METHOD_NAME};
+use crate::heap::{value_payload_size, HeapAllocation, OBJECT_SIZE, PROPERTY_SIZE};
 
-static OBJECT_ID_COUNTER: AtomicUsize = AtomicUsize::new(0);
+// 对象 id 分配器，每个 Context 单独计数，保证同一段脚本每次执行得到相同的 id
+#[derive(Debug, Default)]
+pub struct ObjectIds {
+  last_id: Cell<usize>,
+}
+
+impl ObjectIds {
+  pub fn next_id(&self) -> usize {
+    let id = self.last_id.get() + 1;
+    self.last_id.set(id);
+    id
+  }
+}
 
 #[derive(Debug,Clone)]
 // 对象
@@ -34,6 +49,16 @@ pub struct Object {
   // 如果是实例，则存在 constructor 值，指向构造方法
   // 如： arr.constructor = Array
   pub constructor: Option<Weak<RefCell<Object>>>,
+  // 访问器属性 get / set
+  pub accessor: HashMap<String, Accessor>,
+  // 宿主数据，由嵌入方保存的 Rust 值，对 JS 不可见
+  pub host_data: Option<HostData>,
+  // Map 实例的键值对
+  pub map_data: Option<MapData>,
+  // 堆内存占用记录，通过 create_object 创建的对象才有
+  pub heap_allocation: Option<HeapAllocation>,
+  // 冻结的对象不能添加、修改、删除属性，用于冻结内置对象
+  pub frozen: bool,
   // 对象的值
   value: Option<Box<Statement>>,
   // 对象 id
@@ -41,8 +66,9 @@ pub struct Object {
 }
 
 impl Object {
-  pub fn new(obj_type: ClassType, value: Option<Box<Statement>>) -> Object {
-    let id = OBJECT_ID_COUNTER.fetch_add(1, Ordering::SeqCst) + 1;
+  // id 由所属 Context 分配，同一个解释器中按创建顺序递增
+  pub fn new(object_ids: &ObjectIds, obj_type: ClassType, value: Option<Box<Statement>>) -> Object {
+    let id = object_ids.next_id();
     Object {
       class_type: obj_type,
       // 设置或添加的属性
@@ -52,6 +78,11 @@ impl Object {
       property_list: vec![],
       prototype: None,
       constructor: None,
+      accessor: HashMap::new(),
+      host_data: None,
+      map_data: None,
+      heap_allocation: None,
+      frozen: false,
       value,
       id,
     }
@@ -59,8 +90,8 @@ impl Object {
 
   // 强制拷贝
   // 用于 function.bind，但是不能拷贝 id
-  pub fn force_copy(&self) -> Object {
-    let id = OBJECT_ID_COUNTER.fetch_add(1, Ordering::SeqCst) + 1;
+  pub fn force_copy(&self, object_ids: &ObjectIds) -> Object {
+    let id = object_ids.next_id();
     Object {
       class_type: self.class_type.clone(),
       property: self.property.clone(),
@@ -68,6 +99,11 @@ impl Object {
       property_list: self.property_list.clone(),
       prototype: self.prototype.clone(),
       constructor: self.constructor.clone(),
+      accessor: self.accessor.clone(),
+      host_data: self.host_data.clone(),
+      map_data: self.map_data.clone(),
+      heap_allocation: self.heap_allocation.clone(),
+      frozen: false,
       value: self.value.clone(),
       id
     }
@@ -92,8 +128,14 @@ impl Object {
   pub fn define_property(&mut self, name: String, property: Property) -> bool {
     // 需要实现 descriptpor
     if !self.property_list.contains(&name) {
+      if let Some(allocation) = &mut self.heap_allocation {
+        allocation.grow(PROPERTY_SIZE + name.len());
+      }
       self.property_list.push(name.clone());
     }
+    if let Some(allocation) = &mut self.heap_allocation {
+      allocation.replace_payload(self.property.get(&name).map(|old| &old.value), &property.value);
+    }
     self.property.insert(name, property);
     return true;
   }
@@ -123,7 +165,17 @@ impl Object {
     // 从 property HashMap 中移除
     let removed = self.property.remove(&name);
     // 从 property_list 中移除
+    let list_len = self.property_list.len();
     self.property_list.retain(|key| key != &name);
+    // 归还属性及其字符串值占用的内存
+    if let Some(allocation) = &mut self.heap_allocation {
+      if self.property_list.len() < list_len {
+        allocation.shrink(PROPERTY_SIZE + name.len());
+      }
+      if let Some(removed) = &removed {
+        allocation.shrink(value_payload_size(&removed.value));
+      }
+    }
     // JavaScript 规范：删除不存在的属性返回 true
     // 只有不可配置的属性才会返回 false，这里暂时都返回 true
     true
@@ -161,47 +213,81 @@ impl Object {
         return prop_value;
       }
       // 从 [[Prpperty]] 上获取原型链，从原型链上获取
-      let proto = self.get_inner_property_value(PROTO_PROPERTY_NAME.to_string());
-      if let Some(proto_value) = &proto{
-        let obj = match proto_value {
-          Value::RefObject(proto_obj)=> {
-            proto_obj.upgrade()
-          },
-          Value::Object(obj)=> {
-            Some(Rc::clone(obj))
-          },
-          _ => None,
-        };
-
-        if let Some(proto) = obj {
-          let mut proto_rc = proto;
-          loop {
-            let proto_rc_clone = Rc::clone(&proto_rc);
-            let proto = proto_rc_clone.borrow();
-            let prop = proto.property.get(&name);
-            if let Some(property) = prop {
-              return property.value.clone()
-            } else {
-              // 从 [[Prpperty]] 上获取
-              let new_proto = proto.get_inner_property_value(PROTO_PROPERTY_NAME.to_string());
-              if let Some(new_proto) = new_proto {
-                if let Value::RefObject(new_proto_obj) = new_proto {
-                  let new_proto_op = new_proto_obj.upgrade();
-                  if let Some(proto) = new_proto_op {
-                    proto_rc = Rc::clone(&proto);
-                    continue;
-                  }
-                }
-              }
-            }
-            break;
-          }
+      let mut proto_op = self.get_prototype_object();
+      while let Some(proto_rc) = proto_op {
+        let proto = proto_rc.borrow();
+        if let Some(property) = proto.property.get(&name) {
+          return property.value.clone()
         }
+        proto_op = proto.get_prototype_object();
       }
     }
     Value::Undefined
   }
 
+  // 定义访问器属性
+  pub fn define_accessor(&mut self, name: String, accessor: Accessor) {
+    self.accessor.insert(name, accessor);
+  }
+
+  // 查找访问器属性：自身的数据属性优先，然后沿原型链查找
+  pub fn get_accessor(&self, name: &String) -> Option<Accessor> {
+    if self.property.contains_key(name) {
+      return None;
+    }
+    if let Some(accessor) = self.accessor.get(name) {
+      return Some(accessor.clone());
+    }
+    let mut proto_op = self.get_prototype_object();
+    while let Some(proto_rc) = proto_op {
+      let proto = proto_rc.borrow();
+      if proto.property.contains_key(name) {
+        return None;
+      }
+      if let Some(accessor) = proto.accessor.get(name) {
+        return Some(accessor.clone());
+      }
+      proto_op = proto.get_prototype_object();
+    }
+    None
+  }
+
+  // HasProperty：自身或原型链上存在数据属性或访问器属性
+  pub fn has_property(&self, name: &String) -> bool {
+    if self.property.contains_key(name) || self.accessor.contains_key(name) {
+      return true;
+    }
+    let mut proto_op = self.get_prototype_object();
+    while let Some(proto_rc) = proto_op {
+      let proto = proto_rc.borrow();
+      if proto.property.contains_key(name) || proto.accessor.contains_key(name) {
+        return true;
+      }
+      proto_op = proto.get_prototype_object();
+    }
+    false
+  }
+
+  // 获取 [[Prpperty]] 指向的原型对象，原型为 null 时返回 None
+  pub fn get_prototype_object(&self) -> Option<Rc<RefCell<Object>>> {
+    let proto = self.get_inner_property_value(PROTO_PROPERTY_NAME.to_string())?;
+    proto.to_weak_rc_object()?.upgrade()
+  }
+
+  // 自身属性的键，按照规范排序：整数索引键按数值升序在前，其余字符串键按插入顺序
+  pub fn get_own_property_keys(&self) -> Vec<String> {
+    let mut index_keys: Vec<(u64, &String)> = vec![];
+    let mut string_keys: Vec<&String> = vec![];
+    for key in self.property_list.iter() {
+      match key.parse::<u64>() {
+        Ok(index) if index < 4294967295 && index.to_string() == *key => index_keys.push((index, key)),
+        _ => string_keys.push(key),
+      }
+    }
+    index_keys.sort_by_key(|(index, _)| *index);
+    index_keys.into_iter().map(|(_, key)| key).chain(string_keys).cloned().collect()
+  }
+
   pub fn call(call_ctx: &mut CallContext, name: String, arguments:Vec<Value>) -> JSIResult<Value> {
     let fun = {
       let obj = call_ctx.this.to_object(call_ctx.ctx);
@@ -221,6 +307,9 @@ impl Object {
         // let mut ctx = CallContext{ global: ctx.global, this: Rc::downgrade(&function_define) };
         return (builtin_function)(call_ctx, arguments);
       }
+      if let Statement::HostFunction(host_function) = function_define_value.as_ref() {
+        return (host_function)(call_ctx, arguments, Value::Undefined);
+      }
       if let Statement::Function(_) = function_define_value.as_ref() {
         let call_function_define = Rc::clone(function_define);
         return call_ctx.call_function(call_function_define, Some(Value::Function(Rc::clone(function_define))), None, arguments);
@@ -230,6 +319,25 @@ impl Object {
   }
 }
 
+// 访问器属性，get 和 set 为方法对象
+#[derive(Debug,Clone)]
+pub struct Accessor {
+  pub get: Option<Value>,
+  pub set: Option<Value>,
+  // class 和内置对象的访问器不可枚举
+  pub enumerable: bool,
+}
+
+// 宿主数据
+#[derive(Clone)]
+pub struct HostData(pub Rc<RefCell<dyn Any>>);
+
+impl fmt::Debug for HostData {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    f.write_str("HostData")
+  }
+}
+
 #[derive(Debug,Clone)]
 pub struct Property {
   // 是否可枚举
@@ -240,7 +348,7 @@ pub struct Property {
 
 // 实例化对象
 pub fn create_object(ctx: &mut Context, obj_type: ClassType, value: Option<Box<Statement>>) -> Rc<RefCell<Object>> {
-  let object = Rc::new(RefCell::new(Object::new(obj_type, value)));
+  let object = Rc::new(RefCell::new(Object::new(&ctx.object_ids, obj_type, value)));
 
   // 先获取全局对象和 prototype（不借用新对象）
   let global_object = get_global_object_by_name(ctx, GLOBAL_OBJECT_NAME);
@@ -252,6 +360,8 @@ pub fn create_object(ctx: &mut Context, obj_type: ClassType, value: Option<Box<S
   // 绑定 obj.constructor = global.Object
   object_mut.set_inner_property_value(PROTO_PROPERTY_NAME.to_string(), Value::RefObject(Rc::downgrade(&global_prototype)));
   object_mut.constructor = Some(Rc::downgrade(&global_object));
+  object_mut.heap_allocation = Some(HeapAllocation::new(&ctx.heap, OBJECT_SIZE));
+  ctx.heap.track_object(&object);
 
   object
 }
@@ -271,6 +381,16 @@ pub fn bind_global_object(ctx: &mut Context) {
   let has_own_property_fun = builtin_function(ctx, String::from("hasOwnProperty"), 0f64, has_own_property);
   let object_to_string_fun = builtin_function(ctx, String::from("toString"), 0f64, to_string);
   let object_value_of_fun = builtin_function(ctx, String::from("valueOf"), 0f64, value_of);
+  let object_entries_fun = builtin_function(ctx, String::from("entries"), 1f64, object_entries);
+  let object_values_fun = builtin_function(ctx, String::from("values"), 1f64, object_values);
+  let object_assign_fun = builtin_function(ctx, String::from("assign"), 2f64, object_assign);
+  let object_from_entries_fun = builtin_function(ctx, String::from("fromEntries"), 1f64, object_from_entries);
+  let object_set_prototype_of_fun = builtin_function(ctx, String::from("setPrototypeOf"), 2f64, object_set_prototype_of);
+  let object_is_fun = builtin_function(ctx, String::from("is"), 2f64, object_is);
+  let object_group_by_fun = builtin_function(ctx, String::from("groupBy"), 2f64, object_group_by);
+  let is_prototype_of_fun = builtin_function(ctx, String::from("isPrototypeOf"), 1f64, is_prototype_of);
+  let property_is_enumerable_fun = builtin_function(ctx, String::from("propertyIsEnumerable"), 1f64, property_is_enumerable);
+  let object_to_locale_string_fun = builtin_function(ctx, String::from("toLocaleString"), 0f64, to_locale_string);
 
 
   let mut obj = (*obj_rc).borrow_mut();
@@ -298,6 +418,34 @@ pub fn bind_global_object(ctx: &mut Context) {
   let name = String::from("getPrototypeOf");
   property.insert(name.clone(), Property { enumerable: true, value: object_get_prototype_of_fun });
 
+  // Object.entries
+  let name = String::from("entries");
+  property.insert(name.clone(), Property { enumerable: true, value: object_entries_fun });
+
+  // Object.values
+  let name = String::from("values");
+  property.insert(name.clone(), Property { enumerable: true, value: object_values_fun });
+
+  // Object.assign
+  let name = String::from("assign");
+  property.insert(name.clone(), Property { enumerable: true, value: object_assign_fun });
+
+  // Object.fromEntries
+  let name = String::from("fromEntries");
+  property.insert(name.clone(), Property { enumerable: true, value: object_from_entries_fun });
+
+  // Object.setPrototypeOf
+  let name = String::from("setPrototypeOf");
+  property.insert(name.clone(), Property { enumerable: true, value: object_set_prototype_of_fun });
+
+  // Object.is
+  let name = String::from("is");
+  property.insert(name.clone(), Property { enumerable: true, value: object_is_fun });
+
+  // Object.groupBy
+  let name = String::from("groupBy");
+  property.insert(name.clone(), Property { enumerable: true, value: object_group_by_fun });
+
   if let Some(prop)= &obj.prototype {
 
     let prototype_rc = Rc::clone(prop);
@@ -317,6 +465,18 @@ pub fn bind_global_object(ctx: &mut Context) {
     // Object.prototype.valueOf
     let name = String::from("valueOf");
     prototype.define_property(name.clone(), Property { enumerable: true, value: object_value_of_fun });
+
+    // Object.prototype.isPrototypeOf
+    let name = String::from("isPrototypeOf");
+    prototype.define_property(name.clone(), Property { enumerable: true, value: is_prototype_of_fun });
+
+    // Object.prototype.propertyIsEnumerable
+    let name = String::from("propertyIsEnumerable");
+    prototype.define_property(name.clone(), Property { enumerable: true, value: property_is_enumerable_fun });
+
+    // Object.prototype.toLocaleString
+    let name = String::from("toLocaleString");
+    prototype.define_property(name.clone(), Property { enumerable: true, value: object_to_locale_string_fun });
   }
  
 }
@@ -354,9 +514,9 @@ fn object_has_own(call_ctx: &mut CallContext, args: Vec<Value>) -> JSIResult<Val
   }
   let obj_rc= obj.to_object(call_ctx.ctx);
   if args.len() > 1 {
-    let property_name = args[1].to_string(call_ctx.ctx);
+    let property_name = args[1].to_property_key(call_ctx.ctx);
     let obj = obj_rc.borrow();
-    return Ok(Value::Boolean(obj.property.contains_key(&property_name)));
+    return Ok(Value::Boolean(obj.property.contains_key(&property_name) || obj.accessor.contains_key(&property_name)));
   }
   
   return Ok(Value::Boolean(false));
@@ -364,57 +524,246 @@ fn object_has_own(call_ctx: &mut CallContext, args: Vec<Value>) -> JSIResult<Val
 
 // Object.keys()
 fn object_keys(call_ctx: &mut CallContext, args: Vec<Value>) -> JSIResult<Value> {
-  let array = create_array(call_ctx.ctx, 0);
-  let array_obj = match array {
-    Value::Array(arr) => Some(arr),
-    _ => None
-  }.unwrap();
-
-  if args.len() < 1 {
-    return Ok(Value::Array(array_obj));
-  }
-  let obj_rc= args[0].to_object(call_ctx.ctx);
-  let new_call_ctx = &mut CallContext {
-    ctx: call_ctx.ctx,
-    this: Value::Array(Rc::clone(&array_obj)),
-    reference: None,
-    func_name: String::from("push"),
+  let value = args.first().cloned().unwrap_or(Value::Undefined);
+  let entries = get_own_enumerable_entries(call_ctx.ctx, &value)?;
+  let keys = entries.into_iter().map(|(key, _)| Value::from_property_key(key)).collect();
+  Ok(create_array_from_values(call_ctx.ctx, keys))
+}
+
+// Object.getOwnPropertyNames
+fn object_get_own_property_names(call_ctx: &mut CallContext, args: Vec<Value>) -> JSIResult<Value> {
+  let value = args.first().cloned().unwrap_or(Value::Undefined);
+  let obj_rc = to_object_or_throw(call_ctx.ctx, &value)?;
+  let mut keys: Vec<Value> = vec![];
+  if value.is_string() {
+    // 字符串的索引以及 length
+    let len = value.to_utf16(call_ctx.ctx).len();
+    keys.extend((0..len).map(|index| Value::String(index.to_string())));
+    keys.push(Value::String(String::from("length")));
+  }
+  keys.extend(obj_rc.borrow().get_own_property_keys().into_iter().map(Value::from_property_key));
+  Ok(create_array_from_values(call_ctx.ctx, keys))
+}
+
+// Object.entries
+fn object_entries(call_ctx: &mut CallContext, args: Vec<Value>) -> JSIResult<Value> {
+  let value = args.first().cloned().unwrap_or(Value::Undefined);
+  let entries = get_own_enumerable_entries(call_ctx.ctx, &value)?;
+  let mut list: Vec<Value> = vec![];
+  for (key, value) in entries {
+    list.push(create_array_from_values(call_ctx.ctx, vec![Value::from_property_key(key), value]));
+  }
+  Ok(create_array_from_values(call_ctx.ctx, list))
+}
+
+// Object.values
+fn object_values(call_ctx: &mut CallContext, args: Vec<Value>) -> JSIResult<Value> {
+  let value = args.first().cloned().unwrap_or(Value::Undefined);
+  let entries = get_own_enumerable_entries(call_ctx.ctx, &value)?;
+  let values = entries.into_iter().map(|(_, value)| value).collect();
+  Ok(create_array_from_values(call_ctx.ctx, values))
+}
+
+// Object.assign(target, ...sources)
+fn object_assign(call_ctx: &mut CallContext, args: Vec<Value>) -> JSIResult<Value> {
+  let target = args.first().cloned().unwrap_or(Value::Undefined);
+  let target_obj = to_object_or_throw(call_ctx.ctx, &target)?;
+  if target_obj.borrow().frozen {
+    return Err(JSIError::new(JSIErrorType::TypeError, String::from("Cannot assign to read only object"), 0, 0));
+  }
+  for source in args.iter().skip(1) {
+    // null 和 undefined 会被忽略
+    if source.is_not_strict_null() {
+      continue;
+    }
+    let entries = get_own_enumerable_entries(call_ctx.ctx, source)?;
+    for (key, value) in entries {
+      // 目标上的访问器属性调用 setter
+      let accessor = target_obj.borrow().get_accessor(&key);
+      if let Some(accessor) = accessor {
+        match accessor.set {
+          Some(Value::Function(setter)) => {
+            call_ctx.ctx.call_function_object(setter, Some(target.clone()), None, vec![value])?;
+          },
+          _ => return Err(JSIError::new(JSIErrorType::TypeError, format!("Cannot set property {} which has only a getter", key), 0, 0)),
+        }
+        continue;
+      }
+      let mut target_mut = (*target_obj).borrow_mut();
+      let enumerable = target_mut.property.get(&key).is_none_or(|prop| prop.enumerable);
+      target_mut.define_property(key, Property { enumerable, value });
+    }
+  }
+  Ok(target.to_object_value(call_ctx.ctx))
+}
+
+// Object.fromEntries(iterable)
+fn object_from_entries(call_ctx: &mut CallContext, args: Vec<Value>) -> JSIResult<Value> {
+  let iterable = args.first().cloned().unwrap_or(Value::Undefined);
+  let entries = match get_iterable_values(call_ctx.ctx, &iterable) {
+    Some(entries) => entries,
+    None => return Err(JSIError::new(JSIErrorType::TypeError, format!("{} is not iterable", iterable.type_of()), 0, 0)),
   };
-  let obj = obj_rc.borrow();
-  for key in obj.property_list.iter() {
-    let prop = obj.property.get(key);
-    if let Some(property) = prop {
-      if property.enumerable {
-        Object::call(new_call_ctx, String::from("push"), vec![Value::String(key.clone())])?;
+  let new_object = create_object(call_ctx.ctx, ClassType::Object, None);
+  for entry in entries {
+    if !entry.is_object() {
+      return Err(JSIError::new(JSIErrorType::TypeError, format!("Iterator value {} is not an entry object", entry.to_string(call_ctx.ctx)), 0, 0))
+    }
+    let (key, value) = {
+      let entry_obj = entry.to_object(call_ctx.ctx);
+      let entry_obj = entry_obj.borrow();
+      (entry_obj.get_value(String::from("0")), entry_obj.get_value(String::from("1")))
+    };
+    let key = key.to_property_key(call_ctx.ctx);
+    (*new_object).borrow_mut().define_property(key, Property { enumerable: true, value });
+  }
+  Ok(Value::Object(new_object))
+}
+
+// Object.setPrototypeOf(obj, proto)
+fn object_set_prototype_of(call_ctx: &mut CallContext, args: Vec<Value>) -> JSIResult<Value> {
+  let target = args.first().cloned().unwrap_or(Value::Undefined);
+  let proto = args.get(1).cloned().unwrap_or(Value::Undefined);
+  if target.is_not_strict_null() {
+    return Err(JSIError::new(JSIErrorType::TypeError, String::from("Object.setPrototypeOf called on null or undefined"), 0, 0))
+  }
+  let proto_obj = match &proto {
+    Value::Null => None,
+    Value::RefObject(_) => proto.to_weak_rc_object().and_then(|proto| proto.upgrade()),
+    _ if proto.is_object() => proto.to_weak_rc_object().and_then(|proto| proto.upgrade()),
+    _ => return Err(JSIError::new(JSIErrorType::TypeError, format!("Object prototype may only be an Object or null: {}", proto.to_string(call_ctx.ctx)), 0, 0)),
+  };
+  // 原始值不能设置原型，直接返回
+  if !target.is_object() {
+    return Ok(target)
+  }
+  let target_obj = target.to_object(call_ctx.ctx);
+  if target_obj.borrow().frozen {
+    return Err(JSIError::new(JSIErrorType::TypeError, String::from("Object is not extensible"), 0, 0));
+  }
+  let proto_value = match proto_obj {
+    Some(proto_obj) => {
+      // 原型链上不能出现对象自身
+      let mut current = Some(proto_obj);
+      while let Some(obj) = current {
+        if Rc::ptr_eq(&obj, &target_obj) {
+          return Err(JSIError::new(JSIErrorType::TypeError, String::from("Cyclic __proto__ value"), 0, 0))
+        }
+        current = obj.borrow().get_prototype_object();
+      }
+      proto
+    },
+    None => Value::Null,
+  };
+  (*target_obj).borrow_mut().set_inner_property_value(PROTO_PROPERTY_NAME.to_string(), proto_value);
+  Ok(target)
+}
+
+// Object.is
+fn object_is(call_ctx: &mut CallContext, args: Vec<Value>) -> JSIResult<Value> {
+  let left = args.first().cloned().unwrap_or(Value::Undefined);
+  let right = args.get(1).cloned().unwrap_or(Value::Undefined);
+  Ok(Value::Boolean(left.same_value(call_ctx.ctx, &right)))
+}
+
+// Object.groupBy(items, callback)
+fn object_group_by(call_ctx: &mut CallContext, args: Vec<Value>) -> JSIResult<Value> {
+  let groups = group_by(call_ctx, args, true)?;
+  // 返回值是没有原型的对象
+  let new_object = create_object(call_ctx.ctx, ClassType::Object, None);
+  (*new_object).borrow_mut().set_inner_property_value(PROTO_PROPERTY_NAME.to_string(), Value::Null);
+  for (key, items) in groups {
+    let key = key.to_property_key(call_ctx.ctx);
+    let items = create_array_from_values(call_ctx.ctx, items);
+    (*new_object).borrow_mut().define_property(key, Property { enumerable: true, value: items });
+  }
+  Ok(Value::Object(new_object))
+}
+
+// GroupBy：按照回调返回的键对可迭代对象分组，分组按照键第一次出现的顺序排列
+// is_property_key 为 true 时键转换为属性键（Object.groupBy），否则按照 SameValueZero 比较（Map.groupBy）
+pub fn group_by(call_ctx: &mut CallContext, args: Vec<Value>, is_property_key: bool) -> JSIResult<Vec<(Value, Vec<Value>)>> {
+  let items = args.first().cloned().unwrap_or(Value::Undefined);
+  let callback = args.get(1).cloned().unwrap_or(Value::Undefined);
+  let values = match get_iterable_values(call_ctx.ctx, &items) {
+    Some(values) => values,
+    None => return Err(JSIError::new(JSIErrorType::TypeError, format!("{} is not iterable", items.type_of()), 0, 0)),
+  };
+  let callback = match &callback {
+    Value::Function(callback) => Rc::clone(callback),
+    _ => return Err(JSIError::new(JSIErrorType::TypeError, format!("{} is not a function", callback.to_string(call_ctx.ctx)), 0, 0)),
+  };
+  let mut groups: Vec<(Value, Vec<Value>)> = vec![];
+  for (index, value) in values.into_iter().enumerate() {
+    let key = call_callback(call_ctx, &callback, &Value::Undefined, vec![value.clone(), Value::Number(index as f64)])?;
+    let key = if is_property_key {
+      Value::from_property_key(key.to_property_key(call_ctx.ctx))
+    } else {
+      match key {
+        // -0 作为键时统一为 +0（-0 + 0 为 +0）
+        Value::Number(num) => Value::Number(num + 0f64),
+        _ => key,
+      }
+    };
+    let mut group_index = None;
+    for (i, (group_key, _)) in groups.iter().enumerate() {
+      if group_key.same_value_zero(call_ctx.ctx, &key) {
+        group_index = Some(i);
+        break;
       }
     }
+    match group_index {
+      Some(i) => groups[i].1.push(value),
+      None => groups.push((key, vec![value])),
+    }
   }
-  return Ok(Value::Array(array_obj));
+  Ok(groups)
 }
 
-// Object.getOwnPropertyNames
-fn object_get_own_property_names(call_ctx: &mut CallContext, args: Vec<Value>) -> JSIResult<Value> {
-  let array = create_array(call_ctx.ctx, 0);
-  let array_obj = match array {
-    Value::Array(arr) => Some(arr),
-    _ => None
-  }.unwrap();
-
-  if args.len() < 1 {
-    return Ok(Value::Array(array_obj));
-  }
-  let obj_rc= args[0].to_object(call_ctx.ctx);
-  let new_call_ctx = &mut CallContext {
-    ctx: call_ctx.ctx,
-    this: Value::Array(Rc::clone(&array_obj)),
-    reference: None,
-    func_name: String::from("push"),
+// ToObject，null 和 undefined 抛出 TypeError
+fn to_object_or_throw(ctx: &mut Context, value: &Value) -> JSIResult<Rc<RefCell<Object>>> {
+  if value.is_not_strict_null() {
+    return Err(JSIError::new(JSIErrorType::TypeError, String::from("Cannot convert undefined or null to object"), 0, 0))
+  }
+  Ok(value.to_object(ctx))
+}
+
+// 自身可枚举属性的键值对，按照规范的属性顺序排列，字符串包含每个索引位置
+fn get_own_enumerable_entries(ctx: &mut Context, value: &Value) -> JSIResult<Vec<(String, Value)>> {
+  let obj_rc = to_object_or_throw(ctx, value)?;
+  let mut entries: Vec<(String, Value)> = vec![];
+  if value.is_string() {
+    let units = value.to_utf16(ctx);
+    for (index, unit) in units.into_iter().enumerate() {
+      entries.push((index.to_string(), Value::from_utf16(vec![unit])));
+    }
+  }
+  let (keys, mut getters) = {
+    let obj = obj_rc.borrow();
+    let keys = obj.get_own_property_keys();
+    // 可枚举的访问器属性通过 getter 取值，按名称排序保证顺序稳定
+    let mut getters: Vec<(String, Option<Value>)> = obj.accessor.iter().filter(|(_, accessor)| accessor.enumerable).map(|(key, accessor)| {
+      (key.clone(), accessor.get.clone())
+    }).collect();
+    getters.sort_by(|(a, _), (b, _)| a.cmp(b));
+    (keys, getters)
   };
-  let obj = obj_rc.borrow();
-  for key in obj.property_list.iter() {
-    Object::call(new_call_ctx, String::from("push"), vec![Value::String(key.clone())])?;
+  for key in keys {
+    let property = obj_rc.borrow().property.get(&key).cloned();
+    if let Some(property) = property {
+      if property.enumerable {
+        entries.push((key, property.value));
+      }
+    }
+  }
+  for (key, getter) in getters.drain(..) {
+    let value = match getter {
+      Some(Value::Function(getter)) => ctx.call_function_object(getter, Some(value.clone()), None, vec![])?,
+      _ => Value::Undefined,
+    };
+    entries.push((key, value));
   }
-  return Ok(Value::Array(array_obj));
+  Ok(entries)
 }
 
 // Object.getPrototypeOf
@@ -444,14 +793,61 @@ fn object_get_prototype_of(call_ctx: &mut CallContext, args: Vec<Value>) -> JSIR
 fn has_own_property(call_ctx: &mut CallContext, args: Vec<Value>) -> JSIResult<Value> {
   let obj_rc = call_ctx.this.to_object(call_ctx.ctx);
   if args.len() > 0 {
-    let property_name = args[0].to_string(call_ctx.ctx);
+    let property_name = args[0].to_property_key(call_ctx.ctx);
     let obj = obj_rc.borrow();
-    return Ok(Value::Boolean(obj.property.contains_key(&property_name)));
+    return Ok(Value::Boolean(obj.property.contains_key(&property_name) || obj.accessor.contains_key(&property_name)));
   }
   
   return Ok(Value::Boolean(false));
 }
 
+// Object.prototype.isPrototypeOf
+fn is_prototype_of(call_ctx: &mut CallContext, args: Vec<Value>) -> JSIResult<Value> {
+  let value = args.first().cloned().unwrap_or(Value::Undefined);
+  if !value.is_object() {
+    return Ok(Value::Boolean(false))
+  }
+  let this = call_ctx.this.clone();
+  let this_obj = to_object_or_throw(call_ctx.ctx, &this)?;
+  let mut current = value.to_object(call_ctx.ctx).borrow().get_prototype_object();
+  while let Some(obj) = current {
+    if Rc::ptr_eq(&obj, &this_obj) {
+      return Ok(Value::Boolean(true))
+    }
+    current = obj.borrow().get_prototype_object();
+  }
+  Ok(Value::Boolean(false))
+}
+
+// Object.prototype.propertyIsEnumerable
+fn property_is_enumerable(call_ctx: &mut CallContext, args: Vec<Value>) -> JSIResult<Value> {
+  let property_name = args.first().cloned().unwrap_or(Value::Undefined).to_property_key(call_ctx.ctx);
+  let this = call_ctx.this.clone();
+  let obj_rc = to_object_or_throw(call_ctx.ctx, &this)?;
+  if this.is_string() {
+    // 字符串的索引位置是可枚举的
+    if let Ok(index) = property_name.parse::<usize>() {
+      if index.to_string() == property_name && index < this.to_utf16(call_ctx.ctx).len() {
+        return Ok(Value::Boolean(true))
+      }
+    }
+  }
+  let obj = obj_rc.borrow();
+  let enumerable = match obj.property.get(&property_name) {
+    Some(prop) => prop.enumerable,
+    None => obj.accessor.get(&property_name).is_some_and(|accessor| accessor.enumerable),
+  };
+  Ok(Value::Boolean(enumerable))
+}
+
+// Object.prototype.toLocaleString
+fn to_locale_string(call_ctx: &mut CallContext, _: Vec<Value>) -> JSIResult<Value> {
+  if call_ctx.this.is_not_strict_null() {
+    return Err(JSIError::new(JSIErrorType::TypeError, String::from("Object.prototype.toLocaleString called on null or undefined"), 0, 0))
+  }
+  Object::call(call_ctx, String::from("toString"), vec![])
+}
+
 // Object.prototype.toString
 fn to_string(call_ctx: &mut CallContext, _: Vec<Value>) -> JSIResult<Value> {
   let this_origin = call_ctx.this.to_object(call_ctx.ctx);
@@ -18,7 +18,7 @@ pub const PROMISE_REJECTED_REASON: &str = "[[PromiseRejectedReason]]";
 pub const PROMISE_FULFILLED_REACTIONS: &str = "[[PromiseFulfilledReactions]]";
 pub const PROMISE_REJECTED_REACTIONS: &str = "[[PromiseRejectedReactions]]";
 
-pub fn create_promise(ctx: &mut Context, init: Value) -> Value {
+pub fn create_promise(ctx: &mut Context, init: Value) -> JSIResult<Value> {
     let (promise, resolve_fn, reject_fn) = create_promise_helper(ctx);
 
     if let Value::Function(init_function) = init {
@@ -29,10 +29,21 @@ pub fn create_promise(ctx: &mut Context, init: Value) -> Value {
             reference: None,
             func_name: String::from(""),
         };
-        call_ctx.call_function(init_function, None, None, vec![resolve_fn, reject_fn]).unwrap();
+        let result = call_ctx.call_function(init_function, None, None, vec![resolve_fn, reject_fn.clone()]);
+        if let Err(err) = result {
+            // 执行被终止时不能转换为 reject
+            if err.error_type == JSIErrorType::Terminated {
+                return Err(err);
+            }
+            // executor 抛出错误时 reject
+            if let Value::Function(reject_fn) = reject_fn {
+                let reason = err.value.unwrap_or(Value::String(err.message));
+                call_ctx.call_function(reject_fn, None, None, vec![reason])?;
+            }
+        }
     }
 
-    Value::Promise(promise)
+    Ok(Value::Promise(promise))
 }
 
 
@@ -108,7 +119,7 @@ fn create(call_ctx: &mut CallContext, args: Vec<Value>) -> JSIResult<Value> {
         return Err(JSIError::new(JSIErrorType::TypeError, "Promise resolver is not a function".to_string(), 0, 0));
     }
 
-  Ok(create_promise(call_ctx.ctx, executor.to_owned()))
+  create_promise(call_ctx.ctx, executor.to_owned())
 }
 
 
@@ -383,7 +394,7 @@ fn resolve_static(call_ctx: &mut CallContext, args: Vec<Value>) -> JSIResult<Val
         return Ok(value.to_owned());
     }
 
-    let promise_value = create_promise(call_ctx.ctx, Value::Undefined);
+    let promise_value = create_promise(call_ctx.ctx, Value::Undefined)?;
 
     if let Value::Promise(promise_rc) = &promise_value {
         let mut promise_mut = promise_rc.borrow_mut();
@@ -397,7 +408,7 @@ fn resolve_static(call_ctx: &mut CallContext, args: Vec<Value>) -> JSIResult<Val
 fn reject_static(call_ctx: &mut CallContext, args: Vec<Value>) -> JSIResult<Value> {
     let reason = args.get(0).or(Some(&Value::Undefined)).unwrap();
 
-    let promise_value = create_promise(call_ctx.ctx, Value::Undefined);
+    let promise_value = create_promise(call_ctx.ctx, Value::Undefined)?;
 
     if let Value::Promise(promise_rc) = &promise_value {
         let mut promise_mut = promise_rc.borrow_mut();
@@ -4,6 +4,7 @@ use crate::constants::{PROTO_PROPERTY_NAME, GLOBAL_STRING_NAME};
 use crate::context::{Context};
 use crate::error::{JSIError, JSIErrorType};
 use crate::{value::{Value, INSTANTIATE_OBJECT_METHOD_NAME}, ast_node::{ClassType, CallContext}, error::JSIResult};
+use unicode_normalization::UnicodeNormalization;
 
 use super::global::{get_global_object_prototype_by_name, get_global_object_by_name};
 use super::{object::{create_object, Property}, function::builtin_function};
@@ -32,6 +33,15 @@ pub fn bind_global_string(ctx: &mut Context) {
   let mut string = (*string_rc).borrow_mut();
   let create_function = builtin_function(ctx, INSTANTIATE_OBJECT_METHOD_NAME.to_string(), 1f64, create);
   string.set_inner_property_value(INSTANTIATE_OBJECT_METHOD_NAME.to_string(), create_function);
+  // String.fromCharCode
+  let name = String::from("fromCharCode");
+  string.property.insert(name.clone(), Property { enumerable: true, value: builtin_function(ctx, name, 1f64, string_static_from_char_code) });
+  // String.fromCodePoint
+  let name = String::from("fromCodePoint");
+  string.property.insert(name.clone(), Property { enumerable: true, value: builtin_function(ctx, name, 1f64, string_static_from_code_point) });
+  // String.raw
+  let name = String::from("raw");
+  string.property.insert(name.clone(), Property { enumerable: true, value: builtin_function(ctx, name, 1f64, string_static_raw) });
   if let Some(prop)= &string.prototype {
     let prototype_rc = Rc::clone(prop);
     let mut prototype = (*prototype_rc).borrow_mut();
@@ -65,6 +75,28 @@ pub fn bind_global_string(ctx: &mut Context) {
     prototype.define_property(name.clone(), Property { enumerable: true, value: builtin_function(ctx, name, 1f64, string_split) });
     let name = String::from("concat");
     prototype.define_property(name.clone(), Property { enumerable: true, value: builtin_function(ctx, name, 1f64, string_concat) });
+    let name = String::from("padStart");
+    prototype.define_property(name.clone(), Property { enumerable: true, value: builtin_function(ctx, name, 2f64, string_pad_start) });
+    let name = String::from("padEnd");
+    prototype.define_property(name.clone(), Property { enumerable: true, value: builtin_function(ctx, name, 2f64, string_pad_end) });
+    let name = String::from("repeat");
+    prototype.define_property(name.clone(), Property { enumerable: true, value: builtin_function(ctx, name, 1f64, string_repeat) });
+    let name = String::from("trimStart");
+    prototype.define_property(name.clone(), Property { enumerable: true, value: builtin_function(ctx, name, 0f64, string_trim_start) });
+    let name = String::from("trimEnd");
+    prototype.define_property(name.clone(), Property { enumerable: true, value: builtin_function(ctx, name, 0f64, string_trim_end) });
+    let name = String::from("at");
+    prototype.define_property(name.clone(), Property { enumerable: true, value: builtin_function(ctx, name, 1f64, string_at) });
+    let name = String::from("charCodeAt");
+    prototype.define_property(name.clone(), Property { enumerable: true, value: builtin_function(ctx, name, 1f64, string_char_code_at) });
+    let name = String::from("codePointAt");
+    prototype.define_property(name.clone(), Property { enumerable: true, value: builtin_function(ctx, name, 1f64, string_code_point_at) });
+    let name = String::from("normalize");
+    prototype.define_property(name.clone(), Property { enumerable: true, value: builtin_function(ctx, name, 0f64, string_normalize) });
+    let name = String::from("localeCompare");
+    prototype.define_property(name.clone(), Property { enumerable: true, value: builtin_function(ctx, name, 1f64, string_locale_compare) });
+    let name = String::from("substr");
+    prototype.define_property(name.clone(), Property { enumerable: true, value: builtin_function(ctx, name, 2f64, string_substr) });
     // length 属性
     prototype.define_property(String::from("length"), Property { enumerable: false, value: Value::Number(0f64) });
   }
@@ -79,51 +111,75 @@ fn create(call_ctx: &mut CallContext, args: Vec<Value>) -> JSIResult<Value> {
   Ok(create_string(call_ctx.ctx, param))
 }
 
+// 字符串自身的属性：length 和索引，均以 UTF-16 编码单元计算
+// 不生成完整的 UTF-16 编码，ASCII 字符串直接按字节访问，避免循环访问时每次都重新编码
+pub fn get_string_own_property(ctx: &mut Context, value: &Value, name: &str) -> Option<Value> {
+  match value {
+    Value::String(str) => {
+      if name == "length" {
+        let length = if str.is_ascii() { str.len() } else { str.encode_utf16().count() };
+        return Some(Value::Number(length as f64))
+      }
+      let index = parse_string_index(name)?;
+      if str.is_ascii() {
+        return str.get(index..index + 1).map(|char| Value::String(char.to_string()))
+      }
+      str.encode_utf16().nth(index).map(|unit| Value::from_utf16(vec![unit]))
+    },
+    Value::Utf16String(units) => {
+      if name == "length" {
+        return Some(Value::Number(units.len() as f64))
+      }
+      let index = parse_string_index(name)?;
+      units.get(index).map(|unit| Value::from_utf16(vec![*unit]))
+    },
+    Value::StringObj(obj) => {
+      let inner = obj.borrow().get_inner_property_value(String::from("value"));
+      match inner {
+        Some(inner @ (Value::String(_) | Value::Utf16String(_))) => get_string_own_property(ctx, &inner, name),
+        _ => {
+          let inner = Value::from_utf16(value.to_utf16(ctx));
+          get_string_own_property(ctx, &inner, name)
+        },
+      }
+    },
+    _ => None,
+  }
+}
+
+// 规范的数组索引，如 "01" 不是索引
+fn parse_string_index(name: &str) -> Option<usize> {
+  let index = name.parse::<usize>().ok()?;
+  if index.to_string() != name {
+    return None
+  }
+  Some(index)
+}
+
 // String.prototype.toString
 fn to_string(call_ctx: &mut CallContext, _: Vec<Value>) -> JSIResult<Value> {
   let string = get_string(call_ctx);
-  if let Ok(str) = string {
-    return Ok(Value::String(str))
+  if let Ok(units) = string {
+    return Ok(Value::from_utf16(units))
   }
   Err(JSIError::new(JSIErrorType::TypeError, format!("String.prototype.toString requires that 'this' be a String"), 0, 0))
 }
 
 // String.prototype.charAt
 fn char_at(call_ctx: &mut CallContext, args: Vec<Value>) -> JSIResult<Value> {
-  if call_ctx.this.is_not_strict_null() {
-    return  Err(JSIError::new(JSIErrorType::TypeError, format!("String.prototype.charAt called on null or undefined"), 0, 0))
-  }
-  let string = get_string(call_ctx);
-  if let Err(_) = string {
-    return Err(JSIError::new(JSIErrorType::TypeError, format!("unknown error"), 0, 0))
-  }
-  let str = match string {
-    Ok(str) => str,
-    _ => String::from(""),
-  };
-  let mut index: usize = 0;
-  if args.len() > 0 {
-    let arg = &args[0];
-    let index_number = arg.to_number(call_ctx.ctx);
-    if let Some(index_f64) = index_number {
-      index = index_f64 as usize;
-    } else {
-      let arg_type = arg.type_of();
-      return Err(JSIError::new(JSIErrorType::TypeError, format!("Cannot convert {:?} to primitive value at String.charAt", arg_type), 0, 0))
-    }
+  let units = get_this_string(call_ctx, "charAt")?;
+  let index = args.first().unwrap_or(&Value::Undefined).to_integer_or_infinity(call_ctx.ctx)?;
+  if index < 0f64 || index >= units.len() as f64 {
+    return Ok(Value::String(String::new()))
   }
-  let utf16: Vec<u16> = str.as_str().encode_utf16().collect();
-  if index < utf16.len() {
-    let ch = char::from_u32(utf16[index] as u32).unwrap_or('\0');
-    return Ok(Value::String(ch.to_string()))
-  }
-
-  return Ok(Value::String("".to_string()))
+  Ok(Value::from_utf16(vec![units[index as usize]]))
 }
 
-fn get_string(call_ctx: &mut CallContext) -> Result<String, Box<dyn Error>> {
-  if let Value::String(str) = &call_ctx.this {
-    return Ok(str.clone())
+// this 为 String 或 String 对象时，获取其 UTF-16 编码单元
+fn get_string(call_ctx: &mut CallContext) -> Result<Vec<u16>, Box<dyn Error>> {
+  if let Value::String(_) | Value::Utf16String(_) = &call_ctx.this {
+    let this = call_ctx.this.clone();
+    return Ok(this.to_utf16(call_ctx.ctx))
   }
   let string_obj = match &call_ctx.this {
     Value::Object(string) => {
@@ -139,365 +195,498 @@ fn get_string(call_ctx: &mut CallContext) -> Result<String, Box<dyn Error>> {
   if let Some(str) = string_obj {
     let init = str.borrow().get_inner_property_value(String::from("value"));
     if let Some(value) = init {
-      return Ok(value.to_string(call_ctx.ctx))
+      return Ok(value.to_utf16(call_ctx.ctx))
     }
   }
   Err("error".into())
 }
 
-// String.prototype.includes
-fn string_includes(call_ctx: &mut CallContext, args: Vec<Value>) -> JSIResult<Value> {
-  let string = get_string(call_ctx);
-  if let Err(_) = string {
-    return Err(JSIError::new(JSIErrorType::TypeError, format!("String.prototype.includes called on incompatible receiver"), 0, 0))
+// 获取 this 对应的字符串的 UTF-16 编码单元，null 和 undefined 抛出 TypeError
+fn get_this_string(call_ctx: &mut CallContext, method: &str) -> JSIResult<Vec<u16>> {
+  if call_ctx.this.is_not_strict_null() {
+    return Err(JSIError::new(JSIErrorType::TypeError, format!("String.prototype.{} called on null or undefined", method), 0, 0))
   }
-  let str = string.unwrap();
+  if let Ok(units) = get_string(call_ctx) {
+    return Ok(units)
+  }
+  let this = call_ctx.this.clone();
+  Ok(this.to_utf16(call_ctx.ctx))
+}
 
-  let search_str = if args.len() > 0 {
-    args[0].to_string(call_ctx.ctx)
-  } else {
-    String::from("undefined")
-  };
+// 参数转换为 [0, len] 范围内的位置，参数为 undefined 时返回 default
+fn get_clamped_position(ctx: &mut Context, value: Option<&Value>, default: usize, len: usize) -> JSIResult<usize> {
+  match value {
+    None | Some(Value::Undefined) => Ok(default),
+    Some(value) => Ok(value.to_integer_or_infinity(ctx)?.max(0f64).min(len as f64) as usize),
+  }
+}
 
-  let mut position: usize = 0;
-  if args.len() > 1 {
-    if let Some(pos) = args[1].to_number(call_ctx.ctx) {
-      position = if pos < 0f64 { 0 } else { pos as usize };
-    }
+// 相对位置（负数从末尾开始计算）转换为 [0, len] 范围内的位置
+fn get_relative_position(ctx: &mut Context, value: Option<&Value>, default: usize, len: usize) -> JSIResult<usize> {
+  match value {
+    None | Some(Value::Undefined) => Ok(default),
+    Some(value) => {
+      let relative = value.to_integer_or_infinity(ctx)?;
+      if relative < 0f64 {
+        Ok((len as f64 + relative).max(0f64) as usize)
+      } else {
+        Ok(relative.min(len as f64) as usize)
+      }
+    },
+  }
+}
+
+// 从 from 开始查找 search 在 units 中第一次出现的位置
+fn find_units(units: &[u16], search: &[u16], from: usize) -> Option<usize> {
+  if from > units.len() || search.len() > units.len() - from {
+    return None
   }
+  (from..=units.len() - search.len()).find(|index| units[*index..].starts_with(search))
+}
 
-  if position > str.len() {
-    return Ok(Value::Boolean(false));
+// 查找 search 在 units 中不大于 from 的最后一次出现的位置
+fn rfind_units(units: &[u16], search: &[u16], from: usize) -> Option<usize> {
+  if search.len() > units.len() {
+    return None
   }
+  let start = from.min(units.len() - search.len());
+  (0..=start).rev().find(|index| units[*index..].starts_with(search))
+}
 
-  let result = str[position..].contains(&search_str);
-  Ok(Value::Boolean(result))
+fn get_search_string(ctx: &mut Context, args: &[Value]) -> Vec<u16> {
+  args.first().unwrap_or(&Value::Undefined).to_utf16(ctx)
+}
+
+// String.prototype.includes
+fn string_includes(call_ctx: &mut CallContext, args: Vec<Value>) -> JSIResult<Value> {
+  let units = get_this_string(call_ctx, "includes")?;
+  let search = get_search_string(call_ctx.ctx, &args);
+  let position = get_clamped_position(call_ctx.ctx, args.get(1), 0, units.len())?;
+  Ok(Value::Boolean(find_units(&units, &search, position).is_some()))
 }
 
 // String.prototype.indexOf
 fn string_index_of(call_ctx: &mut CallContext, args: Vec<Value>) -> JSIResult<Value> {
-  let string = get_string(call_ctx);
-  if let Err(_) = string {
-    return Err(JSIError::new(JSIErrorType::TypeError, format!("String.prototype.indexOf called on incompatible receiver"), 0, 0))
+  let units = get_this_string(call_ctx, "indexOf")?;
+  let search = get_search_string(call_ctx.ctx, &args);
+  let position = get_clamped_position(call_ctx.ctx, args.get(1), 0, units.len())?;
+  match find_units(&units, &search, position) {
+    Some(index) => Ok(Value::Number(index as f64)),
+    None => Ok(Value::Number(-1f64)),
   }
-  let str = string.unwrap();
+}
 
-  let search_str = if args.len() > 0 {
-    args[0].to_string(call_ctx.ctx)
-  } else {
-    String::from("undefined")
+// String.prototype.lastIndexOf
+fn string_last_index_of(call_ctx: &mut CallContext, args: Vec<Value>) -> JSIResult<Value> {
+  let units = get_this_string(call_ctx, "lastIndexOf")?;
+  let search = get_search_string(call_ctx.ctx, &args);
+  // position 为 NaN 时从末尾开始搜索
+  let position = match args.get(1) {
+    Some(pos) if !pos.to_number(call_ctx.ctx).unwrap_or(f64::NAN).is_nan() => get_clamped_position(call_ctx.ctx, Some(pos), units.len(), units.len())?,
+    _ => units.len(),
   };
-
-  let mut position: usize = 0;
-  if args.len() > 1 {
-    if let Some(pos) = args[1].to_number(call_ctx.ctx) {
-      position = if pos < 0f64 { 0 } else { pos as usize };
-    }
+  match rfind_units(&units, &search, position) {
+    Some(index) => Ok(Value::Number(index as f64)),
+    None => Ok(Value::Number(-1f64)),
   }
+}
 
-  if position > str.len() {
-    return Ok(Value::Number(-1f64));
-  }
+// WhiteSpace 和 LineTerminator
+fn is_js_whitespace(ch: char) -> bool {
+  matches!(ch,
+    '\u{0009}' | '\u{000A}' | '\u{000B}' | '\u{000C}' | '\u{000D}' | '\u{0020}' | '\u{00A0}' | '\u{1680}' |
+    '\u{2000}'..='\u{200A}' | '\u{2028}' | '\u{2029}' | '\u{202F}' | '\u{205F}' | '\u{3000}' | '\u{FEFF}'
+  )
+}
 
-  let result = str[position..].find(&search_str);
-  match result {
-    Some(index) => Ok(Value::Number((position + index) as f64)),
-    None => Ok(Value::Number(-1f64)),
+// 空白字符都在基本多文种平面内，可以直接按照编码单元判断
+fn is_js_whitespace_unit(unit: &u16) -> bool {
+  char::from_u32(*unit as u32).is_some_and(is_js_whitespace)
+}
+
+// 去除字符串开头和（或）结尾的空白字符
+fn trim_units(units: &[u16], trim_start: bool, trim_end: bool) -> Vec<u16> {
+  let start = if trim_start { units.iter().position(|unit| !is_js_whitespace_unit(unit)).unwrap_or(units.len()) } else { 0 };
+  let end = if trim_end { units.iter().rposition(|unit| !is_js_whitespace_unit(unit)).map_or(start, |index| index + 1) } else { units.len() };
+  if start >= end {
+    return vec![]
   }
+  units[start..end].to_vec()
 }
 
 // String.prototype.trim
 fn string_trim(call_ctx: &mut CallContext, _: Vec<Value>) -> JSIResult<Value> {
-  let string = get_string(call_ctx);
-  if let Err(_) = string {
-    return Err(JSIError::new(JSIErrorType::TypeError, format!("String.prototype.trim called on incompatible receiver"), 0, 0))
-  }
-  let str = string.unwrap();
-  Ok(Value::String(str.trim().to_string()))
+  let units = get_this_string(call_ctx, "trim")?;
+  Ok(Value::from_utf16(trim_units(&units, true, true)))
 }
 
-// String.prototype.startsWith
-fn string_starts_with(call_ctx: &mut CallContext, args: Vec<Value>) -> JSIResult<Value> {
-  let string = get_string(call_ctx);
-  if let Err(_) = string {
-    return Err(JSIError::new(JSIErrorType::TypeError, format!("String.prototype.startsWith called on incompatible receiver"), 0, 0))
-  }
-  let str = string.unwrap();
-
-  let search_str = if args.len() > 0 {
-    args[0].to_string(call_ctx.ctx)
-  } else {
-    String::from("undefined")
-  };
-
-  let mut position: usize = 0;
-  if args.len() > 1 {
-    if let Some(pos) = args[1].to_number(call_ctx.ctx) {
-      position = if pos < 0f64 { 0 } else { pos as usize };
-    }
-  }
+// String.prototype.trimStart
+fn string_trim_start(call_ctx: &mut CallContext, _: Vec<Value>) -> JSIResult<Value> {
+  let units = get_this_string(call_ctx, "trimStart")?;
+  Ok(Value::from_utf16(trim_units(&units, true, false)))
+}
 
-  if position > str.len() {
-    return Ok(Value::Boolean(false));
-  }
+// String.prototype.trimEnd
+fn string_trim_end(call_ctx: &mut CallContext, _: Vec<Value>) -> JSIResult<Value> {
+  let units = get_this_string(call_ctx, "trimEnd")?;
+  Ok(Value::from_utf16(trim_units(&units, false, true)))
+}
 
-  Ok(Value::Boolean(str[position..].starts_with(&search_str)))
+// String.prototype.startsWith
+fn string_starts_with(call_ctx: &mut CallContext, args: Vec<Value>) -> JSIResult<Value> {
+  let units = get_this_string(call_ctx, "startsWith")?;
+  let search = get_search_string(call_ctx.ctx, &args);
+  let position = get_clamped_position(call_ctx.ctx, args.get(1), 0, units.len())?;
+  Ok(Value::Boolean(units[position..].starts_with(&search)))
 }
 
 // String.prototype.endsWith
 fn string_ends_with(call_ctx: &mut CallContext, args: Vec<Value>) -> JSIResult<Value> {
-  let string = get_string(call_ctx);
-  if let Err(_) = string {
-    return Err(JSIError::new(JSIErrorType::TypeError, format!("String.prototype.endsWith called on incompatible receiver"), 0, 0))
-  }
-  let str = string.unwrap();
-
-  let search_str = if args.len() > 0 {
-    args[0].to_string(call_ctx.ctx)
-  } else {
-    String::from("undefined")
-  };
-
-  let mut end_position: usize = str.len();
-  if args.len() > 1 {
-    if let Some(pos) = args[1].to_number(call_ctx.ctx) {
-      end_position = if pos < 0f64 { 0 } else { pos as usize };
-    }
-  }
-
-  if end_position > str.len() {
-    end_position = str.len();
-  }
-
-  Ok(Value::Boolean(str[..end_position].ends_with(&search_str)))
+  let units = get_this_string(call_ctx, "endsWith")?;
+  let search = get_search_string(call_ctx.ctx, &args);
+  let end_position = get_clamped_position(call_ctx.ctx, args.get(1), units.len(), units.len())?;
+  Ok(Value::Boolean(units[..end_position].ends_with(&search)))
 }
 
 // String.prototype.slice
 fn string_slice(call_ctx: &mut CallContext, args: Vec<Value>) -> JSIResult<Value> {
-  let string = get_string(call_ctx);
-  if let Err(_) = string {
-    return Err(JSIError::new(JSIErrorType::TypeError, format!("String.prototype.slice called on incompatible receiver"), 0, 0))
-  }
-  let str = string.unwrap();
-  let len = str.len() as i32;
-
-  let mut start: i32 = 0;
-  if args.len() > 0 {
-    if let Some(pos) = args[0].to_number(call_ctx.ctx) {
-      start = pos as i32;
-      // 处理负数索引
-      if start < 0 {
-        start = len + start;
-        if start < 0 {
-          start = 0;
-        }
-      }
-    }
+  let units = get_this_string(call_ctx, "slice")?;
+  let start = get_relative_position(call_ctx.ctx, args.first(), 0, units.len())?;
+  let end = get_relative_position(call_ctx.ctx, args.get(1), units.len(), units.len())?;
+  if start >= end {
+    return Ok(Value::String(String::new()));
   }
+  Ok(Value::from_utf16(units[start..end].to_vec()))
+}
 
-  let mut end: i32 = len;
-  if args.len() > 1 {
-    if let Some(pos) = args[1].to_number(call_ctx.ctx) {
-      end = pos as i32;
-      // 处理负数索引
-      if end < 0 {
-        end = len + end;
-        if end < 0 {
-          end = 0;
-        }
-      }
+// 对合法的字符片段应用 f，单独的代理项保持不变
+fn map_well_formed(units: &[u16], f: impl Fn(&str) -> String) -> Vec<u16> {
+  let mut result: Vec<u16> = Vec::with_capacity(units.len());
+  let mut segment = String::new();
+  for item in char::decode_utf16(units.iter().copied()) {
+    match item {
+      Ok(ch) => segment.push(ch),
+      Err(err) => {
+        result.extend(f(&segment).encode_utf16());
+        segment.clear();
+        result.push(err.unpaired_surrogate());
+      },
     }
   }
-
-  if start > end {
-    return Ok(Value::String(String::from("")));
-  }
-
-  if start > len {
-    start = len;
-  }
-  if end > len {
-    end = len;
-  }
-
-  let start = start as usize;
-  let end = end as usize;
-
-  Ok(Value::String(str[start..end].to_string()))
+  result.extend(f(&segment).encode_utf16());
+  result
 }
 
 // String.prototype.toLowerCase
 fn string_to_lower_case(call_ctx: &mut CallContext, _: Vec<Value>) -> JSIResult<Value> {
-  let string = get_string(call_ctx);
-  if let Err(_) = string {
-    return Err(JSIError::new(JSIErrorType::TypeError, format!("String.prototype.toLowerCase called on incompatible receiver"), 0, 0))
-  }
-  let str = string.unwrap();
-  Ok(Value::String(str.to_lowercase()))
+  let units = get_this_string(call_ctx, "toLowerCase")?;
+  Ok(Value::from_utf16(map_well_formed(&units, |str| str.to_lowercase())))
 }
 
 // String.prototype.toUpperCase
 fn string_to_upper_case(call_ctx: &mut CallContext, _: Vec<Value>) -> JSIResult<Value> {
-  let string = get_string(call_ctx);
-  if let Err(_) = string {
-    return Err(JSIError::new(JSIErrorType::TypeError, format!("String.prototype.toUpperCase called on incompatible receiver"), 0, 0))
-  }
-  let str = string.unwrap();
-  Ok(Value::String(str.to_uppercase()))
+  let units = get_this_string(call_ctx, "toUpperCase")?;
+  Ok(Value::from_utf16(map_well_formed(&units, |str| str.to_uppercase())))
 }
 
 // String.prototype.split
+// str.split([separator[, limit]])
 fn string_split(call_ctx: &mut CallContext, args: Vec<Value>) -> JSIResult<Value> {
-  let string = get_string(call_ctx);
-  if let Err(_) = string {
-    return Err(JSIError::new(JSIErrorType::TypeError, format!("String.prototype.split called on incompatible receiver"), 0, 0))
-  }
-  let str = string.unwrap();
-
-  // 如果没有分隔符，返回包含原字符串的单元素数组
-  if args.len() == 0 {
-    let arr = create_array(call_ctx.ctx, 0);
-    if let Value::Array(arr_obj) = &arr {
-      let mut arr_mut = arr_obj.borrow_mut();
-      arr_mut.define_property(String::from("0"), Property { enumerable: true, value: Value::String(str) });
-      arr_mut.define_property(String::from("length"), Property { enumerable: false, value: Value::Number(1f64) });
-    }
-    return Ok(arr);
-  }
-
-  let separator = &args[0];
-
-  // 如果分隔符是 undefined，返回包含原字符串的单元素数组
-  if let Value::Undefined = separator {
-    let arr = create_array(call_ctx.ctx, 0);
-    if let Value::Array(arr_obj) = &arr {
-      let mut arr_mut = arr_obj.borrow_mut();
-      arr_mut.define_property(String::from("0"), Property { enumerable: true, value: Value::String(str) });
-      arr_mut.define_property(String::from("length"), Property { enumerable: false, value: Value::Number(1f64) });
-    }
-    return Ok(arr);
-  }
-
-  // 如果分隔符是空字符串，将每个字符作为数组元素
-  let sep_str = separator.to_string(call_ctx.ctx);
-  if sep_str.is_empty() {
-    let arr = create_array(call_ctx.ctx, 0);
-    if let Value::Array(arr_obj) = &arr {
-      let mut arr_mut = arr_obj.borrow_mut();
-      let chars: Vec<char> = str.chars().collect();
-      for (i, ch) in chars.iter().enumerate() {
-        arr_mut.define_property(i.to_string(), Property { enumerable: true, value: Value::String(ch.to_string()) });
+  let units = get_this_string(call_ctx, "split")?;
+  // limit 转换为 Uint32
+  let limit = match args.get(1) {
+    None | Some(Value::Undefined) => u32::MAX as usize,
+    Some(limit) => {
+      let limit = limit.to_integer_or_infinity(call_ctx.ctx)?;
+      if limit.is_finite() { limit.rem_euclid(4294967296f64) as usize } else { 0 }
+    },
+  };
+  let mut parts: Vec<Value> = vec![];
+  match args.first() {
+    _ if limit == 0 => {},
+    // 如果没有分隔符，返回包含原字符串的单元素数组
+    None | Some(Value::Undefined) => parts.push(Value::from_utf16(units)),
+    Some(separator) => {
+      let separator = separator.to_utf16(call_ctx.ctx);
+      if separator.is_empty() {
+        // 如果分隔符是空字符串，将每个编码单元作为数组元素
+        parts = units.iter().take(limit).map(|unit| Value::from_utf16(vec![*unit])).collect();
+      } else if units.is_empty() {
+        parts.push(Value::String(String::new()));
+      } else {
+        let mut start = 0;
+        while let Some(index) = find_units(&units, &separator, start) {
+          parts.push(Value::from_utf16(units[start..index].to_vec()));
+          if parts.len() >= limit {
+            break;
+          }
+          start = index + separator.len();
+        }
+        if parts.len() < limit {
+          parts.push(Value::from_utf16(units[start..].to_vec()));
+        }
       }
-      arr_mut.define_property(String::from("length"), Property { enumerable: false, value: Value::Number(chars.len() as f64) });
-    }
-    return Ok(arr);
+    },
   }
-
-  // 正常分割
-  let parts: Vec<&str> = str.split(&sep_str).collect();
   let arr = create_array(call_ctx.ctx, 0);
   if let Value::Array(arr_obj) = &arr {
     let mut arr_mut = arr_obj.borrow_mut();
-    for (i, part) in parts.iter().enumerate() {
-      arr_mut.define_property(i.to_string(), Property { enumerable: true, value: Value::String(part.to_string()) });
+    let length = parts.len();
+    for (i, part) in parts.into_iter().enumerate() {
+      arr_mut.define_property(i.to_string(), Property { enumerable: true, value: part });
     }
-    arr_mut.define_property(String::from("length"), Property { enumerable: false, value: Value::Number(parts.len() as f64) });
+    arr_mut.define_property(String::from("length"), Property { enumerable: false, value: Value::Number(length as f64) });
   }
   Ok(arr)
 }
 
-// String.prototype.lastIndexOf
-fn string_last_index_of(call_ctx: &mut CallContext, args: Vec<Value>) -> JSIResult<Value> {
-  let string = get_string(call_ctx);
-  if let Err(_) = string {
-    return Err(JSIError::new(JSIErrorType::TypeError, format!("String.prototype.lastIndexOf called on incompatible receiver"), 0, 0))
-  }
-  let str = string.unwrap();
-
-  let search_str = if args.len() > 0 {
-    args[0].to_string(call_ctx.ctx)
+// String.prototype.substring
+fn string_substring(call_ctx: &mut CallContext, args: Vec<Value>) -> JSIResult<Value> {
+  let units = get_this_string(call_ctx, "substring")?;
+  let start = get_clamped_position(call_ctx.ctx, args.first(), 0, units.len())?;
+  let end = get_clamped_position(call_ctx.ctx, args.get(1), units.len(), units.len())?;
+  // substring 自动调整 start 和 end 的顺序
+  let (final_start, final_end) = if start > end {
+    (end, start)
   } else {
-    String::from("undefined")
+    (start, end)
   };
+  Ok(Value::from_utf16(units[final_start..final_end].to_vec()))
+}
 
-  let mut position: usize = str.len();
-  if args.len() > 1 {
-    if let Some(pos) = args[1].to_number(call_ctx.ctx) {
-      position = if pos < 0f64 { 0 } else { pos as usize };
-      if position > str.len() {
-        position = str.len();
-      }
-    }
+// String.prototype.concat
+fn string_concat(call_ctx: &mut CallContext, args: Vec<Value>) -> JSIResult<Value> {
+  let mut units = get_this_string(call_ctx, "concat")?;
+  for arg in args.iter() {
+    let arg_units = arg.to_utf16(call_ctx.ctx);
+    call_ctx.ctx.check_allocation((units.len() + arg_units.len()) * 2)?;
+    units.extend(arg_units);
   }
+  Ok(Value::from_utf16(units))
+}
 
-  // 从 position 开始反向搜索
-  let search_from = &str[..position];
-  let result = search_from.rfind(&search_str);
-  match result {
-    Some(index) => Ok(Value::Number(index as f64)),
-    None => Ok(Value::Number(-1f64)),
+// 按照 UTF-16 编码单元填充字符串，at_start 为 true 时填充在开头
+fn pad_string(call_ctx: &mut CallContext, args: Vec<Value>, method: &str, at_start: bool) -> JSIResult<Value> {
+  let units = get_this_string(call_ctx, method)?;
+  let max_length = args.first().unwrap_or(&Value::Undefined).to_integer_or_infinity(call_ctx.ctx)?;
+  if max_length <= units.len() as f64 {
+    return Ok(Value::from_utf16(units))
   }
+  let filler: Vec<u16> = match args.get(1) {
+    None | Some(Value::Undefined) => vec![0x20],
+    Some(filler) => filler.to_utf16(call_ctx.ctx),
+  };
+  if filler.is_empty() {
+    return Ok(Value::from_utf16(units))
+  }
+  // 字符串的最大长度为 2^30 - 25
+  if max_length > 1073741799f64 {
+    return Err(JSIError::new(JSIErrorType::RangeError, String::from("Invalid string length"), 0, 0))
+  }
+  call_ctx.ctx.check_allocation(max_length as usize * 2)?;
+  let fill_len = max_length as usize - units.len();
+  let mut padding: Vec<u16> = vec![];
+  for (index, unit) in filler.iter().cycle().take(fill_len).enumerate() {
+    call_ctx.ctx.check_limits_in_loop(index)?;
+    padding.push(*unit);
+  }
+  let result = if at_start { [padding, units].concat() } else { [units, padding].concat() };
+  Ok(Value::from_utf16(result))
 }
 
-// String.prototype.substring
-fn string_substring(call_ctx: &mut CallContext, args: Vec<Value>) -> JSIResult<Value> {
-  let string = get_string(call_ctx);
-  if let Err(_) = string {
-    return Err(JSIError::new(JSIErrorType::TypeError, format!("String.prototype.substring called on incompatible receiver"), 0, 0))
+// String.prototype.padStart
+// str.padStart(targetLength[, padString])
+fn string_pad_start(call_ctx: &mut CallContext, args: Vec<Value>) -> JSIResult<Value> {
+  pad_string(call_ctx, args, "padStart", true)
+}
+
+// String.prototype.padEnd
+// str.padEnd(targetLength[, padString])
+fn string_pad_end(call_ctx: &mut CallContext, args: Vec<Value>) -> JSIResult<Value> {
+  pad_string(call_ctx, args, "padEnd", false)
+}
+
+// String.prototype.repeat
+// str.repeat(count)
+fn string_repeat(call_ctx: &mut CallContext, args: Vec<Value>) -> JSIResult<Value> {
+  let units = get_this_string(call_ctx, "repeat")?;
+  let count = args.first().unwrap_or(&Value::Undefined).to_integer_or_infinity(call_ctx.ctx)?;
+  if count < 0f64 || count.is_infinite() {
+    return Err(JSIError::new(JSIErrorType::RangeError, format!("Invalid count value: {}", count), 0, 0))
+  }
+  if units.is_empty() || count == 0f64 {
+    return Ok(Value::String(String::new()))
+  }
+  // 字符串的最大长度为 2^30 - 25
+  if (units.len() as f64) * count > 1073741799f64 {
+    return Err(JSIError::new(JSIErrorType::RangeError, String::from("Invalid string length"), 0, 0))
+  }
+  call_ctx.ctx.check_allocation(units.len() * count as usize * 2)?;
+  let mut result: Vec<u16> = vec![];
+  for index in 0..count as usize {
+    call_ctx.ctx.check_limits_in_loop(index)?;
+    result.extend_from_slice(&units);
+  }
+  Ok(Value::from_utf16(result))
+}
+
+// String.prototype.at
+// str.at(index)
+fn string_at(call_ctx: &mut CallContext, args: Vec<Value>) -> JSIResult<Value> {
+  let units = get_this_string(call_ctx, "at")?;
+  let relative = args.first().unwrap_or(&Value::Undefined).to_integer_or_infinity(call_ctx.ctx)?;
+  let index = if relative >= 0f64 { relative } else { units.len() as f64 + relative };
+  if index < 0f64 || index >= units.len() as f64 {
+    return Ok(Value::Undefined)
   }
-  let str = string.unwrap();
-  let len = str.len();
+  Ok(Value::from_utf16(vec![units[index as usize]]))
+}
 
-  let mut start: usize = 0;
-  if args.len() > 0 {
-    if let Some(pos) = args[0].to_number(call_ctx.ctx) {
-      let pos_int = pos as i32;
-      if pos_int < 0 {
-        start = 0;
-      } else if pos_int as usize > len {
-        start = len;
-      } else {
-        start = pos_int as usize;
-      }
-    }
+// String.prototype.charCodeAt
+// str.charCodeAt(index)
+fn string_char_code_at(call_ctx: &mut CallContext, args: Vec<Value>) -> JSIResult<Value> {
+  let units = get_this_string(call_ctx, "charCodeAt")?;
+  let index = args.first().unwrap_or(&Value::Undefined).to_integer_or_infinity(call_ctx.ctx)?;
+  if index < 0f64 || index >= units.len() as f64 {
+    return Ok(Value::NAN)
   }
+  Ok(Value::Number(units[index as usize] as f64))
+}
 
-  let mut end: usize = len;
-  if args.len() > 1 {
-    if let Some(pos) = args[1].to_number(call_ctx.ctx) {
-      let pos_int = pos as i32;
-      if pos_int < 0 {
-        end = 0;
-      } else if pos_int as usize > len {
-        end = len;
-      } else {
-        end = pos_int as usize;
-      }
+// String.prototype.codePointAt
+// str.codePointAt(index)
+fn string_code_point_at(call_ctx: &mut CallContext, args: Vec<Value>) -> JSIResult<Value> {
+  let units = get_this_string(call_ctx, "codePointAt")?;
+  let index = args.first().unwrap_or(&Value::Undefined).to_integer_or_infinity(call_ctx.ctx)?;
+  if index < 0f64 || index >= units.len() as f64 {
+    return Ok(Value::Undefined)
+  }
+  let index = index as usize;
+  let first = units[index];
+  // 高代理项后面紧跟低代理项时，合并为一个码点
+  if (0xD800..=0xDBFF).contains(&first) && index + 1 < units.len() {
+    let second = units[index + 1];
+    if (0xDC00..=0xDFFF).contains(&second) {
+      let code_point = ((first as u32 - 0xD800) << 10) + (second as u32 - 0xDC00) + 0x10000;
+      return Ok(Value::Number(code_point as f64))
     }
   }
+  Ok(Value::Number(first as f64))
+}
 
-  // substring 自动调整 start 和 end 的顺序
-  let (final_start, final_end) = if start > end {
-    (end, start)
-  } else {
-    (start, end)
+// String.prototype.normalize
+// str.normalize([form])
+fn string_normalize(call_ctx: &mut CallContext, args: Vec<Value>) -> JSIResult<Value> {
+  let units = get_this_string(call_ctx, "normalize")?;
+  let form = match args.first() {
+    None | Some(Value::Undefined) => String::from("NFC"),
+    Some(form) => form.to_string(call_ctx.ctx),
+  };
+  let result = match form.as_str() {
+    "NFC" => map_well_formed(&units, |str| str.nfc().collect()),
+    "NFD" => map_well_formed(&units, |str| str.nfd().collect()),
+    "NFKC" => map_well_formed(&units, |str| str.nfkc().collect()),
+    "NFKD" => map_well_formed(&units, |str| str.nfkd().collect()),
+    _ => return Err(JSIError::new(JSIErrorType::RangeError, String::from("The normalization form should be one of NFC, NFD, NFKC, NFKD."), 0, 0)),
   };
+  Ok(Value::from_utf16(result))
+}
 
-  Ok(Value::String(str[final_start..final_end].to_string()))
+// String.prototype.localeCompare
+// 暂不支持 locales 和 options，按照规范化之后的码点顺序比较
+fn string_locale_compare(call_ctx: &mut CallContext, args: Vec<Value>) -> JSIResult<Value> {
+  let units = get_this_string(call_ctx, "localeCompare")?;
+  let that = get_search_string(call_ctx.ctx, &args);
+  let left = map_well_formed(&units, |str| str.nfc().collect());
+  let right = map_well_formed(&that, |str| str.nfc().collect());
+  let left: Vec<u32> = char::decode_utf16(left).map(|item| item.map_or_else(|err| err.unpaired_surrogate() as u32, |ch| ch as u32)).collect();
+  let right: Vec<u32> = char::decode_utf16(right).map(|item| item.map_or_else(|err| err.unpaired_surrogate() as u32, |ch| ch as u32)).collect();
+  let result = match left.cmp(&right) {
+    std::cmp::Ordering::Less => -1f64,
+    std::cmp::Ordering::Equal => 0f64,
+    std::cmp::Ordering::Greater => 1f64,
+  };
+  Ok(Value::Number(result))
 }
 
-// String.prototype.concat
-fn string_concat(call_ctx: &mut CallContext, args: Vec<Value>) -> JSIResult<Value> {
-  let string = get_string(call_ctx);
-  if let Err(_) = string {
-    return Err(JSIError::new(JSIErrorType::TypeError, format!("String.prototype.concat called on incompatible receiver"), 0, 0))
+// String.prototype.substr
+// str.substr(start[, length])
+fn string_substr(call_ctx: &mut CallContext, args: Vec<Value>) -> JSIResult<Value> {
+  let units = get_this_string(call_ctx, "substr")?;
+  let start = get_relative_position(call_ctx.ctx, args.first(), 0, units.len())?;
+  let length = match args.get(1) {
+    None | Some(Value::Undefined) => units.len() as f64,
+    Some(length) => length.to_integer_or_infinity(call_ctx.ctx)?,
+  };
+  let end = (start as f64 + length).min(units.len() as f64);
+  if start as f64 >= end {
+    return Ok(Value::String(String::new()))
+  }
+  Ok(Value::from_utf16(units[start..end as usize].to_vec()))
+}
+
+// String.fromCharCode
+// String.fromCharCode(num1[, ...[, numN]])
+fn string_static_from_char_code(call_ctx: &mut CallContext, args: Vec<Value>) -> JSIResult<Value> {
+  let mut units: Vec<u16> = Vec::with_capacity(args.len());
+  for arg in args.iter() {
+    // ToUint16
+    let number = arg.to_integer_or_infinity(call_ctx.ctx)?;
+    let unit = if number.is_finite() { number.rem_euclid(65536f64) as u16 } else { 0 };
+    units.push(unit);
   }
-  let str = string.unwrap();
+  Ok(Value::from_utf16(units))
+}
 
-  let mut result = str;
+// String.fromCodePoint
+// String.fromCodePoint(num1[, ...[, numN]])
+fn string_static_from_code_point(call_ctx: &mut CallContext, args: Vec<Value>) -> JSIResult<Value> {
+  let mut units: Vec<u16> = Vec::with_capacity(args.len());
   for arg in args.iter() {
-    result.push_str(&arg.to_string(call_ctx.ctx));
+    let number = arg.to_number(call_ctx.ctx).unwrap_or(f64::NAN);
+    if number.is_nan() || number.trunc() != number || !(0f64..=1114111f64).contains(&number) {
+      return Err(JSIError::new(JSIErrorType::RangeError, format!("Invalid code point {}", arg.to_string(call_ctx.ctx)), 0, 0))
+    }
+    let code_point = number as u32;
+    if code_point < 0x10000 {
+      units.push(code_point as u16);
+    } else {
+      let offset = code_point - 0x10000;
+      units.push((0xD800 + (offset >> 10)) as u16);
+      units.push((0xDC00 + (offset & 0x3FF)) as u16);
+    }
   }
+  Ok(Value::from_utf16(units))
+}
 
-  Ok(Value::String(result))
-}
\ No newline at end of file
+// String.raw
+// String.raw(strings, ...substitutions)
+fn string_static_raw(call_ctx: &mut CallContext, args: Vec<Value>) -> JSIResult<Value> {
+  let template = args.first().cloned().unwrap_or(Value::Undefined);
+  if template.is_not_strict_null() {
+    return Err(JSIError::new(JSIErrorType::TypeError, String::from("Cannot convert undefined or null to object"), 0, 0))
+  }
+  let raw = template.to_object(call_ctx.ctx).borrow().get_value(String::from("raw"));
+  if raw.is_not_strict_null() {
+    return Err(JSIError::new(JSIErrorType::TypeError, String::from("Cannot convert undefined or null to object"), 0, 0))
+  }
+  let raw = raw.to_object(call_ctx.ctx);
+  let length = raw.borrow().get_value(String::from("length")).to_integer_or_infinity(call_ctx.ctx)?;
+  if length <= 0f64 {
+    return Ok(Value::String(String::new()))
+  }
+  let mut units: Vec<u16> = vec![];
+  let mut index = 0f64;
+  loop {
+    let segment = raw.borrow().get_value(index.to_string());
+    units.extend(segment.to_utf16(call_ctx.ctx));
+    if index + 1f64 >= length {
+      break;
+    }
+    if let Some(substitution) = args.get(index as usize + 1) {
+      units.extend(substitution.to_utf16(call_ctx.ctx));
+    }
+    index += 1f64;
+  }
+  Ok(Value::from_utf16(units))
+}
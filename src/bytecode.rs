@@ -41,6 +41,8 @@ pub enum EByteCodeop {
     OpGetFunc,
     // 函数调用，弹出 n 个值作为参数、弹出一个值作为 function，进行执行，结果入栈
     OpCall,
+    // new 调用，弹出 n 个值作为参数、弹出一个值作为构造函数，创建的实例入栈
+    OpNew,
     OpReturn,
     // 标签
     OpLabel,
@@ -101,6 +103,7 @@ impl fmt::Display for EByteCodeop {
             EByteCodeop::OpMul => write!(f, "Mul"),
             EByteCodeop::OpDiv => write!(f, "Div"),
             EByteCodeop::OpCall => write!(f, "Call"),
+            EByteCodeop::OpNew => write!(f, "New"),
             _ => write!(f, "Unknown"),
         }
     }
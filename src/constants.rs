@@ -8,11 +8,29 @@ pub const GLOBAL_FUNCTION_NAME: &str = "Function";
 
 // more
 pub const GLOBAL_PROMISE_NAME: &str = "Promise";
+pub const GLOBAL_MAP_NAME: &str = "Map";
 
 pub const GLOBAL_ERROR_NAME: &str = "Error";
 pub const GLOBAL_TYPE_ERROR_NAME: &str = "TypeError";
+pub const GLOBAL_RANGE_ERROR_NAME: &str = "RangeError";
+pub const GLOBAL_SYNTAX_ERROR_NAME: &str = "SyntaxError";
+pub const GLOBAL_REFERENCE_ERROR_NAME: &str = "ReferenceError";
+pub const GLOBAL_EVAL_ERROR_NAME: &str = "EvalError";
+pub const GLOBAL_URI_ERROR_NAME: &str = "URIError";
+pub const GLOBAL_AGGREGATE_ERROR_NAME: &str = "AggregateError";
 
-pub const GLOBAL_OBJECT_NAME_LIST: [&str;9] = [
+// Error 的子类，原型链指向 Error.prototype
+pub const GLOBAL_NATIVE_ERROR_NAME_LIST: [&str;7] = [
+    GLOBAL_TYPE_ERROR_NAME,
+    GLOBAL_RANGE_ERROR_NAME,
+    GLOBAL_SYNTAX_ERROR_NAME,
+    GLOBAL_REFERENCE_ERROR_NAME,
+    GLOBAL_EVAL_ERROR_NAME,
+    GLOBAL_URI_ERROR_NAME,
+    GLOBAL_AGGREGATE_ERROR_NAME,
+];
+
+pub const GLOBAL_OBJECT_NAME_LIST: [&str;16] = [
     GLOBAL_NUMBER_NAME,
     GLOBAL_STRING_NAME,
     GLOBAL_BOOLEAN_NAME,
@@ -21,9 +39,16 @@ pub const GLOBAL_OBJECT_NAME_LIST: [&str;9] = [
     GLOBAL_ARRAY_NAME,
     GLOBAL_FUNCTION_NAME,
     GLOBAL_PROMISE_NAME,
+    GLOBAL_MAP_NAME,
     // Error
     GLOBAL_ERROR_NAME,
     GLOBAL_TYPE_ERROR_NAME,
+    GLOBAL_RANGE_ERROR_NAME,
+    GLOBAL_SYNTAX_ERROR_NAME,
+    GLOBAL_REFERENCE_ERROR_NAME,
+    GLOBAL_EVAL_ERROR_NAME,
+    GLOBAL_URI_ERROR_NAME,
+    GLOBAL_AGGREGATE_ERROR_NAME,
 ];
 
 pub const PROTO_PROPERTY_NAME: &str = "[[Property]]";
\ No newline at end of file
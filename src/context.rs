@@ -1,9 +1,13 @@
-use std::{cell::RefCell, collections::HashMap, rc::{Rc, Weak}};
+use std::{cell::RefCell, collections::HashMap, rc::{Rc, Weak}, sync::{atomic::{AtomicBool, Ordering}, Arc}, time::Instant};
 
-use crate::{ast::Program, ast_node::{ArrayLiteral, AssignExpression, BinaryExpression, CallContext, CallExpression, ClassType, Declaration, Expression, ForStatement, ForInStatement, ForOfStatement, IdentifierLiteral, Keywords, NewExpression, ObjectLiteral, PostfixUnaryExpression, PrefixUnaryExpression, Statement, SwitchStatement, VariableFlag}, ast_token::Token, builtins::{array::create_array, console::create_console, function::{builtin_function, create_function, create_function_with_bytecode, get_builtin_function_name, get_function_this}, global::{bind_global, get_global_object, new_global_this, IS_GLOABL_OBJECT}, object::{create_object, Object, Property}, promise::create_promise_helper}, bytecode::{self, ByteCode, EByteCodeop}, constants::{GLOBAL_OBJECT_NAME_LIST, PROTO_PROPERTY_NAME}, error::{JSIError, JSIErrorType, JSIResult}, scope::{get_value_and_scope, get_value_info_and_scope, Scope}, value::{CallStatementOptions, Value, ValueInfo}};
+use crate::{ast::Program, ast_node::{Span, ArrayLiteral, AssignExpression, BinaryExpression, CallContext, CallExpression, ClassType, Declaration, Expression, ForStatement, ForInStatement, ForOfStatement, HostFunction, IdentifierLiteral, Keywords, NewExpression, ObjectLiteral, PostfixUnaryExpression, PrefixUnaryExpression, Statement, SwitchStatement, VariableFlag}, ast_token::{get_token_description, Token}, builtins::{array::{create_array, get_iterable_values, is_array_iterator}, map::is_map, string::get_string_own_property, console::{create_console, ConsoleState}, function::{builtin_function, create_function, create_function_with_bytecode, get_builtin_function_name, get_function_this}, global::{bind_global, get_global_object, new_global_this, IS_GLOABL_OBJECT}, object::{create_object, Accessor, Object, ObjectIds, Property}, promise::create_promise_helper}, bytecode::{self, ByteCode, EByteCodeop}, constants::{GLOBAL_OBJECT_NAME_LIST, PROTO_PROPERTY_NAME}, error::{JSIError, JSIErrorType, JSIResult, StackFrame}, scope::{get_value_and_scope, get_value_info_and_scope, Scope}, value::{CallStatementOptions, Value, ValueInfo}};
 
 
-use super::ast::AST;
+use super::ast::{AST, DEFAULT_MAX_NESTING_DEPTH};
+use super::ast_utils::stack_address;
+use super::builder::SandboxConfig;
+use super::heap::{Heap, HeapAllocation, SCOPE_SIZE};
+use super::realm::{Realm, RealmId};
 
 // 循环中断处理结果
 #[derive(Debug, Clone, Copy, PartialEq)]
@@ -123,28 +127,195 @@ pub struct Context {
   pub cur_scope: Rc<RefCell<Scope>>,
   // 调用栈
   stack: Vec<ValueInfo>,
+  // 函数调用栈，记录函数名和当前执行到的位置，用于生成错误的 stack
+  call_stack: Vec<StackFrame>,
   // label index map
   label_index_map: HashMap<String, usize>,
+  // 执行限制
+  limits: ExecutionLimits,
+  // 最大调用深度，超过时抛出 RangeError，避免原生栈溢出
+  max_call_depth: usize,
+  // 解析时语句、表达式的最大嵌套深度
+  max_nesting_depth: usize,
+  // 每次执行允许使用的原生栈空间（字节），以及据此计算出的最低栈地址
+  max_stack_size: usize,
+  stack_limit: usize,
+  // 堆内存统计
+  pub heap: Rc<Heap>,
+  // 内置对象，从全局移除或替换后内部仍然使用原来的对象
+  intrinsics: Rc<HashMap<String, Rc<RefCell<Object>>>>,
+  // 所有的领域，global、scope、intrinsics 是当前领域的
+  realms: Vec<Realm>,
+  cur_realm: RealmId,
+  // 对象 id 分配，所有领域共用，和堆内存统计分开
+  pub object_ids: ObjectIds,
+  // 是否允许通过字符串生成代码，如 Function 构造方法
+  pub code_generation: bool,
+  // JSIBuilder 设置的沙箱配置
+  pub(crate) sandbox: SandboxConfig,
+  // console 的输出目标和计时、计数等状态
+  pub console: ConsoleState,
+}
+
+const OUT_OF_MEMORY_MESSAGE: &str = "Out of memory";
+
+// 内置方法的循环中每隔多少次检查一次执行限制
+const LOOP_LIMIT_CHECK_INTERVAL: usize = 256;
+
+// 默认的最大调用深度，release 构建下每层 JS 调用约占用 6KB 栈空间
+pub const DEFAULT_MAX_CALL_DEPTH: usize = 256;
+
+// 默认允许使用的原生栈空间，debug 构建下每层 JS 调用约占用 53KB，每层解析嵌套约 70KB
+// 检查点之间最多使用数百 KB，留出余量后可以在 2MB 栈的线程（如测试线程）中安全执行
+pub const DEFAULT_MAX_STACK_SIZE: usize = 1024 * 1024;
+
+// 中断句柄，可以在其他线程中中断正在执行的脚本
+#[derive(Clone, Debug)]
+pub struct InterruptHandle(Arc<AtomicBool>);
+
+impl InterruptHandle {
+  pub fn interrupt(&self) {
+    self.0.store(true, Ordering::SeqCst);
+  }
+}
+
+#[derive(Debug, Default)]
+struct ExecutionLimits {
+  // 每次执行允许的操作数，语句和字节码指令各计一次
+  operation_budget: Option<u64>,
+  operation_count: u64,
+  deadline: Option<Instant>,
+  interrupt: Arc<AtomicBool>,
+  // 超出限制后的错误信息，之后的每次检查都会失败，保证脚本无法继续执行
+  terminated: Option<String>,
 }
 
 impl Context {
     pub fn new() -> Context {
       let scope = Rc::new(RefCell::new(Scope::new()));
       let cur_scope = Rc::clone(&scope);
-      let global = new_global_this();
+      let object_ids = ObjectIds::default();
+      let global = new_global_this(&object_ids);
       let mut ctx = Context {
         global,
         strict: true,
         scope,
         cur_scope,
         stack: vec![],
+        call_stack: vec![],
         label_index_map: HashMap::new(),
+        limits: ExecutionLimits::default(),
+        max_call_depth: DEFAULT_MAX_CALL_DEPTH,
+        max_nesting_depth: DEFAULT_MAX_NESTING_DEPTH,
+        max_stack_size: DEFAULT_MAX_STACK_SIZE,
+        stack_limit: 0,
+        heap: Rc::new(Heap::default()),
+        intrinsics: Rc::new(HashMap::new()),
+        realms: vec![],
+        cur_realm: RealmId(0),
+        object_ids,
+        code_generation: true,
+        sandbox: SandboxConfig::default(),
+        console: ConsoleState::default(),
       };
-      bind_global(&mut ctx);
-      ctx.init();
+      ctx.init_realm();
       return ctx;
     }
 
+    // 在当前的全局对象和全局作用域上安装内置对象，并登记为新的领域
+    fn init_realm(&mut self) -> RealmId {
+      // 全局变量持有的字符串同样计入堆内存
+      self.scope.borrow_mut().heap_allocation = Some(HeapAllocation::new(&self.heap, SCOPE_SIZE));
+      self.heap.track_scope(&self.scope);
+      bind_global(self);
+      self.init();
+      self.intrinsics = Rc::new(self.global.borrow().property.iter().filter_map(|(name, property)| {
+        property.value.to_weak_rc_object().and_then(|object| object.upgrade()).map(|object| (name.clone(), object))
+      }).collect());
+      self.realms.push(Realm {
+        global: Rc::clone(&self.global),
+        scope: Rc::clone(&self.scope),
+        intrinsics: Rc::clone(&self.intrinsics),
+      });
+      self.apply_sandbox();
+      RealmId(self.realms.len() - 1)
+    }
+
+    // 在当前领域上应用沙箱配置：移除全局变量、冻结内置对象、安装宿主提供的全局变量
+    pub(crate) fn apply_sandbox(&mut self) {
+      let omitted = self.sandbox.omitted.clone();
+      for name in omitted.iter() {
+        self.remove_global_value(name);
+      }
+      if self.sandbox.freeze_intrinsics {
+        self.freeze_intrinsics();
+      }
+      let globals = self.sandbox.globals.clone();
+      for (name, value) in globals.into_iter() {
+        self.define_global_value(name, value);
+      }
+    }
+
+    // 创建新的领域，拥有独立的全局对象和内置对象，创建后仍然停留在当前领域
+    pub fn create_realm(&mut self) -> RealmId {
+      let previous = self.cur_realm;
+      self.global = new_global_this(&self.object_ids);
+      self.scope = Rc::new(RefCell::new(Scope::new()));
+      self.intrinsics = Rc::new(HashMap::new());
+      let realm = self.init_realm();
+      self.enter_realm(previous);
+      realm
+    }
+
+    pub fn current_realm(&self) -> RealmId {
+      self.cur_realm
+    }
+
+    // 切换当前领域，返回切换前的领域
+    pub fn enter_realm(&mut self, realm: RealmId) -> RealmId {
+      let previous = self.cur_realm;
+      let record = self.realms[realm.0].clone();
+      self.global = record.global;
+      self.scope = record.scope;
+      self.intrinsics = record.intrinsics;
+      self.cur_realm = realm;
+      previous
+    }
+
+    // 在指定的领域中执行代码，执行完成后回到当前领域
+    pub fn run_in_realm(&mut self, realm: RealmId, code: String) -> JSIResult<Value> {
+      let previous = self.enter_realm(realm);
+      let outer = std::mem::replace(&mut self.cur_scope, Rc::clone(&self.scope));
+      let result = self.run(code);
+      self.cur_scope = outer;
+      self.enter_realm(previous);
+      result
+    }
+
+    // 进入函数定义时所在的领域，函数中创建的对象使用该领域的内置对象
+    fn enter_scope_realm(&mut self, scope: &Option<Rc<RefCell<Scope>>>) -> RealmId {
+      let previous = self.cur_realm;
+      if self.realms.len() > 1 {
+        if let Some(scope) = scope {
+          let mut root = Rc::clone(scope);
+          loop {
+            let parent = root.borrow().parent.clone();
+            match parent {
+              Some(parent) => root = parent,
+              None => break,
+            }
+          }
+          let realm = self.realms.iter().position(|realm| Rc::ptr_eq(&realm.scope, &root));
+          if let Some(realm) = realm {
+            if realm != previous.0 {
+              self.enter_realm(RealmId(realm));
+            }
+          }
+        }
+      }
+      previous
+    }
+
     pub fn set_strict(&mut self,strict: bool) {
       self.strict = strict;
     }
@@ -158,25 +329,245 @@ impl Context {
       }
     }
     
+    pub fn set_operation_budget(&mut self, budget: Option<u64>) {
+      self.limits.operation_budget = budget;
+    }
+
+    pub fn set_deadline(&mut self, deadline: Option<Instant>) {
+      self.limits.deadline = deadline;
+    }
+
+    pub fn get_interrupt_handle(&self) -> InterruptHandle {
+      InterruptHandle(Arc::clone(&self.limits.interrupt))
+    }
+
+    pub fn set_max_call_depth(&mut self, depth: usize) {
+      self.max_call_depth = depth;
+    }
+
+    pub fn set_max_nesting_depth(&mut self, depth: usize) {
+      self.max_nesting_depth = depth;
+    }
+
+    // 允许使用的原生栈空间，从 run、call 等入口处开始计算
+    pub fn set_max_stack_size(&mut self, size: usize) {
+      self.max_stack_size = size;
+    }
+
+    // 检查调用深度，在进入 JS 函数前调用
+    fn check_call_depth(&self) -> JSIResult<()> {
+      if self.call_stack.len() >= self.max_call_depth {
+        return Err(JSIError::new(JSIErrorType::RangeError, String::from("Maximum call stack size exceeded"), 0, 0));
+      }
+      self.check_stack()
+    }
+
+    // 检查剩余的原生栈空间，在递归执行语句、表达式时调用
+    pub(crate) fn check_stack(&self) -> JSIResult<()> {
+      if stack_address() < self.stack_limit {
+        return Err(JSIError::new(JSIErrorType::RangeError, String::from("Maximum call stack size exceeded"), 0, 0));
+      }
+      Ok(())
+    }
+
+    // 当前执行的最低栈地址，不在执行中时从当前位置重新计算
+    fn current_stack_limit(&self) -> usize {
+      if self.call_stack.is_empty() {
+        return stack_address().saturating_sub(self.max_stack_size);
+      }
+      self.stack_limit
+    }
+
+    // 开始新的一次执行，重置操作计数，嵌套执行时不重置
+    pub fn begin_execution(&mut self) {
+      if self.call_stack.is_empty() {
+        self.stack_limit = self.current_stack_limit();
+        self.limits.operation_count = 0;
+        self.limits.terminated = None;
+        self.heap.reset_exceeded();
+        // 上一次执行被终止时可能停留在内层作用域
+        self.cur_scope = Rc::clone(&self.scope);
+      }
+    }
+
+    // 执行结束时检查是否被终止，终止错误可能在 Promise 回调等位置被吞掉
+    pub fn finish_execution(&mut self, result: JSIResult<Value>) -> JSIResult<Value> {
+      if result.is_ok() && self.call_stack.is_empty() {
+        // 最后一个操作超出堆内存限制时，没有下一次检查
+        if self.limits.terminated.is_none() && self.heap.is_exceeded() {
+          self.limits.terminated = Some(String::from(OUT_OF_MEMORY_MESSAGE));
+        }
+        if let Some(message) = &self.limits.terminated {
+          return Err(JSIError::new(JSIErrorType::Terminated, message.clone(), 0, 0));
+        }
+      }
+      result
+    }
+
+    // 检查执行限制，超出限制时返回不可捕获的 Terminated 错误
+    fn check_limits(&mut self) -> JSIResult<()> {
+      if self.limits.terminated.is_none() {
+        self.limits.operation_count += 1;
+        if let Some(budget) = self.limits.operation_budget {
+          if self.limits.operation_count > budget {
+            self.limits.terminated = Some(String::from("Operation budget exceeded"));
+          }
+        }
+        if self.limits.interrupt.swap(false, Ordering::SeqCst) {
+          self.limits.terminated = Some(String::from("Execution interrupted"));
+        }
+        if self.heap.is_exceeded() {
+          self.limits.terminated = Some(String::from(OUT_OF_MEMORY_MESSAGE));
+        }
+        // 获取时间的开销较大，每 256 次操作检查一次
+        if let Some(deadline) = self.limits.deadline {
+          if self.limits.operation_count % 256 == 1 && Instant::now() >= deadline {
+            self.limits.terminated = Some(String::from("Execution deadline exceeded"));
+          }
+        }
+      }
+      if let Some(message) = &self.limits.terminated {
+        return Err(JSIError::new(JSIErrorType::Terminated, message.clone(), 0, 0));
+      }
+      Ok(())
+    }
+
+    // 内置方法中的长循环调用，宿主可以终止单个耗时的调用；堆内存超限时立即终止
+    pub(crate) fn check_limits_in_loop(&mut self, iteration: usize) -> JSIResult<()> {
+      if iteration.is_multiple_of(LOOP_LIMIT_CHECK_INTERVAL) || self.heap.is_exceeded() {
+        // 内置方法每次迭代的开销可能较大，截止时间在每个检查点都检查
+        if let Some(deadline) = self.limits.deadline {
+          if self.limits.terminated.is_none() && Instant::now() >= deadline {
+            self.limits.terminated = Some(String::from("Execution deadline exceeded"));
+          }
+        }
+        return self.check_limits()
+      }
+      Ok(())
+    }
+
+    // 创建字符串、批量创建数组元素前检查堆内存限制，超出时终止执行
+    pub(crate) fn check_allocation(&mut self, size: usize) -> JSIResult<()> {
+      if !self.heap.check_transient(size) {
+        self.limits.terminated = Some(String::from(OUT_OF_MEMORY_MESSAGE));
+        return Err(JSIError::new(JSIErrorType::Terminated, String::from(OUT_OF_MEMORY_MESSAGE), 0, 0));
+      }
+      Ok(())
+    }
+
+    // 定义全局变量，同时挂载到 globalThis 上
+    pub fn define_global_value(&mut self, name: String, value: Value) {
+      self.global.borrow_mut().define_property(name.clone(), Property {
+        enumerable: true,
+        value: value.clone(),
+      });
+      self.scope.borrow_mut().set_value(name, value, false);
+    }
+
+    // 移除全局变量，内部仍然可以通过 get_intrinsic 使用内置对象
+    pub fn remove_global_value(&mut self, name: &str) {
+      self.global.borrow_mut().delete_property(name.to_string());
+      self.scope.borrow_mut().remove_value(name);
+    }
+
+    // 获取内置对象，如 Array、Function
+    pub fn get_intrinsic(&self, name: &str) -> Option<Rc<RefCell<Object>>> {
+      self.intrinsics.get(name).cloned()
+    }
+
+    // 冻结内置对象以及它们的原型和方法，脚本不能再修改，globalThis 本身不冻结
+    pub fn freeze_intrinsics(&mut self) {
+      let mut pending: Vec<Rc<RefCell<Object>>> = self.intrinsics.values().cloned().collect();
+      if let Some(console) = self.get_global_value(String::from("console")).to_weak_rc_object().and_then(|console| console.upgrade()) {
+        pending.push(console);
+      }
+      while let Some(object) = pending.pop() {
+        let mut object_mut = object.borrow_mut();
+        if object_mut.frozen || Rc::ptr_eq(&object, &self.global) {
+          continue;
+        }
+        object_mut.frozen = true;
+        let values = object_mut.property.values().chain(object_mut.inner_property.values()).map(|property| &property.value);
+        pending.extend(values.filter_map(|value| value.to_weak_rc_object().and_then(|value| value.upgrade())));
+        if let Some(prototype) = &object_mut.prototype {
+          pending.push(Rc::clone(prototype));
+        }
+      }
+    }
+
+    // 获取全局变量，先查找全局作用域，再查找 globalThis 上的属性
+    pub fn get_global_value(&mut self, name: String) -> Value {
+      let (value, _, _) = get_value_and_scope(Rc::clone(&self.scope), name.clone());
+      if let Some(value) = value {
+        return value;
+      }
+      self.global.borrow().get_property_value(name)
+    }
+
+    // 获取当前的调用栈，栈顶的帧在前
+    pub fn get_call_stack(&self) -> Vec<StackFrame> {
+      self.call_stack.iter().rev().cloned().collect()
+    }
+
+    // 进入函数时添加一帧
+    fn push_call_frame(&mut self, function_name: String) {
+      self.call_stack.push(StackFrame { function_name, line: 0, column: 0 });
+    }
+
+    // 更新当前帧执行到的位置，没有位置信息的节点不更新
+    fn set_cur_position(&mut self, span: &Span) {
+      if span.line == 0 {
+        return;
+      }
+      if let Some(frame) = self.call_stack.last_mut() {
+        frame.line = span.line as i32;
+        frame.column = span.column as i32;
+      }
+    }
+
+    // 错误第一次离开调用帧时记录调用栈，并补充错误的行列号
+    fn capture_error_stack(&self, err: &mut JSIError) {
+      if err.error_type == JSIErrorType::Await || !err.stack.is_empty() {
+        return;
+      }
+      err.stack = self.get_call_stack();
+      if err.line == 0 {
+        if let Some(frame) = err.stack.first() {
+          err.line = frame.line;
+          err.column = frame.column;
+        }
+      }
+    }
+
     // 运行一段 JS 代码
     pub fn run(&mut self, code: String) -> JSIResult<Value> {
       let program = self.parse(code)?;
+      self.begin_execution();
       // print!("program: {:?}", program);
-      self.call(program)
+      let result = self.call(program);
+      self.finish_execution(result)
     }
 
     pub fn run_with_bytecode(&mut self, code: String) -> JSIResult<Value> {
        let program = self.parse(code)?;
         let bytecode = program.bytecode;
-
-        self.run_with_bytecode_list(0, &bytecode)?;
-
-        if self.stack.is_empty() {
-          return Ok(Value::Undefined);
+        self.begin_execution();
+
+        self.push_call_frame(String::new());
+        let result = self.run_with_bytecode_list(0, &bytecode);
+        if let Err(mut err) = result {
+          self.capture_error_stack(&mut err);
+          self.call_stack.pop();
+          return Err(err);
         }
+        self.call_stack.pop();
+
         // 返回栈顶的值
-        let result = self.stack.pop().unwrap();
-        Ok(result.value)
+        let result = match self.stack.pop() {
+          Some(result) => result.value,
+          None => Value::Undefined,
+        };
+        self.finish_execution(Ok(result))
     }
 
     pub fn run_with_bytecode_list(&mut self, cur_index: usize, bytecode: &Vec<ByteCode>) -> JSIResult<Value> {
@@ -187,6 +578,7 @@ impl Context {
         let cur_index = bytecode_index;
         let bytecode_item = &bytecode[cur_index];
         bytecode_index += 1;
+        self.check_limits()?;
         // println!("");
         // println!(">>>> cur_stack {:?} {:?} {:?}", self.stack.len(), self.stack.iter().map(|item| item.name.clone().unwrap_or(String::from(""))).collect::<Vec<String>>(), bytecode_item.op);
         // println!(">>>> cur_stack {:?} {:?}", self.stack, bytecode_item.op);
@@ -205,7 +597,12 @@ impl Context {
           },
           EByteCodeop::OpString => {
             if let Some(arg) = bytecode_item.args.get(0) {
-              self.stack.push(Value::String(arg.clone()).to_value_info());
+              // 包含单独代理项的字符串，第二个参数为逗号分隔的 UTF-16 编码单元
+              let value = match bytecode_item.args.get(1) {
+                Some(units) => Value::from_utf16(units.split(',').filter_map(|unit| unit.parse::<u16>().ok()).collect()),
+                None => Value::String(arg.clone()),
+              };
+              self.stack.push(value.to_value_info());
             } else {
               return Err(JSIError::new(JSIErrorType::SyntaxError, String::from("string arg is required"), 0, 0));
             }
@@ -261,7 +658,7 @@ impl Context {
             {
               let mut obj_mut = object.borrow_mut();
               for i in 0..property_count {
-                let key = items[i * 2 + 1].value.to_string(self);
+                let key = items[i * 2 + 1].value.to_property_key(self);
                 let value = items[i * 2].value.clone();
                 obj_mut.define_property(key, Property {
                   enumerable: true,
@@ -311,8 +708,17 @@ impl Context {
               }
 
               let left_clone = left.clone();
-              let left_obj = left.to_object(self);
-              let value = (*left_obj).borrow().get_value(property_name.clone());
+              let value = match get_string_own_property(self, &left, property_name) {
+                Some(value) => value,
+                None => {
+                  let left_obj = left.to_object(self);
+                  let accessor = (*left_obj).borrow().get_accessor(property_name);
+                  match accessor {
+                    Some(accessor) => self.get_accessor_value(accessor, left.clone())?,
+                    None => (*left_obj).borrow().get_value(property_name.clone()),
+                  }
+                },
+              };
               let property_valueinfo = ValueInfo {
                 is_const: false,
                 value,
@@ -338,6 +744,11 @@ impl Context {
           },
           EByteCodeop::OpCall => {
             // 函数调用
+            if bytecode_item.line > 0 {
+              if let Some(frame) = self.call_stack.last_mut() {
+                frame.line = bytecode_item.line as i32;
+              }
+            }
             let mut args = vec![];
             // 从栈中弹出参数
             let arg_count = bytecode_item.args.get(0).unwrap().parse::<usize>().unwrap_or(0);
@@ -354,7 +765,7 @@ impl Context {
                     if let Some(call_ref) = &callee.reference {
                       reference = call_ref.to_weak_rc_object();
                     }
-                    let _ = self.call_function_with_bytecode(function_object.to_owned(), callee.reference, reference, args);
+                    self.call_function_with_bytecode(function_object.to_owned(), callee.reference, reference, args)?;
                   },
                   _ => {}
                 }
@@ -363,6 +774,21 @@ impl Context {
             }
             
           },
+          EByteCodeop::OpNew => {
+            if bytecode_item.line > 0 {
+              if let Some(frame) = self.call_stack.last_mut() {
+                frame.line = bytecode_item.line as i32;
+              }
+            }
+            let arg_count = bytecode_item.args.first().and_then(|count| count.parse::<usize>().ok()).unwrap_or(0);
+            if arg_count >= self.stack.len() {
+              return Err(JSIError::new(JSIErrorType::SyntaxError, String::from("new args not enough"), 0, 0));
+            }
+            let args: Vec<Value> = self.stack.drain(self.stack.len() - arg_count..).map(|arg| arg.value).collect();
+            let constructor = self.stack.pop().unwrap();
+            let instance = self.construct_with_name(&constructor.value, args, &constructor.access_path)?;
+            self.stack.push(instance.to_value_info());
+          },
           EByteCodeop::OpGetArg => {
             // OpCall 的时候 arg 是按照从左到右顺序插入的
             let arg = self.cur_scope.borrow_mut().function_call_args.remove(0);
@@ -372,7 +798,7 @@ impl Context {
             // 从栈中弹出一个值作为返回值
             if self.stack.len() > 0 {
               // 中断执行
-              return Ok(Value::Interrupt(Token::Return, Expression::Unknown));
+              return Ok(Value::Interrupt(Token::Return, Box::new(Expression::Unknown)));
             } else {
               return Err(JSIError::new(JSIErrorType::SyntaxError, String::from("return value is required"), 0, 0));
             }
@@ -491,9 +917,10 @@ impl Context {
             };
             // 执行二元运算
             let result = self.execute_binary_expression(&BinaryExpression {
-              left: Box::new(Expression::Value(Box::new(left.value))),
-              right: Box::new(Expression::Value(Box::new(right.value))),
+              left: Box::new(Expression::Value(Box::new(left.value), Span::default())),
+              right: Box::new(Expression::Value(Box::new(right.value), Span::default())),
               operator,
+              span: Span::default(),
             })?;
             self.stack.push(result.to_value_info());
           },
@@ -535,9 +962,20 @@ impl Context {
     pub fn parse(&mut self, code: String) -> JSIResult<Program> {
       let mut ast = AST::new(code);
       ast.set_strict(self.strict);
+      ast.set_max_nesting_depth(self.max_nesting_depth);
+      ast.set_stack_limit(self.current_stack_limit());
       ast.parse()
     }
 
+    // 解析代码，遇到语法错误时继续解析，返回部分 Program 和所有的语法错误
+    pub fn parse_with_recovery(&mut self, code: String) -> (Program, Vec<JSIError>) {
+      let mut ast = AST::new(code);
+      ast.set_strict(self.strict);
+      ast.set_max_nesting_depth(self.max_nesting_depth);
+      ast.set_stack_limit(self.current_stack_limit());
+      ast.parse_with_recovery()
+    }
+
 
     pub fn run_function_def_bytecode(&mut self, level:usize, next_bytecode_index: usize, cur_bytecode: &ByteCode, bytecode_list: &Vec<ByteCode>) -> usize {
       let mut function_name = String::new();
@@ -586,8 +1024,15 @@ impl Context {
 
 
     fn call(&mut self, program: Program) -> JSIResult<Value> {
-      let block_result = self.call_block(&program.declarations, &program.body)?;
-      Ok(block_result.1)
+      self.push_call_frame(String::new());
+      let block_result = self.call_block(&program.declarations, &program.body);
+      if let Err(mut err) = block_result {
+        self.capture_error_stack(&mut err);
+        self.call_stack.pop();
+        return Err(err);
+      }
+      self.call_stack.pop();
+      Ok(block_result?.1)
     }
 
     fn call_block(&mut self, declarations: &Vec<Declaration>, body: &Vec<Statement>) -> JSIResult<(Value, Value, Value)> {
@@ -622,6 +1067,9 @@ impl Context {
     }
 
     fn call_statement(&mut self, statement: &Statement, result_value: &mut Value, last_statement_value: &mut Value, interrupt: &mut Value, call_options: CallStatementOptions) -> JSIResult<bool> {
+      self.set_cur_position(&statement.span());
+      self.check_limits()?;
+      self.check_stack()?;
       match statement {
         Statement::Var(var_statement) => {
           // var_statement.flag 是 var 还是 let，在上层调用链路中处理
@@ -651,7 +1099,7 @@ impl Context {
           let result = self.execute_expression(&return_statement.expression)?;
           (*result_value) = result;
           (*last_statement_value) = result_value.clone();
-          (*interrupt) = Value::Interrupt(Token::Return, Expression::Unknown);
+          (*interrupt) = Value::Interrupt(Token::Return, Box::new(Expression::Unknown));
           Ok(true)
         },
         Statement::Function(_) => {
@@ -688,40 +1136,46 @@ impl Context {
           Ok(true)
         },
         Statement::For(for_statment) => {
-          self.execute_for(for_statment, result_value, last_statement_value, interrupt, call_options)
+          self.execute_for(for_statment, interrupt, call_options)
         },
         Statement::ForIn(for_in_statement) => {
-          self.execute_for_in(for_in_statement, result_value, last_statement_value, interrupt, call_options)
+          self.execute_for_in(for_in_statement, interrupt, call_options)
         },
         Statement::ForOf(for_of_statement) => {
-          self.execute_for_of(for_of_statement, result_value, last_statement_value, interrupt, call_options)
+          self.execute_for_of(for_of_statement, interrupt, call_options)
         },
         Statement::While(for_statment) => {
-          self.execute_for(for_statment, result_value, last_statement_value, interrupt, call_options)
+          self.execute_for(for_statment, interrupt, call_options)
         },
         Statement::Switch(switch_statement) => {
           self.execute_switch(switch_statement, result_value, last_statement_value, interrupt, call_options)
         },
         Statement::Try(try_statement) => {
-          self.switch_scope(Some(Rc::clone(&self.cur_scope)));
+          let outer = self.switch_scope(Some(Rc::clone(&self.cur_scope)));
           let result = self.call_block(&vec![], &try_statement.body.statements);
-          self.close_scope();
+          self.close_scope(outer);
           if let Ok(value) = &result {
             (*result_value) = value.0.clone();
             (*last_statement_value) = value.1.clone();
             (*interrupt) = value.2.clone();
           } else if let Err(err) = &result {
+            if err.error_type == JSIErrorType::Terminated {
+              return Err(err.clone());
+            }
             if let Some(catch) = &try_statement.catch {
-              self.switch_scope(Some(Rc::clone(&self.cur_scope)));
+              let outer = self.switch_scope(Some(Rc::clone(&self.cur_scope)));
               if let Some(error_decl) =&catch.declaration {
+                let mut err = err.clone();
+                self.capture_error_stack(&mut err);
                 let err_obj = err.to_error_object(self);
                 (*self.cur_scope).borrow_mut().set_value(error_decl.literal.clone(), Value::Object(err_obj), false);
               }
-              let result = self.call_block(&vec![], &catch.body.statements)?;
+              let result = self.call_block(&vec![], &catch.body.statements);
+              self.close_scope(outer);
+              let result = result?;
               (*result_value) = result.0;
               (*last_statement_value) = result.1;
               (*interrupt) = result.2;
-              self.close_scope();
             }
           }
           // TODO: finaly
@@ -730,16 +1184,18 @@ impl Context {
         },
         Statement::Throw(throw) => {
           let throw_value = self.execute_expression(&throw.expression)?;
+          self.set_cur_position(&throw.span);
           let mut err = JSIError::new(JSIErrorType::Unknown, format!(""), 0, 0);
           err.set_value(throw_value);
           Err(err)
         },
         Statement::Block(block) => {
-          self.switch_scope(Some(Rc::clone(&self.cur_scope)));
-          let result = self.call_block(&vec![], &block.statements)?;
+          let outer = self.switch_scope(Some(Rc::clone(&self.cur_scope)));
+          let result = self.call_block(&vec![], &block.statements);
+          self.close_scope(outer);
+          let result = result?;
           (*result_value) = result.0;
           (*interrupt) = result.2;
-          self.close_scope();
           Ok(true)
         },
         Statement::Break(break_statement) => {
@@ -748,9 +1204,9 @@ impl Context {
             if !label_exists {
               // TODO: throw error label not exists
             }
-            (*interrupt) = Value::Interrupt(Token::Break,Expression::Identifier(IdentifierLiteral {literal: label.literal.clone()}));
+            (*interrupt) = Value::Interrupt(Token::Break, Box::new(Expression::Identifier(IdentifierLiteral {literal: label.literal.clone(), span: label.span})));
           } else {
-            (*interrupt) = Value::Interrupt(Token::Break, Expression::Unknown);
+            (*interrupt) = Value::Interrupt(Token::Break, Box::new(Expression::Unknown));
           }
           Ok(true)
         },
@@ -760,9 +1216,9 @@ impl Context {
             if !label_exists {
               // TODO: throw error label not exists
             }
-            (*interrupt) = Value::Interrupt(Token::Continue,Expression::Identifier(IdentifierLiteral {literal: label.literal.clone()}));
+            (*interrupt) = Value::Interrupt(Token::Continue, Box::new(Expression::Identifier(IdentifierLiteral {literal: label.literal.clone(), span: label.span})));
           } else {
-            (*interrupt) = Value::Interrupt(Token::Continue, Expression::Unknown);
+            (*interrupt) = Value::Interrupt(Token::Continue, Box::new(Expression::Unknown));
           }
           Ok(true)
         },
@@ -779,8 +1235,10 @@ impl Context {
 
     fn execute_expression_info(&mut self, expression: &Expression) -> JSIResult<ValueInfo> {
       // println!("expression: {:?}", expression);
+      self.check_stack()?;
+      self.set_cur_position(&expression.span());
       match expression {
-        Expression::Value(valuebox) => {
+        Expression::Value(valuebox, _) => {
           let value = *valuebox.clone();
           Ok(ValueInfo { is_const: false, value, name: None, access_path: String::from(""), reference: None })
         },
@@ -818,25 +1276,18 @@ impl Context {
           }
           let right = &property_access.name.literal;
 
-          // 特殊处理原始字符串的 length 属性
-          if right == "length" {
-            match &left {
-              Value::String(str) => {
-                return Ok(ValueInfo { is_const: false, value: Value::Number(str.len() as f64), name: Some(right.clone()), access_path: format!("{}.{}", left_info.access_path, property_access.name.literal), reference: Some(left) });
-              },
-              Value::StringObj(str_obj) => {
-                let inner_value = str_obj.borrow().get_inner_property_value(String::from("value"));
-                if let Some(Value::String(str)) = inner_value {
-                  return Ok(ValueInfo { is_const: false, value: Value::Number(str.len() as f64), name: Some(right.clone()), access_path: format!("{}.{}", left_info.access_path, property_access.name.literal), reference: Some(left) });
-                }
-              },
-              _ => {}
-            }
+          // 特殊处理字符串的 length 和索引属性
+          if let Some(value) = get_string_own_property(self, &left, right) {
+            return Ok(ValueInfo { is_const: false, value, name: Some(right.clone()), access_path: format!("{}.{}", left_info.access_path, property_access.name.literal), reference: Some(left) });
           }
 
           let left_clone = left.clone();
           let left_obj = left.to_object(self);
-          let value = (*left_obj).borrow().get_value(right.clone());
+          let accessor = (*left_obj).borrow().get_accessor(right);
+          let value = match accessor {
+            Some(accessor) => self.get_accessor_value(accessor, left.clone())?,
+            None => (*left_obj).borrow().get_value(right.clone()),
+          };
           Ok(ValueInfo { is_const: false, value, name: Some(right.clone()), access_path: format!("{}.{}", left_info.access_path, property_access.name.literal), reference: Some(left_clone) })
         },
         Expression::ComputedPropertyName(property_name) => {
@@ -847,14 +1298,21 @@ impl Context {
           let left = self.execute_expression(&element_access.expression)?;
           
           let left_obj = left.to_object(self);
-          let right = self.execute_expression(&element_access.argument)?.to_string(self);
+          let right = self.execute_expression(&element_access.argument)?.to_property_key(self);
           if left.is_equal_to(self, &Value::Null, true) {
             return Err(JSIError::new( JSIErrorType::TypeError, format!("Cannot read properties of null (reading '{}')", right), 0, 0))
           }
           if left.is_equal_to(self, &Value::Undefined, true) {
             return Err(JSIError::new( JSIErrorType::TypeError, format!("Cannot read properties of undefined (reading '{}')", right), 0, 0))
           }
-          let value = (*left_obj).borrow().get_value(right.clone());
+          if let Some(value) = get_string_own_property(self, &left, &right) {
+            return Ok(ValueInfo { is_const: false, value, name: Some(right.clone()),  access_path: String::from(""),reference: Some(Value::Object(left_obj)) })
+          }
+          let accessor = (*left_obj).borrow().get_accessor(&right);
+          let value = match accessor {
+            Some(accessor) => self.get_accessor_value(accessor, Value::Object(Rc::clone(&left_obj)))?,
+            None => (*left_obj).borrow().get_value(right.clone()),
+          };
           Ok(ValueInfo { is_const: false, value, name: Some(right.clone()),  access_path: String::from(""),reference: Some(Value::Object(left_obj)) })
         },
         Expression::Conditional(condition) => {
@@ -882,13 +1340,15 @@ impl Context {
           Ok(ValueInfo {is_const: false, value: Value::String(string.value.clone()), name: None, access_path: string.value.clone(), reference: None })
         },
         Expression::TemplateLiteral(template) => {
-          let mut strings: Vec<String> = vec![];
+          let mut units: Vec<u16> = vec![];
           for span in template.spans.iter() {
             let value = self.execute_expression(span)?;
-            strings.push(value.to_string(self));
+            units.extend(value.to_utf16(self));
+            self.check_allocation(units.len() * 2)?;
           }
-          let string = strings.join("");
-          Ok(ValueInfo {is_const: false, value: Value::String(string.clone()), name: None, access_path:string, reference: None })
+          let value = Value::from_utf16(units);
+          let string = value.to_string(self);
+          Ok(ValueInfo {is_const: false, value, name: None, access_path:string, reference: None })
         },
         Expression::Number(number) => {
           Ok(ValueInfo {is_const: false, value: Value::Number(number.value.clone()), name: None, access_path: number.literal.clone(), reference: None })
@@ -896,7 +1356,7 @@ impl Context {
         Expression::New(new_object) => {
           Ok(ValueInfo { is_const: false, value: self.execute_new_expression(new_object)?, name: None, access_path: String::from(""),reference: None })
         },
-        Expression::Keyword(keyword) => {
+        Expression::Keyword(keyword, _) => {
           Ok(ValueInfo {
             is_const: false,
             value: match *keyword {
@@ -985,7 +1445,18 @@ impl Context {
           if expression.operator == Token::Plus {
             // 如果有一个是字符串，那就返回字符串
             if left.is_string() || right.is_string() {
-              return Ok(Value::String(left.to_string(self) + right.to_string(self).as_str()));
+              // 两边都是合法的 UTF-16 时直接拼接，否则需要按照编码单元拼接（单独的代理项可能组成代理对）
+              if let (Value::Utf16String(_), _) | (_, Value::Utf16String(_)) = (&left, &right) {
+                let mut units = left.to_utf16(self);
+                let right_units = right.to_utf16(self);
+                self.check_allocation((units.len() + right_units.len()) * 2)?;
+                units.extend(right_units);
+                return Ok(Value::from_utf16(units));
+              }
+              let left_string = left.to_string(self);
+              let right_string = right.to_string(self);
+              self.check_allocation(left_string.len() + right_string.len())?;
+              return Ok(Value::String(left_string + right_string.as_str()));
             }
           }
 
@@ -1008,18 +1479,20 @@ impl Context {
           Ok(self.execute_bitwise_expression(&left, &right, &expression.operator))
         },
         Token::In => {
-          let key = left.to_string(self);
+          let key = left.to_property_key(self);
           match &right {
-            Value::Object(obj_rc) => {
-              Ok(Value::Boolean(obj_rc.borrow().property.contains_key(&key)))
-            },
             Value::RefObject(weak) => {
               if let Some(obj_rc) = weak.upgrade() {
-                Ok(Value::Boolean(obj_rc.borrow().property.contains_key(&key)))
+                Ok(Value::Boolean(obj_rc.borrow().has_property(&key)))
               } else {
                 Ok(Value::Boolean(false))
               }
             },
+            _ if right.is_object() => {
+              let obj_rc = right.to_object(self);
+              let has_property = obj_rc.borrow().has_property(&key);
+              Ok(Value::Boolean(has_property))
+            },
             _ => Err(JSIError::new(JSIErrorType::TypeError, format!("Cannot use 'in' operator to search for '{}' in non-object", key), 0, 0)),
           }
         },
@@ -1169,19 +1642,19 @@ impl Context {
       if let Some(num) = left.to_number(self) {
         left_number = num;
       } else {
-        return Err(JSIError::new(JSIErrorType::SyntaxError, format!("Unexpected token '{:?}'", operator), 0, 0))
+        return Err(JSIError::new(JSIErrorType::SyntaxError, format!("Unexpected token {}", get_token_description(operator)), 0, 0))
       }
       if let Some(num) = right.to_number(self) {
         right_number = num;
       } else {
-        return Err(JSIError::new(JSIErrorType::SyntaxError, format!("Unexpected token '{:?}'", operator), 0, 0))
+        return Err(JSIError::new(JSIErrorType::SyntaxError, format!("Unexpected token {}", get_token_description(operator)), 0, 0))
       }
       match operator {
         Token::Greater => Ok(Value::Boolean(left_number > right_number)),
         Token::GreaterOrEqual => Ok(Value::Boolean(left_number >= right_number)),
         Token::Less => Ok(Value::Boolean(left_number < right_number)),
         Token::LessOrEqual => Ok(Value::Boolean(left_number <= right_number)),
-        _=> Err(JSIError::new(JSIErrorType::SyntaxError, format!("Unexpected token '{:?}'", operator), 0, 0)),
+        _=> Err(JSIError::new(JSIErrorType::SyntaxError, format!("Unexpected token {}", get_token_description(operator)), 0, 0)),
       }
     }
 
@@ -1192,6 +1665,7 @@ impl Context {
       for arg in expression.arguments.iter() {
         arguments.push(self.execute_expression(arg)?);
       }
+      self.set_cur_position(&expression.span);
       match &callee.value {
         Value::Function(function_object) => {
           let mut reference = None;
@@ -1270,7 +1744,8 @@ impl Context {
         let binary_expression = BinaryExpression {
           left: expression.left.clone(),
           right: expression.right.clone(),
-          operator: operator
+          operator: operator,
+          span: expression.span,
         };
         right_value = self.execute_binary_expression(&binary_expression)?;
       }
@@ -1408,7 +1883,10 @@ impl Context {
 
       let operand_info = self.execute_expression_info(&expression.operand);
       if &Token::Typeof == &expression.operator {
-        if let Err(_) = operand_info {
+        if let Err(err) = &operand_info {
+          if err.error_type == JSIErrorType::Terminated {
+            return Err(err.clone());
+          }
           return Ok(Value::String(String::from("undefined")));
         }
       }
@@ -1466,6 +1944,12 @@ impl Context {
                 // 删除对象属性
                 if let Some(name) = &operand_info.name {
                   let obj = reference.to_object(self);
+                  if obj.borrow().frozen {
+                    if self.strict {
+                      return Err(JSIError::new(JSIErrorType::TypeError, format!("Cannot delete property '{}' of object", name), 0, 0));
+                    }
+                    return Ok(Value::Boolean(false));
+                  }
                   let deleted = obj.borrow_mut().delete_property(name.clone());
                   Ok(Value::Boolean(deleted))
                 } else {
@@ -1555,7 +2039,7 @@ impl Context {
     ) -> LoopInterruptAction {
       if let Value::Interrupt(token, expr) = interrupt_value {
         if token == &Token::Break {
-          if let Expression::Identifier(identifier) = expr {
+          if let Expression::Identifier(identifier) = expr.as_ref() {
             if let Some(last_label_str) = label {
               if *last_label_str == identifier.literal {
                 // break 当前循环
@@ -1568,7 +2052,7 @@ impl Context {
           }
           return LoopInterruptAction::Break;
         } else if token == &Token::Continue {
-          if let Expression::Identifier(identifier) = expr {
+          if let Expression::Identifier(identifier) = expr.as_ref() {
             if let Some(last_label_str) = label {
               if *last_label_str == identifier.literal {
                 // continue 当前循环
@@ -1589,7 +2073,15 @@ impl Context {
     }
 
     // 执行循环
-    fn execute_for(&mut self, for_statment: &ForStatement, _: &mut Value, _: &mut Value, interrupt: &mut Value, call_options: CallStatementOptions) -> JSIResult<bool> {
+    fn execute_for(&mut self, for_statment: &ForStatement, interrupt: &mut Value, call_options: CallStatementOptions) -> JSIResult<bool> {
+      // 循环中出错时也需要关闭循环创建的作用域
+      let outer = Rc::clone(&self.cur_scope);
+      let result = self.execute_for_in_scope(for_statment, interrupt, call_options);
+      self.close_scope(outer);
+      result
+    }
+
+    fn execute_for_in_scope(&mut self, for_statment: &ForStatement, interrupt: &mut Value, call_options: CallStatementOptions) -> JSIResult<bool> {
       let mut is_change_scope = false;
       let initializer = *for_statment.initializer.clone();
       let mut for_result = Value::Undefined;
@@ -1618,6 +2110,8 @@ impl Context {
       }
 
       loop {
+        // 空的循环体不会执行语句，每次循环单独检查
+        self.check_limits()?;
         if !for_statment.post_judgment {
           let condition = &for_statment.condition;
           
@@ -1663,12 +2157,19 @@ impl Context {
           }
         }
       }
-      self.close_scope();
       Ok(true)
     }
 
     // 执行 for-in 循环
-    fn execute_for_in(&mut self, for_in_statement: &ForInStatement, _: &mut Value, _: &mut Value, interrupt: &mut Value, call_options: CallStatementOptions) -> JSIResult<bool> {
+    fn execute_for_in(&mut self, for_in_statement: &ForInStatement, interrupt: &mut Value, call_options: CallStatementOptions) -> JSIResult<bool> {
+      // 循环中出错时也需要关闭循环创建的作用域
+      let outer = Rc::clone(&self.cur_scope);
+      let result = self.execute_for_in_in_scope(for_in_statement, interrupt, call_options);
+      self.close_scope(outer);
+      result
+    }
+
+    fn execute_for_in_in_scope(&mut self, for_in_statement: &ForInStatement, interrupt: &mut Value, call_options: CallStatementOptions) -> JSIResult<bool> {
       // Create scope for the loop variable
       self.switch_scope(Some(Rc::clone(&self.cur_scope)));
 
@@ -1685,15 +2186,15 @@ impl Context {
       // Get enumerable properties
       let properties: Vec<String> = {
         let obj_ref = object.borrow();
-        obj_ref.property.keys().filter(|key| {
-          obj_ref.property.get(*key).map_or(false, |prop| prop.enumerable)
-        }).cloned().collect()
+        obj_ref.get_own_property_keys().into_iter().filter(|key| {
+          obj_ref.property.get(key).is_some_and(|prop| prop.enumerable)
+        }).collect()
       };
 
       // Iterate over each property
       for key in properties {
         // Set the variable to the current key
-        (*self.cur_scope).borrow_mut().set_value(variable_name.clone(), Value::String(key.clone()), for_in_statement.var_flag == VariableFlag::Const);
+        (*self.cur_scope).borrow_mut().set_value(variable_name.clone(), Value::from_property_key(key.clone()), for_in_statement.var_flag == VariableFlag::Const);
 
         // Execute the loop body
         if let Statement::Block(block) = for_in_statement.statement.as_ref() {
@@ -1707,12 +2208,19 @@ impl Context {
         }
       }
 
-      self.close_scope();
       Ok(true)
     }
 
     // 执行 for-of 循环
-    fn execute_for_of(&mut self, for_of_statement: &ForOfStatement, _: &mut Value, _: &mut Value, interrupt: &mut Value, call_options: CallStatementOptions) -> JSIResult<bool> {
+    fn execute_for_of(&mut self, for_of_statement: &ForOfStatement, interrupt: &mut Value, call_options: CallStatementOptions) -> JSIResult<bool> {
+      // 循环中出错时也需要关闭循环创建的作用域
+      let outer = Rc::clone(&self.cur_scope);
+      let result = self.execute_for_of_in_scope(for_of_statement, interrupt, call_options);
+      self.close_scope(outer);
+      result
+    }
+
+    fn execute_for_of_in_scope(&mut self, for_of_statement: &ForOfStatement, interrupt: &mut Value, call_options: CallStatementOptions) -> JSIResult<bool> {
       // Create scope for the loop variable
       self.switch_scope(Some(Rc::clone(&self.cur_scope)));
 
@@ -1739,15 +2247,18 @@ impl Context {
           }
           vals
         },
-        Value::String(s) => {
-          s.chars().map(|c| Value::String(c.to_string())).collect()
+        Value::String(_) | Value::Utf16String(_) => {
+          get_iterable_values(self, &object_value).unwrap_or_default()
+        },
+        Value::Object(_) if is_array_iterator(&object_value) || is_map(&object_value) => {
+          get_iterable_values(self, &object_value).unwrap_or_default()
         },
         Value::Object(obj) => {
           // For objects, iterate over values
           let obj_ref = obj.borrow();
           let mut vals = vec![];
           for key in obj_ref.property.keys() {
-            if obj_ref.property.get(key).map_or(false, |prop| prop.enumerable) {
+            if obj_ref.property.get(key).is_some_and(|prop| prop.enumerable) {
               vals.push(obj_ref.get_value(key.clone()));
             }
           }
@@ -1776,7 +2287,6 @@ impl Context {
         }
       }
 
-      self.close_scope();
       Ok(true)
     }
 
@@ -1818,9 +2328,19 @@ impl Context {
       for element in &new_object.arguments {
         arguments.push(self.execute_expression(element)?);
       }
+      self.set_cur_position(&new_object.span);
+      self.construct_with_name(&constructor.value, arguments, &constructor.access_path)
+    }
+
+    // 以 constructor 为构造函数创建实例，等同于 new constructor(...arguments)
+    pub fn construct(&mut self, constructor: &Value, arguments: Vec<Value>) -> JSIResult<Value> {
+      let name = constructor.to_string(self);
+      self.construct_with_name(constructor, arguments, &name)
+    }
 
+    fn construct_with_name(&mut self, constructor: &Value, arguments: Vec<Value>, name: &str) -> JSIResult<Value> {
       // new function
-      if let Value::Function(function_declare) = &constructor.value {
+      if let Value::Function(function_declare) = constructor {
        
         let prototype = {
           let func_clone = Rc::clone(function_declare);
@@ -1837,18 +2357,29 @@ impl Context {
             obj_borrowed.set_inner_property_value(PROTO_PROPERTY_NAME.to_string(), Value::RefObject(Rc::downgrade(&proto)));
            }
            
+          let initializer = function_declare.borrow().get_initializer();
+          if let Some(Statement::HostFunction(host_function)) = initializer.map(|initializer| *initializer) {
+            // 宿主构造函数返回对象时，以返回的对象作为 new 的结果
+            let result = self.call_host_function(function_declare, host_function, Value::Object(Rc::clone(&obj)), arguments, constructor.clone())?;
+            if result.is_object() {
+              return Ok(result)
+            }
+            return Ok(Value::Object(obj))
+          }
            // 执行构造函数
           self.call_function_object(Rc::clone(function_declare), Some(Value::Object(Rc::clone(&obj))), None, arguments)?;
           return Ok(Value::Object(obj))
         }
       }
 
-      let obj = constructor.value.instantiate_object(self, arguments, true);
-      if let Ok(obj) = obj {
-        return Ok(obj)
+      let obj = constructor.instantiate_object(self, arguments, true);
+      match obj {
+        Ok(obj) => return Ok(obj),
+        Err(err) if err.error_type == JSIErrorType::Terminated => return Err(err),
+        _ => {},
       }
 
-      return Err(JSIError::new(JSIErrorType::TypeError, format!("{} is not a constructor", constructor.access_path), 0, 0))
+      return Err(JSIError::new(JSIErrorType::TypeError, format!("{} is not a constructor", name), 0, 0))
     }
 
     fn new_object(&mut self, expression: &ObjectLiteral) -> JSIResult<Value> {
@@ -1860,7 +2391,7 @@ impl Context {
       let mut normal_propertys: Vec<(String, Value)> = vec![];
       for property_index in 0..expression.properties.len() {
         let property = &expression.properties[property_index];
-        let name = self.execute_expression(&property.name)?.to_string(self);
+        let name = self.execute_expression(&property.name)?.to_property_key(self);
         let mut initializer = self.execute_expression(&property.initializer)?;
         initializer.bind_name(name.clone());
         // ComputedPropertyName 优先级更高，影响 object 的属性顺序
@@ -1930,6 +2461,13 @@ impl Context {
         self.stack.push(builtin_res.to_value_info());
         return Ok(Value::Undefined);
       }
+      // 宿主方法
+      if let Statement::HostFunction(host_function) = *function_define_value.clone() {
+        let arguments = args.iter().map(|info| info.value.clone()).collect::<Vec<Value>>();
+        let host_res = self.call_host_function(&function_define, host_function, this_obj, arguments, Value::Undefined)?;
+        self.stack.push(host_res.to_value_info());
+        return Ok(Value::Undefined);
+      }
 
       // 检查是否为箭头函数或无 bytecode 的函数
       let function_declaration = match &*function_define_value {
@@ -1946,6 +2484,7 @@ impl Context {
 
       // 判断是否需要通过 bytecode 执行（有 bytecode 且不是箭头函数）
       let use_bytecode_execution = bytecode_list.len() > 0 && function_declaration.as_ref().map_or(true, |f| !f.is_arrow);
+      self.check_call_depth()?;
 
        // 创建新的执行作用域
       let define_scope = (*function_define).borrow_mut().get_inner_property_value(String::from("define_scope"));
@@ -1955,7 +2494,8 @@ impl Context {
           define_scope_value = Some(scope);
         }
       }
-      self.switch_scope(define_scope_value);
+      let outer_realm = self.enter_scope_realm(&define_scope_value);
+      let outer = self.switch_scope(define_scope_value);
 
       let argument_object = create_object(self, ClassType::Object, None);
       {
@@ -1972,10 +2512,14 @@ impl Context {
       (*self.cur_scope).borrow_mut().set_value(String::from("arguments"), Value::Object(argument_object), false);
       (*self.cur_scope).borrow_mut().this = Some(this_obj.clone());
 
+      let func_name = get_builtin_function_name(self, &function_define);
+      self.push_call_frame(func_name);
+      let stack_len = self.stack.len();
+      let mut result = Ok(());
       if use_bytecode_execution {
         // 使用 bytecode 执行（常规函数）
         (*self.cur_scope).borrow_mut().function_call_args = args.clone();
-        let _ =  self.run_with_bytecode_list(0, &bytecode_list);
+        result = self.run_with_bytecode_list(0, &bytecode_list).map(|_| ());
       } else if let Some(ref func_decl) = function_declaration {
         // 使用 function declaration 执行（箭头函数）
         let arguments: Vec<Value> = args.iter().map(|info| info.value.clone()).collect();
@@ -1987,15 +2531,23 @@ impl Context {
             (*self.cur_scope).borrow_mut().set_value(func_decl.parameters[parameter_index].name.literal.clone(), Value::Undefined, false);
           }
         }
-        // 执行 body
-        let block_result = self.call_block(&func_decl.declarations, &func_decl.body.statements);
-        // 将返回值推入栈中
-        if let Ok((result_value, _, _)) = block_result {
+        // 执行 body，将返回值推入栈中
+        result = self.call_block(&func_decl.declarations, &func_decl.body.statements).map(|(result_value, _, _)| {
           self.stack.push(result_value.to_value_info());
-        }
+        });
+      }
+      if let Err(err) = &mut result {
+        self.capture_error_stack(err);
+        // 出错时丢弃函数执行过程中留在栈上的值
+        self.stack.truncate(stack_len);
       }
+      self.call_stack.pop();
 
-      self.close_scope();
+      self.close_scope(outer);
+      if outer_realm != self.cur_realm {
+        self.enter_realm(outer_realm);
+      }
+      result?;
       // 这个return 其实没啥用，都是走 stack
       Ok(Value::Undefined)
     }
@@ -2003,6 +2555,26 @@ impl Context {
     // 调用方法
     // call_this 指向调用时的 this
     // reference 指向
+    // 调用访问器的 getter，没有 getter 时返回 undefined
+    fn get_accessor_value(&mut self, accessor: Accessor, this: Value) -> JSIResult<Value> {
+      if let Some(Value::Function(getter)) = accessor.get {
+        return self.call_function_object(getter, Some(this), None, vec![]);
+      }
+      Ok(Value::Undefined)
+    }
+
+    // 调用宿主方法，new 调用时 new_target 为被调用的构造函数
+    fn call_host_function(&mut self, function_define: &Rc<RefCell<Object>>, host_function: HostFunction, this: Value, arguments: Vec<Value>, new_target: Value) -> JSIResult<Value> {
+      let func_name = get_builtin_function_name(self, function_define);
+      let mut ctx = CallContext{
+        ctx: self,
+        this,
+        reference: Some(Rc::downgrade(function_define)),
+        func_name,
+      };
+      (host_function)(&mut ctx, arguments, new_target)
+    }
+
     pub fn call_function_object(&mut self, function_define: Rc<RefCell<Object>>, call_this: Option<Value>, reference: Option<Weak<RefCell<Object>>>, arguments: Vec<Value>) -> JSIResult<Value> {
       // 获取 function 定义
       let function_define_value = (*function_define).borrow_mut().get_initializer().unwrap();
@@ -2029,11 +2601,16 @@ impl Context {
         };
         return (builtin_function)(&mut ctx, arguments);
       }
+      // 宿主方法
+      if let Statement::HostFunction(host_function) = *function_define_value {
+        return self.call_host_function(&function_define, host_function, this_obj, arguments, Value::Undefined);
+      }
 
       let function_declaration =  match *function_define_value {
         Statement::Function(function_declaration) => Some(function_declaration),
         _ => None,
       }.unwrap();
+      self.check_call_depth()?;
       let async_result_promise = if function_declaration.is_async {
         Some(create_promise_helper(self))
       } else {
@@ -2047,7 +2624,8 @@ impl Context {
           define_scope_value = Some(scope);
         }
       }
-      self.switch_scope(define_scope_value);
+      let outer_realm = self.enter_scope_realm(&define_scope_value);
+      let outer = self.switch_scope(define_scope_value);
 
       let argument_object = create_object(self, ClassType::Object, None);
       {
@@ -2072,8 +2650,17 @@ impl Context {
         }
       }
       // 执行 body
-      let result = self.call_block(&function_declaration.declarations, &function_declaration.body.statements);
-      self.close_scope();
+      let func_name = get_builtin_function_name(self, &function_define);
+      self.push_call_frame(func_name);
+      let mut result = self.call_block(&function_declaration.declarations, &function_declaration.body.statements);
+      if let Err(err) = &mut result {
+        self.capture_error_stack(err);
+      }
+      self.call_stack.pop();
+      self.close_scope(outer);
+      if outer_realm != self.cur_realm {
+        self.enter_realm(outer_realm);
+      }
 
       // Handle async function
       if function_declaration.is_async {
@@ -2085,6 +2672,9 @@ impl Context {
             let _ = call_function_value(self, &resolve_fn, return_value);
           },
           Err(err) => {
+            if err.error_type == JSIErrorType::Terminated {
+              return Err(err);
+            }
             if err.error_type == JSIErrorType::Await {
               if let Some(Value::Promise(awaited_promise)) = err.value {
                 let resume_callback = create_async_resume_callback(
@@ -2119,7 +2709,8 @@ impl Context {
     }
 
     // 切换作用域
-    fn switch_scope(&mut self, define_scope: Option<Rc<RefCell<Scope>>>) {
+    // 创建并进入新的作用域，返回进入前的作用域，退出时传给 close_scope
+    fn switch_scope(&mut self, define_scope: Option<Rc<RefCell<Scope>>>) -> Rc<RefCell<Scope>> {
       // 创建新的作用域
       let mut new_scope = Scope::new();
       // 作用域的父级为定义是的作用域，而不是调用时的作用域
@@ -2131,31 +2722,25 @@ impl Context {
       }
       // 添加调用时的来源作用域，调用完成之后得关闭当前作用域，回到调用时的作用域
       new_scope.from = Some(Rc::clone(&self.cur_scope));
+      new_scope.heap_allocation = Some(HeapAllocation::new(&self.heap, SCOPE_SIZE));
       let scope_rc = Rc::new(RefCell::new(new_scope));
-      let rc = Rc::clone(&scope_rc);
-      (*self.cur_scope).borrow_mut().childs.push(scope_rc);
-      self.cur_scope = rc;
-    }
-
-    // 退出作用域
-    fn close_scope(&mut self) {
-      let cur = Rc::clone(&self.cur_scope);
-      let cur_rc = cur.borrow();
-      if let Some(parent_scope_rc) = &cur_rc.from {
-        let len = (*parent_scope_rc).borrow_mut().childs.len();
-        let mut cur_scope_index = len;
-        for index in 0..len {
-          if (*parent_scope_rc).borrow_mut().childs[index].borrow().id == self.cur_scope.borrow().id {
-            cur_scope_index = index;
-            break;
-          }
-        }
-        if cur_scope_index != len {
-          (*parent_scope_rc).borrow_mut().childs.remove(cur_scope_index);
+      self.heap.track_scope(&scope_rc);
+      std::mem::replace(&mut self.cur_scope, scope_rc)
+    }
+
+    // 退出作用域，回到进入前的作用域
+    // 出错时内层的作用域可能没有关闭，这里一并断开
+    // 退出后作用域只会被闭包持有，断开 from 避免闭包持有调用方的作用域
+    fn close_scope(&mut self, outer: Rc<RefCell<Scope>>) {
+      let mut scope = Rc::clone(&self.cur_scope);
+      while !Rc::ptr_eq(&scope, &outer) {
+        let from = scope.borrow_mut().from.take();
+        match from {
+          Some(from) => scope = from,
+          None => break,
         }
-        // 回到父级作用域
-        self.cur_scope = Rc::clone(parent_scope_rc);
       }
+      self.cur_scope = outer;
     }
 
     // 初始化，主要是挂载全局对象
@@ -2202,7 +2787,3 @@ impl Context {
 
     // }
 } 
-
-
-
-pub type CallbackFunction = dyn Fn(&mut Context)-> JSIResult<Value>;
\ No newline at end of file
@@ -0,0 +1,288 @@
+use std::{collections::HashMap, hash::BuildHasher, rc::Rc};
+
+use crate::{ast_node::{ClassType, HostFunction}, builtins::{array::{create_array_from_values, get_iterable_values}, object::{create_object, Property}}, context::Context, error::{JSIError, JSIErrorType, JSIResult}, value::Value};
+
+// Rust 值转换为 JS 值
+pub trait IntoJs {
+  fn into_js(self, ctx: &mut Context) -> Value;
+}
+
+// JS 值转换为 Rust 值，类型不匹配时返回 TypeError
+pub trait FromJs: Sized {
+  fn from_js(ctx: &mut Context, value: &Value) -> JSIResult<Self>;
+}
+
+fn get_type_name(value: &Value) -> String {
+  match value {
+    Value::Null => String::from("null"),
+    Value::Array(_) => String::from("array"),
+    _ => value.type_of(),
+  }
+}
+
+fn convert_error(value: &Value, target: &str) -> JSIError {
+  JSIError::new(JSIErrorType::TypeError, format!("Cannot convert {} to {}", get_type_name(value), target), 0, 0)
+}
+
+// 包装对象（new Number(1) 等）先转换为原始值
+fn unwrap_primitive(ctx: &mut Context, value: &Value) -> Value {
+  if let Some(primitive) = value.to_primitive_value(ctx) {
+    return primitive;
+  }
+  value.clone()
+}
+
+impl IntoJs for Value {
+  fn into_js(self, _: &mut Context) -> Value {
+    self
+  }
+}
+
+impl FromJs for Value {
+  fn from_js(_: &mut Context, value: &Value) -> JSIResult<Self> {
+    Ok(value.clone())
+  }
+}
+
+impl IntoJs for () {
+  fn into_js(self, _: &mut Context) -> Value {
+    Value::Undefined
+  }
+}
+
+impl IntoJs for bool {
+  fn into_js(self, _: &mut Context) -> Value {
+    Value::Boolean(self)
+  }
+}
+
+impl FromJs for bool {
+  fn from_js(ctx: &mut Context, value: &Value) -> JSIResult<Self> {
+    match unwrap_primitive(ctx, value) {
+      Value::Boolean(boolean) => Ok(boolean),
+      _ => Err(convert_error(value, "boolean")),
+    }
+  }
+}
+
+impl IntoJs for f64 {
+  fn into_js(self, _: &mut Context) -> Value {
+    if self.is_nan() {
+      return Value::NAN;
+    }
+    Value::Number(self)
+  }
+}
+
+impl FromJs for f64 {
+  fn from_js(ctx: &mut Context, value: &Value) -> JSIResult<Self> {
+    match unwrap_primitive(ctx, value) {
+      Value::Number(number) => Ok(number),
+      Value::NAN => Ok(f64::NAN),
+      _ => Err(convert_error(value, "number")),
+    }
+  }
+}
+
+impl IntoJs for f32 {
+  fn into_js(self, ctx: &mut Context) -> Value {
+    (self as f64).into_js(ctx)
+  }
+}
+
+impl FromJs for f32 {
+  fn from_js(ctx: &mut Context, value: &Value) -> JSIResult<Self> {
+    Ok(f64::from_js(ctx, value)? as f32)
+  }
+}
+
+// 整数只接受范围内的整数值，不做截断
+macro_rules! impl_integer_convert {
+  ($($int:ty),*) => {
+    $(
+      impl IntoJs for $int {
+        fn into_js(self, _: &mut Context) -> Value {
+          Value::Number(self as f64)
+        }
+      }
+
+      impl FromJs for $int {
+        fn from_js(ctx: &mut Context, value: &Value) -> JSIResult<Self> {
+          let number = f64::from_js(ctx, value)?;
+          if number.fract() != 0.0 || number < <$int>::MIN as f64 || number > <$int>::MAX as f64 {
+            return Err(JSIError::new(JSIErrorType::TypeError, format!("Cannot convert {} to {}", number, stringify!($int)), 0, 0));
+          }
+          Ok(number as $int)
+        }
+      }
+    )*
+  };
+}
+
+impl_integer_convert!(i8, i16, i32, i64, isize, u8, u16, u32, u64, usize);
+
+impl IntoJs for String {
+  fn into_js(self, _: &mut Context) -> Value {
+    Value::String(self)
+  }
+}
+
+impl IntoJs for &str {
+  fn into_js(self, _: &mut Context) -> Value {
+    Value::String(self.to_string())
+  }
+}
+
+impl FromJs for String {
+  fn from_js(ctx: &mut Context, value: &Value) -> JSIResult<Self> {
+    match unwrap_primitive(ctx, value) {
+      Value::String(str) => Ok(str),
+      // 单独的代理项无法用 String 表示，按 U+FFFD 替换
+      Value::Utf16String(units) => Ok(String::from_utf16_lossy(&units)),
+      _ => Err(convert_error(value, "string")),
+    }
+  }
+}
+
+// None 对应 undefined，undefined 和 null 都转换为 None
+impl<T: IntoJs> IntoJs for Option<T> {
+  fn into_js(self, ctx: &mut Context) -> Value {
+    match self {
+      Some(value) => value.into_js(ctx),
+      None => Value::Undefined,
+    }
+  }
+}
+
+impl<T: FromJs> FromJs for Option<T> {
+  fn from_js(ctx: &mut Context, value: &Value) -> JSIResult<Self> {
+    match value {
+      Value::Undefined | Value::Null => Ok(None),
+      _ => Ok(Some(T::from_js(ctx, value)?)),
+    }
+  }
+}
+
+impl<T: IntoJs> IntoJs for Vec<T> {
+  fn into_js(self, ctx: &mut Context) -> Value {
+    let values = self.into_iter().map(|value| value.into_js(ctx)).collect::<Vec<Value>>();
+    create_array_from_values(ctx, values)
+  }
+}
+
+impl<T: FromJs> FromJs for Vec<T> {
+  fn from_js(ctx: &mut Context, value: &Value) -> JSIResult<Self> {
+    let values = get_array_values(ctx, value, "array")?;
+    values.iter().map(|value| T::from_js(ctx, value)).collect()
+  }
+}
+
+fn get_array_values(ctx: &mut Context, value: &Value, target: &str) -> JSIResult<Vec<Value>> {
+  if let Value::Array(_) = value {
+    if let Some(values) = get_iterable_values(ctx, value) {
+      return Ok(values);
+    }
+  }
+  Err(convert_error(value, target))
+}
+
+impl<T: IntoJs, S: BuildHasher> IntoJs for HashMap<String, T, S> {
+  fn into_js(self, ctx: &mut Context) -> Value {
+    let object = create_object(ctx, ClassType::Object, None);
+    // HashMap 无序，按 key 排序保证属性顺序稳定
+    let mut entries = self.into_iter().collect::<Vec<(String, T)>>();
+    entries.sort_by(|a, b| a.0.cmp(&b.0));
+    for (key, value) in entries {
+      let value = value.into_js(ctx);
+      object.borrow_mut().define_property(key, Property { enumerable: true, value });
+    }
+    Value::Object(object)
+  }
+}
+
+impl<T: FromJs, S: BuildHasher + Default> FromJs for HashMap<String, T, S> {
+  fn from_js(ctx: &mut Context, value: &Value) -> JSIResult<Self> {
+    let object = match value {
+      Value::Object(object) => Rc::clone(object),
+      _ => return Err(convert_error(value, "object")),
+    };
+    // 只转换自身的可枚举属性
+    let entries = {
+      let object = object.borrow();
+      object.property_list.iter().filter_map(|key| {
+        object.property.get(key).filter(|property| property.enumerable).map(|property| (key.clone(), property.value.clone()))
+      }).collect::<Vec<(String, Value)>>()
+    };
+    let mut map = HashMap::with_hasher(S::default());
+    for (key, value) in entries {
+      let value = T::from_js(ctx, &value)?;
+      map.insert(key, value);
+    }
+    Ok(map)
+  }
+}
+
+// 元组和数组互相转换
+macro_rules! impl_tuple_convert {
+  ($len:expr, $($name:ident : $index:tt),+) => {
+    impl<$($name: IntoJs),+> IntoJs for ($($name,)+) {
+      fn into_js(self, ctx: &mut Context) -> Value {
+        let values = vec![$(self.$index.into_js(ctx)),+];
+        create_array_from_values(ctx, values)
+      }
+    }
+
+    impl<$($name: FromJs),+> FromJs for ($($name,)+) {
+      fn from_js(ctx: &mut Context, value: &Value) -> JSIResult<Self> {
+        let values = get_array_values(ctx, value, "tuple")?;
+        if values.len() != $len {
+          return Err(JSIError::new(JSIErrorType::TypeError, format!("Cannot convert array of length {} to tuple of length {}", values.len(), $len), 0, 0));
+        }
+        Ok(($($name::from_js(ctx, &values[$index])?,)+))
+      }
+    }
+  };
+}
+
+impl_tuple_convert!(1, A: 0);
+impl_tuple_convert!(2, A: 0, B: 1);
+impl_tuple_convert!(3, A: 0, B: 1, C: 2);
+impl_tuple_convert!(4, A: 0, B: 1, C: 2, D: 3);
+impl_tuple_convert!(5, A: 0, B: 1, C: 2, D: 3, E: 4);
+impl_tuple_convert!(6, A: 0, B: 1, C: 2, D: 3, E: 4, F: 5);
+
+// 参数和返回值带类型的 Rust 方法，可以转换为宿主方法
+// 参数缺失时按 undefined 转换，返回值通过 IntoJs 转换为 JS 值
+pub trait TypedFunction<Args> {
+  fn length(&self) -> usize;
+  fn into_host_function(self) -> HostFunction;
+}
+
+macro_rules! impl_typed_function {
+  ($len:expr $(, $arg:ident : $ty:ident)*) => {
+    impl<Fun, Ret $(, $ty)*> TypedFunction<($($ty,)*)> for Fun
+    where Fun: Fn($($ty),*) -> JSIResult<Ret> + 'static, Ret: IntoJs $(, $ty: FromJs)* {
+      fn length(&self) -> usize {
+        $len
+      }
+
+      #[allow(unused_mut, unused_variables)]
+      fn into_host_function(self) -> HostFunction {
+        Rc::new(move |call_ctx, args, _| {
+          let mut args = args.into_iter();
+          $(let $arg = $ty::from_js(call_ctx.ctx, &args.next().unwrap_or(Value::Undefined))?;)*
+          let result = (self)($($arg),*)?;
+          Ok(result.into_js(call_ctx.ctx))
+        })
+      }
+    }
+  };
+}
+
+impl_typed_function!(0);
+impl_typed_function!(1, a: A);
+impl_typed_function!(2, a: A, b: B);
+impl_typed_function!(3, a: A, b: B, c: C);
+impl_typed_function!(4, a: A, b: B, c: C, d: D);
+impl_typed_function!(5, a: A, b: B, c: C, d: D, e: E);
+impl_typed_function!(6, a: A, b: B, c: C, d: D, e: E, f: F);
@@ -1,7 +1,7 @@
-use std::{result, rc::Rc, cell::RefCell};
-use crate::constants::{GLOBAL_ERROR_NAME, GLOBAL_TYPE_ERROR_NAME};
+use std::{fmt, result, rc::Rc, cell::RefCell};
+use crate::constants::{GLOBAL_ERROR_NAME, GLOBAL_TYPE_ERROR_NAME, GLOBAL_SYNTAX_ERROR_NAME, GLOBAL_REFERENCE_ERROR_NAME, GLOBAL_RANGE_ERROR_NAME, GLOBAL_EVAL_ERROR_NAME, GLOBAL_URI_ERROR_NAME};
 use crate::context::{Context};
-use crate::{builtins::{object::Object, error::create_error}, value::Value};
+use crate::{builtins::{object::{Object, Property}, error::create_error, number::number_to_string}, value::Value};
 
 pub type JSIResult<T> = result::Result<T, JSIError>;
 
@@ -15,8 +15,14 @@ pub enum JSIErrorType {
   ReferenceError,
   // 范围错误，如设置 array 的length为非数字
   RangeError,
+  // eval 相关的错误
+  EvalError,
+  // URI 编码、解码错误，如 decodeURI 传入非法的字符串
+  URIError,
   // 内部使用，表示 async function 在 await pending Promise 时挂起
   Await,
+  // 超出执行限制（操作数、截止时间）或被中断，不能被 try catch 捕获
+  Terminated,
   Unknown,
 }
 
@@ -27,26 +33,76 @@ impl JSIErrorType {
         JSIErrorType::TypeError => String::from("TypeError"),
         JSIErrorType::ReferenceError => String::from("ReferenceError"),
         JSIErrorType::RangeError => String::from("RangeError"),
+        JSIErrorType::EvalError => String::from("EvalError"),
+        JSIErrorType::URIError => String::from("URIError"),
       JSIErrorType::Await => String::from("Await"),
+        JSIErrorType::Terminated => String::from("Terminated"),
         JSIErrorType::Unknown => String::from("Unknown"),
     }
   }
   // 转换为全局错误对象名称
   pub fn to_global_error_type(&self) -> &str {
     match self {
+        JSIErrorType::SyntaxError => GLOBAL_SYNTAX_ERROR_NAME,
         JSIErrorType::TypeError => GLOBAL_TYPE_ERROR_NAME,
+        JSIErrorType::ReferenceError => GLOBAL_REFERENCE_ERROR_NAME,
+        JSIErrorType::RangeError => GLOBAL_RANGE_ERROR_NAME,
+        JSIErrorType::EvalError => GLOBAL_EVAL_ERROR_NAME,
+        JSIErrorType::URIError => GLOBAL_URI_ERROR_NAME,
         _ => GLOBAL_ERROR_NAME,
     }
   }
 }
 
+// 调用栈中的一帧，line 和 column 为该函数当前执行到的位置（从 1 开始，0 表示未知）
+#[derive(Debug, Clone, PartialEq)]
+pub struct StackFrame {
+  // 函数名，顶层代码和匿名函数为空字符串
+  pub function_name: String,
+  pub line: i32,
+  pub column: i32,
+}
+
+impl fmt::Display for StackFrame {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    // 位置未知时只输出函数名
+    let location = if self.line == 0 {
+      String::from("<anonymous>")
+    } else {
+      format!("<anonymous>:{}:{}", self.line, self.column)
+    };
+    if self.function_name.is_empty() {
+      write!(f, "at {}", location)
+    } else {
+      write!(f, "at {} ({})", self.function_name, location)
+    }
+  }
+}
+
+// 生成 error.stack 字符串，如 "Error: msg\n    at foo (<anonymous>:2:9)"
+pub fn format_error_stack(name: &str, message: &str, frames: &[StackFrame]) -> String {
+  let mut stack = if message.is_empty() {
+    name.to_string()
+  } else {
+    format!("{}: {}", name, message)
+  };
+  for frame in frames.iter() {
+    stack.push_str(&format!("\n    {}", frame));
+  }
+  stack
+}
+
 #[derive(Debug, Clone)]
 pub struct JSIError {
     pub error_type: JSIErrorType,
     pub message: String,
     pub line: i32,
     pub column: i32,
-    pub value: Option<Value>
+    pub value: Option<Value>,
+    // 错误发生时的调用栈，栈顶的帧在前
+    pub stack: Vec<StackFrame>,
+    // 语法错误时期望出现的内容，如 ')'、expression
+    pub expected: Option<String>,
 }
 
 impl JSIError {
@@ -56,7 +112,80 @@ impl JSIError {
         message,
         line,
         column,
-        value: None
+        value: None,
+        stack: vec![],
+        expected: None,
+      }
+    }
+
+    // 错误的类型名和错误信息
+    // throw 的值是对象时使用对象上的 name 和 message，其他值没有类型名
+    fn get_name_and_message(&self) -> (Option<String>, String) {
+      let value = match &self.value {
+        Some(value) => value,
+        None => return (Some(self.error_type.to_string()), self.message.clone()),
+      };
+      if let Some(obj) = value.to_weak_rc_object().and_then(|obj| obj.upgrade()) {
+        let obj = obj.borrow();
+        let name = match obj.get_value(String::from("name")) {
+          Value::String(name) => name,
+          _ => String::from("Error"),
+        };
+        let message = match obj.get_value(String::from("message")) {
+          Value::String(message) => message,
+          _ => String::new(),
+        };
+        return (Some(name), message);
+      }
+      let message = match value {
+        Value::String(str) => str.clone(),
+        Value::Utf16String(units) => String::from_utf16_lossy(units),
+        Value::Number(num) => number_to_string(*num),
+        Value::NAN => String::from("NaN"),
+        Value::Boolean(bool) => bool.to_string(),
+        Value::Null => String::from("null"),
+        _ => String::from("undefined"),
+      };
+      (None, message)
+    }
+
+    // 生成带源码片段的错误信息，在出错的列下标出 ^
+    // SyntaxError: Unexpected token ';'
+    //  --> <anonymous>:2:13
+    //   |
+    // 2 | let b = (2 +;
+    //   |             ^ expected expression
+    pub fn render(&self, source: &str) -> String {
+      let mut result = self.get_header();
+      let line_text = if self.line > 0 {
+        source.lines().nth((self.line - 1) as usize)
+      } else {
+        None
+      };
+      let line_text = match line_text {
+        Some(line_text) => line_text,
+        None => return result,
+      };
+      let line_number = self.line.to_string();
+      let gutter = " ".repeat(line_number.len());
+      // 保留 tab，保证 ^ 和源码对齐
+      let caret_indent: String = line_text.chars().take((self.column.max(1) - 1) as usize).map(|char| if char == '\t' { '\t' } else { ' ' }).collect();
+      result.push_str(&format!("\n{}--> <anonymous>:{}:{}", gutter, self.line, self.column));
+      result.push_str(&format!("\n{} |", gutter));
+      result.push_str(&format!("\n{} | {}", line_number, line_text));
+      result.push_str(&format!("\n{} | {}^", gutter, caret_indent));
+      if let Some(expected) = &self.expected {
+        result.push_str(&format!(" expected {}", expected));
+      }
+      result
+    }
+
+    // 错误的第一行，如 TypeError: x is not a function
+    fn get_header(&self) -> String {
+      match self.get_name_and_message() {
+        (Some(name), message) if message.is_empty() => name,
+        (Some(name), message) => format!("{}: {}", name, message),
+        (None, message) => format!("Uncaught {}", message),
       }
     }
 
@@ -64,17 +193,34 @@ impl JSIError {
       if let Some(value) = &self.value {
         return value.to_object(ctx);
       }
-      let new_error = create_error(ctx, Value::String(self.message.clone()), self.error_type.to_global_error_type());
-      // TODO: set error line/stack
+      let error_type = self.error_type.to_global_error_type();
+      let new_error = create_error(ctx, Value::String(self.message.clone()), error_type);
       let obj = if let Value::Object(obj) = new_error {
         Some(obj)
       } else {
         None
       }.unwrap();
+      // 使用错误发生时的调用栈，而不是创建错误对象时的调用栈
+      if !self.stack.is_empty() {
+        let stack = format_error_stack(error_type, &self.message, &self.stack);
+        obj.borrow_mut().define_property(String::from("stack"), Property { enumerable: false, value: Value::String(stack) });
+      }
       return obj;
     }
 
     pub fn set_value(&mut self, value: Value) {
       self.value = Some(value);
     }
-}
\ No newline at end of file
+}
+
+impl fmt::Display for JSIError {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    write!(f, "{}", self.get_header())?;
+    if self.line > 0 {
+      write!(f, " (<anonymous>:{}:{})", self.line, self.column)?;
+    }
+    Ok(())
+  }
+}
+
+impl std::error::Error for JSIError {}
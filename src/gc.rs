@@ -0,0 +1,161 @@
+// 循环引用回收
+// 采用试探删除：对象和作用域之间的强引用从引用计数中减去后，计数仍大于 0 的节点被外部引用（宿主持有的值、Context 的栈等），
+// 从这些节点出发能访问到的节点都是存活的，其余节点只被循环引用持有，清空它们的引用后由 Rc 释放
+use std::{cell::RefCell, collections::HashMap, mem, rc::Rc};
+
+use crate::{builtins::object::Object, context::Context, scope::Scope, value::Value};
+
+enum Node {
+  Object(Rc<RefCell<Object>>),
+  Scope(Rc<RefCell<Scope>>),
+}
+
+impl Node {
+  fn as_ptr(&self) -> *const () {
+    match self {
+      Node::Object(object) => Rc::as_ptr(object) as *const (),
+      Node::Scope(scope) => Rc::as_ptr(scope) as *const (),
+    }
+  }
+
+  fn strong_count(&self) -> usize {
+    match self {
+      Node::Object(object) => Rc::strong_count(object),
+      Node::Scope(scope) => Rc::strong_count(scope),
+    }
+  }
+
+  // 获取节点引用的其他节点，(指针, 是否强引用)
+  // 节点正在被借用时返回 None，这时把它当作外部引用的节点
+  fn edges(&self) -> Option<Vec<(*const (), bool)>> {
+    let mut edges = vec![];
+    match self {
+      Node::Object(object) => {
+        let object = object.try_borrow().ok()?;
+        for property in object.property.values().chain(object.inner_property.values()) {
+          value_edge(&property.value, &mut edges);
+        }
+        for accessor in object.accessor.values() {
+          for value in accessor.get.iter().chain(accessor.set.iter()) {
+            value_edge(value, &mut edges);
+          }
+        }
+        if let Some(map_data) = &object.map_data {
+          for (key, value) in map_data.iter() {
+            value_edge(key, &mut edges);
+            value_edge(value, &mut edges);
+          }
+        }
+        if let Some(prototype) = &object.prototype {
+          edges.push((Rc::as_ptr(prototype) as *const (), true));
+        }
+        if let Some(constructor) = &object.constructor {
+          edges.push((constructor.as_ptr() as *const (), false));
+        }
+      },
+      Node::Scope(scope) => {
+        let scope = scope.try_borrow().ok()?;
+        for related in scope.parent.iter().chain(scope.from.iter()) {
+          edges.push((Rc::as_ptr(related) as *const (), true));
+        }
+        for value in scope.this.iter().chain(scope.variable_values()) {
+          value_edge(value, &mut edges);
+        }
+        for value_info in scope.function_call_args.iter() {
+          value_edge(&value_info.value, &mut edges);
+          if let Some(reference) = &value_info.reference {
+            value_edge(reference, &mut edges);
+          }
+        }
+      },
+    }
+    Some(edges)
+  }
+
+  // 清空节点持有的引用，返回被移除的内容，在所有借用结束后再释放
+  fn clear(&self, removed: &mut Vec<Box<dyn std::any::Any>>) {
+    match self {
+      Node::Object(object) => {
+        let mut object = object.borrow_mut();
+        removed.push(Box::new(mem::take(&mut object.property)));
+        removed.push(Box::new(mem::take(&mut object.inner_property)));
+        removed.push(Box::new(mem::take(&mut object.accessor)));
+        removed.push(Box::new(object.prototype.take()));
+        removed.push(Box::new(object.constructor.take()));
+        removed.push(Box::new(object.host_data.take()));
+        removed.push(Box::new(object.map_data.take()));
+      },
+      Node::Scope(scope) => {
+        removed.push(Box::new(mem::replace(&mut *scope.borrow_mut(), Scope::new())));
+      },
+    }
+  }
+}
+
+fn value_edge(value: &Value, edges: &mut Vec<(*const (), bool)>) {
+  match value {
+    Value::Object(object) | Value::Function(object) | Value::Array(object) | Value::Promise(object) |
+    Value::StringObj(object) | Value::NumberObj(object) | Value::BooleanObj(object) => {
+      edges.push((Rc::as_ptr(object) as *const (), true));
+    },
+    Value::RefObject(object) => {
+      edges.push((object.as_ptr() as *const (), false));
+    },
+    Value::Scope(scope) => {
+      edges.push((Rc::as_ptr(scope) as *const (), true));
+    },
+    _ => {},
+  }
+}
+
+// 回收只被循环引用持有的对象和作用域，返回回收的数量
+pub fn collect_cycles(ctx: &mut Context) -> usize {
+  let mut nodes: Vec<Node> = ctx.heap.tracked_objects().into_iter().map(Node::Object).collect();
+  nodes.extend(ctx.heap.tracked_scopes().into_iter().map(Node::Scope));
+  let index_map: HashMap<*const (), usize> = nodes.iter().enumerate().map(|(index, node)| (node.as_ptr(), index)).collect();
+
+  // 减去 nodes 自身持有的一次引用
+  let mut external_refs: Vec<usize> = nodes.iter().map(|node| node.strong_count() - 1).collect();
+  let mut edges: Vec<Vec<(usize, bool)>> = Vec::with_capacity(nodes.len());
+  let mut borrowed = vec![false; nodes.len()];
+  for (index, node) in nodes.iter().enumerate() {
+    match node.edges() {
+      Some(node_edges) => {
+        edges.push(node_edges.into_iter().filter_map(|(ptr, strong)| index_map.get(&ptr).map(|index| (*index, strong))).collect());
+      },
+      None => {
+        borrowed[index] = true;
+        edges.push(vec![]);
+      },
+    }
+  }
+  for node_edges in edges.iter() {
+    for (target, strong) in node_edges.iter() {
+      if *strong {
+        external_refs[*target] = external_refs[*target].saturating_sub(1);
+      }
+    }
+  }
+
+  // 从外部引用的节点开始标记，弱引用指向的节点也保留，避免原型等被提前释放
+  let mut alive = vec![false; nodes.len()];
+  let mut stack: Vec<usize> = (0..nodes.len()).filter(|index| external_refs[*index] > 0 || borrowed[*index]).collect();
+  while let Some(index) = stack.pop() {
+    if alive[index] {
+      continue;
+    }
+    alive[index] = true;
+    stack.extend(edges[index].iter().map(|(target, _)| *target).filter(|target| !alive[*target]));
+  }
+
+  let mut removed: Vec<Box<dyn std::any::Any>> = vec![];
+  let mut count = 0;
+  for (index, node) in nodes.iter().enumerate() {
+    if !alive[index] {
+      node.clear(&mut removed);
+      count += 1;
+    }
+  }
+  drop(removed);
+  count
+}
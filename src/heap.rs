@@ -0,0 +1,205 @@
+// 堆内存统计
+// 对象创建时登记大小，对象释放时归还；字符串在生成时检查剩余空间，
+// 保存到对象属性或变量中时计入持有者的占用，直到被替换或删除
+// 同时记录可能参与循环引用的对象和作用域，供垃圾回收遍历
+use std::{cell::{Cell, RefCell}, mem::size_of, rc::{Rc, Weak}};
+
+use crate::{builtins::object::{Object, Property}, scope::Scope, value::Value};
+
+// 对象本身的大小
+pub const OBJECT_SIZE: usize = size_of::<Object>();
+// 作用域本身的大小
+pub const SCOPE_SIZE: usize = size_of::<Scope>();
+// 每个属性的大小，不包含属性名的内容
+pub const PROPERTY_SIZE: usize = size_of::<Property>() + size_of::<String>();
+
+// 值持有的额外内存，目前只计算字符串的内容，对象单独登记
+pub fn value_payload_size(value: &Value) -> usize {
+  match value {
+    Value::String(string) => string.len(),
+    Value::Utf16String(units) => units.len() * 2,
+    _ => 0,
+  }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct HeapStats {
+  // 当前存活对象占用的字节数
+  pub used: usize,
+  // 占用的峰值，包含拼接过程中产生的字符串
+  pub peak: usize,
+  // 累计分配的字节数
+  pub allocated: usize,
+  pub limit: Option<usize>,
+}
+
+#[derive(Debug, Default)]
+pub struct Heap {
+  used: Cell<usize>,
+  peak: Cell<usize>,
+  allocated: Cell<usize>,
+  limit: Cell<Option<usize>>,
+  // 是否超出过限制，由执行限制检查转换为终止错误
+  exceeded: Cell<bool>,
+  objects: RefCell<Vec<Weak<RefCell<Object>>>>,
+  scopes: RefCell<Vec<Weak<RefCell<Scope>>>>,
+}
+
+// 跟踪列表超过这个长度时清理已经释放的记录
+const TRACK_PRUNE_SIZE: usize = 1024;
+
+fn track<T>(list: &RefCell<Vec<Weak<T>>>, item: &Rc<T>) {
+  let mut list = list.borrow_mut();
+  // Weak 会占住 Rc 的内存，已释放的记录需要定期清理
+  if list.len() >= TRACK_PRUNE_SIZE && list.len().is_power_of_two() {
+    list.retain(|item| item.strong_count() > 0);
+  }
+  list.push(Rc::downgrade(item));
+}
+
+fn upgrade_tracked<T>(list: &RefCell<Vec<Weak<T>>>) -> Vec<Rc<T>> {
+  let mut list = list.borrow_mut();
+  list.retain(|item| item.strong_count() > 0);
+  list.iter().filter_map(|item| item.upgrade()).collect()
+}
+
+impl Heap {
+  pub fn stats(&self) -> HeapStats {
+    HeapStats {
+      used: self.used.get(),
+      peak: self.peak.get(),
+      allocated: self.allocated.get(),
+      limit: self.limit.get(),
+    }
+  }
+
+  pub fn set_limit(&self, limit: Option<usize>) {
+    self.limit.set(limit);
+  }
+
+  // 新的一次执行开始时清除超限标记，仍然超限时下一次分配会再次终止执行
+  // 这样可以执行释放内存的脚本，如把引用置为 null
+  pub fn reset_exceeded(&self) {
+    self.exceeded.set(false);
+  }
+
+  pub fn is_exceeded(&self) -> bool {
+    self.exceeded.get()
+  }
+
+  // 再分配 size 字节是否会超出限制，不记录分配
+  pub fn is_over_limit(&self, size: usize) -> bool {
+    match self.limit.get() {
+      Some(limit) => self.used.get().saturating_add(size) > limit,
+      None => false,
+    }
+  }
+
+  fn update_peak(&self, used: usize) {
+    if used > self.peak.get() {
+      self.peak.set(used);
+    }
+  }
+
+  fn charge(&self, size: usize) {
+    if self.is_over_limit(size) {
+      self.exceeded.set(true);
+    }
+    let used = self.used.get() + size;
+    self.used.set(used);
+    self.allocated.set(self.allocated.get() + size);
+    self.update_peak(used);
+  }
+
+  fn release(&self, size: usize) {
+    self.used.set(self.used.get().saturating_sub(size));
+  }
+
+  pub fn track_object(&self, object: &Rc<RefCell<Object>>) {
+    track(&self.objects, object);
+  }
+
+  pub fn track_scope(&self, scope: &Rc<RefCell<Scope>>) {
+    track(&self.scopes, scope);
+  }
+
+  // 获取所有存活的对象和作用域
+  pub fn tracked_objects(&self) -> Vec<Rc<RefCell<Object>>> {
+    upgrade_tracked(&self.objects)
+  }
+
+  pub fn tracked_scopes(&self) -> Vec<Rc<RefCell<Scope>>> {
+    upgrade_tracked(&self.scopes)
+  }
+
+  // 临时分配（如拼接的字符串），超出限制时返回 false，不会记为已占用
+  pub fn check_transient(&self, size: usize) -> bool {
+    if self.is_over_limit(size) {
+      self.exceeded.set(true);
+      return false;
+    }
+    self.allocated.set(self.allocated.get() + size);
+    self.update_peak(self.used.get() + size);
+    true
+  }
+}
+
+// 对象持有的分配记录，对象释放时归还给堆
+#[derive(Debug)]
+pub struct HeapAllocation {
+  heap: Weak<Heap>,
+  size: usize,
+}
+
+impl HeapAllocation {
+  pub fn new(heap: &Rc<Heap>, size: usize) -> HeapAllocation {
+    heap.charge(size);
+    HeapAllocation { heap: Rc::downgrade(heap), size }
+  }
+
+  // 对象增加属性时追加占用
+  pub fn grow(&mut self, size: usize) {
+    // 没有新增占用时不检查，已经超出限制时仍然可以释放引用
+    if size == 0 {
+      return;
+    }
+    if let Some(heap) = self.heap.upgrade() {
+      heap.charge(size);
+      self.size += size;
+    }
+  }
+
+  // 删除属性或替换掉字符串值时归还占用
+  pub fn shrink(&mut self, size: usize) {
+    let size = size.min(self.size);
+    if let Some(heap) = self.heap.upgrade() {
+      heap.release(size);
+      self.size -= size;
+    }
+  }
+
+  // 属性值替换时，计入新值并归还旧值占用的内存
+  pub fn replace_payload(&mut self, old: Option<&Value>, new: &Value) {
+    self.grow(value_payload_size(new));
+    if let Some(old) = old {
+      self.shrink(value_payload_size(old));
+    }
+  }
+}
+
+impl Clone for HeapAllocation {
+  fn clone(&self) -> Self {
+    match self.heap.upgrade() {
+      Some(heap) => HeapAllocation::new(&heap, self.size),
+      None => HeapAllocation { heap: Weak::new(), size: 0 },
+    }
+  }
+}
+
+impl Drop for HeapAllocation {
+  fn drop(&mut self) {
+    if let Some(heap) = self.heap.upgrade() {
+      heap.release(self.size);
+    }
+  }
+}
@@ -0,0 +1,335 @@
+// 类似 Node.js util.inspect 的值格式化，用于 console.log 以及在 Rust 中调试 Value
+// 不会调用 getter、toString 等 JS 方法，格式化过程中不会执行脚本
+use std::{cell::RefCell, collections::HashMap, rc::Rc};
+
+use crate::{ast_node::ClassType, builtins::{global::IS_GLOABL_OBJECT, map::get_map_entries, number::number_to_string, object::Object, promise::{PROMISE_FULFILLED_VALUE, PROMISE_REJECTED_REASON, PROMISE_STATE}}, constants::PROTO_PROPERTY_NAME, value::Value};
+
+#[derive(Debug, Clone)]
+pub struct InspectOptions {
+  // 展开的嵌套层数，超过时显示为 [Object]、[Array]
+  pub depth: usize,
+  // 单行显示的最大长度，超过时每个属性单独一行
+  pub break_length: usize,
+  // 数组、Map 最多显示的元素个数
+  pub max_array_length: usize,
+}
+
+impl Default for InspectOptions {
+  fn default() -> Self {
+    InspectOptions { depth: 2, break_length: 80, max_array_length: 100 }
+  }
+}
+
+pub fn inspect(value: &Value, options: &InspectOptions) -> String {
+  let mut inspector = Inspector { options, seen: vec![], circular: HashMap::new() };
+  inspector.format_value(value, 0, 0)
+}
+
+// 对象中除属性以外的内容，如数组元素、Map 的键值对
+enum Entry {
+  Value(Value),
+  Pair(Value, Value),
+  // 带前缀的值，如 <rejected> reason
+  Prefixed(&'static str, Value),
+  Text(String),
+}
+
+struct Inspector<'a> {
+  options: &'a InspectOptions,
+  // 正在格式化的对象，再次遇到时是循环引用
+  seen: Vec<*const RefCell<Object>>,
+  // 被循环引用的对象的编号
+  circular: HashMap<*const RefCell<Object>, usize>,
+}
+
+impl Inspector<'_> {
+  fn format_value(&mut self, value: &Value, depth: usize, indentation: usize) -> String {
+    match value {
+      Value::String(_) | Value::Utf16String(_) => quote_string(&value_to_string(value)),
+      Value::Number(number) => format_number(*number),
+      Value::Boolean(bool) => bool.to_string(),
+      Value::NAN => String::from("NaN"),
+      Value::Undefined => String::from("undefined"),
+      Value::Null => String::from("null"),
+      Value::Scope(_) => String::from("[Scope]"),
+      Value::Interrupt(_, _) | Value::ByteCode(_) => String::from("[Internal]"),
+      _ => match value.to_weak_rc_object().and_then(|object| object.upgrade()) {
+        Some(object) => self.format_object(&object, depth, indentation),
+        None => String::from("undefined"),
+      },
+    }
+  }
+
+  fn format_object(&mut self, object: &Rc<RefCell<Object>>, depth: usize, indentation: usize) -> String {
+    let ptr = Rc::as_ptr(object);
+    if self.seen.contains(&ptr) {
+      let next_id = self.circular.len() + 1;
+      let id = *self.circular.entry(ptr).or_insert(next_id);
+      return format!("[Circular *{}]", id);
+    }
+    let object_ref = match object.try_borrow() {
+      Ok(object_ref) => object_ref,
+      Err(_) => return String::from("[Object]"),
+    };
+    let class_type = object_ref.class_type.clone();
+    let is_global_object = matches!(object_ref.get_inner_property_value(IS_GLOABL_OBJECT.to_string()), Some(Value::Boolean(true)));
+    if class_type == ClassType::Function || is_global_object {
+      return format_function(&object_ref);
+    }
+    let class_name = get_class_name(&object_ref);
+    if depth > self.options.depth {
+      return match class_type {
+        ClassType::Array => String::from("[Array]"),
+        _ => format!("[{}]", class_name.unwrap_or_else(|| String::from("Object"))),
+      };
+    }
+
+    let mut entries: Vec<Entry> = vec![];
+    let mut skip_keys: Vec<String> = vec![];
+    let prefix;
+    let mut braces = ("{", "}");
+    match class_type {
+      ClassType::Array => {
+        let length = match object_ref.property.get("length").map(|property| &property.value) {
+          Some(Value::Number(length)) => *length as usize,
+          _ => 0,
+        };
+        let mut holes = 0;
+        for index in 0..length.min(self.options.max_array_length) {
+          match object_ref.property.get(&index.to_string()) {
+            Some(property) => {
+              if holes > 0 {
+                entries.push(Entry::Text(format_holes(holes)));
+                holes = 0;
+              }
+              entries.push(Entry::Value(property.value.clone()));
+            },
+            None => holes += 1,
+          }
+        }
+        if holes > 0 {
+          entries.push(Entry::Text(format_holes(holes)));
+        }
+        if length > self.options.max_array_length {
+          entries.push(Entry::Text(format!("... {} more item{}", length - self.options.max_array_length, plural(length - self.options.max_array_length))));
+        }
+        skip_keys.push(String::from("length"));
+        prefix = match class_name.as_deref() {
+          Some("Array") | None => String::new(),
+          Some(name) => format!("{}({}) ", name, length),
+        };
+        braces = ("[", "]");
+      },
+      ClassType::Map => {
+        let map_entries = get_map_entries(object);
+        prefix = format!("{}({}) ", class_name.unwrap_or_else(|| String::from("Map")), map_entries.len());
+        if map_entries.len() > self.options.max_array_length {
+          let more = map_entries.len() - self.options.max_array_length;
+          entries.extend(map_entries.into_iter().take(self.options.max_array_length).map(|(key, value)| Entry::Pair(key, value)));
+          entries.push(Entry::Text(format!("... {} more item{}", more, plural(more))));
+        } else {
+          entries.extend(map_entries.into_iter().map(|(key, value)| Entry::Pair(key, value)));
+        }
+        skip_keys.push(String::from("size"));
+      },
+      ClassType::Promise => {
+        let state = object_ref.get_inner_property_value(PROMISE_STATE.to_string());
+        let state = match state {
+          Some(Value::String(state)) => state,
+          _ => String::from("pending"),
+        };
+        match state.as_str() {
+          "fulfilled" => entries.push(Entry::Value(object_ref.get_inner_property_value(PROMISE_FULFILLED_VALUE.to_string()).unwrap_or(Value::Undefined))),
+          "rejected" => entries.push(Entry::Prefixed("<rejected>", object_ref.get_inner_property_value(PROMISE_REJECTED_REASON.to_string()).unwrap_or(Value::Undefined))),
+          _ => entries.push(Entry::Text(String::from("<pending>"))),
+        }
+        prefix = format!("{} ", class_name.unwrap_or_else(|| String::from("Promise")));
+      },
+      ClassType::Error => {
+        let text = match object_ref.property.get("stack").map(|property| &property.value) {
+          // 嵌套时调用栈的每一行都要缩进
+          Some(Value::String(stack)) => stack.replace('\n', &format!("\n{}", " ".repeat(indentation))),
+          _ => {
+            let name = class_name.unwrap_or_else(|| String::from("Error"));
+            match object_ref.property.get("message").map(|property| &property.value) {
+              Some(Value::String(message)) if !message.is_empty() => format!("[{}: {}]", name, message),
+              _ => format!("[{}]", name),
+            }
+          },
+        };
+        skip_keys.extend([String::from("stack"), String::from("message")]);
+        prefix = format!("{} ", text);
+        // 没有其他属性时只显示调用栈
+        if !object_ref.property_list.iter().any(|key| !skip_keys.contains(key) && object_ref.property.get(key).map(|property| property.enumerable).unwrap_or(false)) {
+          return text;
+        }
+      },
+      ClassType::String | ClassType::Number | ClassType::Boolean => {
+        let value = object_ref.get_inner_property_value(String::from("value")).unwrap_or(Value::Undefined);
+        let name = class_type.to_string();
+        let value = self.format_value(&value, depth, indentation);
+        let has_properties = object_ref.property_list.iter().any(|key| key != "length" && object_ref.property.get(key).map(|property| property.enumerable).unwrap_or(false));
+        if !has_properties {
+          return format!("[{}: {}]", name, value);
+        }
+        prefix = format!("[{}: {}] ", name, value);
+        skip_keys.push(String::from("length"));
+      },
+      _ => {
+        let has_prototype = object_ref.get_inner_property_value(PROTO_PROPERTY_NAME.to_string()).and_then(|proto| proto.to_weak_rc_object()).and_then(|proto| proto.upgrade()).is_some();
+        prefix = match class_name.as_deref() {
+          _ if !has_prototype => String::from("[Object: null prototype] "),
+          Some("Object") | None => String::new(),
+          Some(name) => format!("{} ", name),
+        };
+      },
+    }
+    drop(object_ref);
+    self.format_container(object, (&prefix, braces), entries, skip_keys, depth, indentation)
+  }
+
+  // 格式化 entries 以及对象的可枚举属性，skip_keys 是已经在 entries 中输出的属性
+  fn format_container(&mut self, object: &Rc<RefCell<Object>>, (prefix, braces): (&str, (&str, &str)), entries: Vec<Entry>, skip_keys: Vec<String>, depth: usize, indentation: usize) -> String {
+    let (properties, accessors) = {
+      let object_ref = object.borrow();
+      // 数组的元素已经在 entries 中
+      let is_array = object_ref.class_type == ClassType::Array;
+      // 和 Object.keys 相同，整数索引键按数值升序在前，其余按插入顺序
+      let properties: Vec<(String, Value)> = object_ref.get_own_property_keys().into_iter().filter(|key| {
+        !skip_keys.contains(key) && (!is_array || key.parse::<usize>().is_err())
+      }).filter_map(|key| {
+        let value = object_ref.property.get(&key).filter(|property| property.enumerable).map(|property| property.value.clone());
+        value.map(|value| (key, value))
+      }).collect();
+      let mut accessors: Vec<(String, &'static str)> = object_ref.accessor.iter().map(|(key, accessor)| {
+        let kind = match (&accessor.get, &accessor.set) {
+          (Some(_), Some(_)) => "[Getter/Setter]",
+          (Some(_), None) => "[Getter]",
+          _ => "[Setter]",
+        };
+        (key.clone(), kind)
+      }).collect();
+      accessors.sort();
+      (properties, accessors)
+    };
+
+    let ptr = Rc::as_ptr(object);
+    self.seen.push(ptr);
+    let mut output: Vec<String> = vec![];
+    for entry in entries.into_iter() {
+      output.push(match entry {
+        Entry::Value(value) => self.format_value(&value, depth + 1, indentation + 2),
+        Entry::Pair(key, value) => {
+          let key = self.format_value(&key, depth + 1, indentation + 2);
+          format!("{} => {}", key, self.format_value(&value, depth + 1, indentation + 2))
+        },
+        Entry::Prefixed(prefix, value) => format!("{} {}", prefix, self.format_value(&value, depth + 1, indentation + 2)),
+        Entry::Text(text) => text,
+      });
+    }
+    for (key, value) in properties.iter() {
+      let value = self.format_value(value, depth + 1, indentation + 2);
+      output.push(format!("{}: {}", format_key(key), value));
+    }
+    for (key, kind) in accessors.iter() {
+      output.push(format!("{}: {}", format_key(key), kind));
+    }
+    self.seen.pop();
+
+    // 和 Node.js 一样，<ref *1> 不计入单行的长度
+    let single_line_length = prefix.chars().count() + indentation + output.iter().map(|entry| entry.chars().count() + 2).sum::<usize>() + 2;
+    let prefix = match self.circular.get(&ptr) {
+      Some(id) => format!("<ref *{}> {}", id, prefix),
+      None => prefix.to_string(),
+    };
+    if output.is_empty() {
+      return format!("{}{}{}", prefix, braces.0, braces.1);
+    }
+    if single_line_length <= self.options.break_length && !output.iter().any(|entry| entry.contains('\n')) {
+      return format!("{}{} {} {}", prefix, braces.0, output.join(", "), braces.1);
+    }
+    let inner_indent = " ".repeat(indentation + 2);
+    format!("{}{}\n{}{}\n{}{}", prefix, braces.0, inner_indent, output.join(&format!(",\n{}", inner_indent)), " ".repeat(indentation), braces.1)
+  }
+}
+
+fn value_to_string(value: &Value) -> String {
+  match value {
+    Value::String(string) => string.clone(),
+    Value::Utf16String(units) => String::from_utf16_lossy(units),
+    _ => String::new(),
+  }
+}
+
+fn format_number(number: f64) -> String {
+  if number == 0f64 && number.is_sign_negative() {
+    return String::from("-0");
+  }
+  number_to_string(number)
+}
+
+fn plural(count: usize) -> &'static str {
+  if count == 1 { "" } else { "s" }
+}
+
+fn format_holes(count: usize) -> String {
+  format!("<{} empty item{}>", count, plural(count))
+}
+
+// 字符串默认使用单引号，包含单引号而不包含双引号时使用双引号
+fn quote_string(string: &str) -> String {
+  let quote = if string.contains('\'') && !string.contains('"') { '"' } else { '\'' };
+  let mut result = String::from(quote);
+  for char in string.chars() {
+    match char {
+      '\n' => result.push_str("\\n"),
+      '\t' => result.push_str("\\t"),
+      '\r' => result.push_str("\\r"),
+      '\\' => result.push_str("\\\\"),
+      _ if char == quote => {
+        result.push('\\');
+        result.push(char);
+      },
+      _ => result.push(char),
+    }
+  }
+  result.push(quote);
+  result
+}
+
+// 合法的标识符直接显示，其他的键加上引号
+fn format_key(key: &str) -> String {
+  let mut chars = key.chars();
+  let is_identifier = match chars.next() {
+    Some(first) => (first.is_alphabetic() || first == '_' || first == '$') && chars.all(|char| char.is_alphanumeric() || char == '_' || char == '$'),
+    None => false,
+  };
+  if is_identifier {
+    key.to_string()
+  } else {
+    quote_string(key)
+  }
+}
+
+fn get_name(object: &Object) -> Option<String> {
+  let name = object.property.get("name").map(|property| property.value.clone()).or_else(|| object.get_inner_property_value(String::from("name")));
+  match name {
+    Some(Value::String(name)) if !name.is_empty() => Some(name),
+    _ => None,
+  }
+}
+
+fn format_function(object: &Object) -> String {
+  match get_name(object) {
+    Some(name) => format!("[Function: {}]", name),
+    None => String::from("[Function (anonymous)]"),
+  }
+}
+
+// 通过原型上的 constructor 获取类名
+fn get_class_name(object: &Object) -> Option<String> {
+  let proto = object.get_inner_property_value(PROTO_PROPERTY_NAME.to_string())?.to_weak_rc_object()?.upgrade()?;
+  let constructor = proto.try_borrow().ok()?.property.get("constructor")?.value.to_weak_rc_object()?.upgrade()?;
+  let constructor = constructor.try_borrow().ok()?;
+  get_name(&constructor)
+}
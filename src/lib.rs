@@ -9,10 +9,29 @@ pub mod error;
 pub mod builtins;
 pub mod constants;
 pub mod bytecode;
+pub mod convert;
+pub mod native_class;
+pub mod inspect;
+pub mod heap;
+pub mod gc;
+pub mod builder;
+pub mod realm;
+#[cfg(feature = "serde")]
+pub mod serde_value;
 
+use std::{rc::Rc, time::Instant};
 use ast::Program;
-use context::Context;
-use error::JSIResult;
+use ast_node::CallContext;
+use builtins::function::host_function;
+use context::{Context, InterruptHandle};
+use convert::{FromJs, IntoJs, TypedFunction};
+use native_class::NativeClass;
+use builder::JSIBuilder;
+use builtins::console::ConsoleSink;
+use realm::RealmId;
+use heap::HeapStats;
+use inspect::InspectOptions;
+use error::{JSIError, JSIErrorType, JSIResult};
 use value::Value;
 pub struct JSI {
   context: Context,
@@ -26,10 +45,89 @@ impl JSI {
       }
   }
 
+  // 创建受限的运行环境，可以移除、替换全局变量或冻结内置对象
+  pub fn builder() -> JSIBuilder {
+    JSIBuilder::new()
+  }
+
   pub fn set_strict(&mut self,strict: bool) {
     self.context.set_strict(strict);
   }
 
+  // 每次执行（run、call 等）允许的操作数，None 表示不限制
+  pub fn set_operation_budget(&mut self, budget: Option<u64>) {
+    self.context.set_operation_budget(budget);
+  }
+
+  // 执行的截止时间，None 表示不限制
+  pub fn set_deadline(&mut self, deadline: Option<Instant>) {
+    self.context.set_deadline(deadline);
+  }
+
+  // 获取中断句柄，可以在其他线程中中断正在执行的脚本
+  pub fn interrupt_handle(&self) -> InterruptHandle {
+    self.context.get_interrupt_handle()
+  }
+
+  // 最大调用深度，超过时抛出 RangeError: Maximum call stack size exceeded
+  pub fn set_max_call_depth(&mut self, depth: usize) {
+    self.context.set_max_call_depth(depth);
+  }
+
+  // 解析时语句、表达式的最大嵌套深度
+  pub fn set_max_nesting_depth(&mut self, depth: usize) {
+    self.context.set_max_nesting_depth(depth);
+  }
+
+  // 每次执行允许使用的原生栈空间（字节），超过时抛出 RangeError，需要小于执行线程的栈大小
+  pub fn set_max_stack_size(&mut self, size: usize) {
+    self.context.set_max_stack_size(size);
+  }
+
+  // 堆内存上限（字节），包含内置对象占用的内存，超出时以 Out of memory 终止执行
+  pub fn set_heap_limit(&mut self, limit: Option<usize>) {
+    self.context.heap.set_limit(limit);
+  }
+
+  // 当前和峰值的堆内存占用
+  pub fn heap_stats(&self) -> HeapStats {
+    self.context.heap.stats()
+  }
+
+  // 回收循环引用的对象和作用域，返回回收的数量
+  pub fn gc(&mut self) -> usize {
+    gc::collect_cycles(&mut self.context)
+  }
+
+  // 创建新的领域，拥有独立的全局对象和内置对象，对象可以在领域之间传递
+  pub fn create_realm(&mut self) -> RealmId {
+    self.context.create_realm()
+  }
+
+  pub fn current_realm(&self) -> RealmId {
+    self.context.current_realm()
+  }
+
+  // 切换当前领域，之后的 run、get_global、set_global 等都在该领域中执行，返回切换前的领域
+  pub fn enter_realm(&mut self, realm: RealmId) -> RealmId {
+    self.context.enter_realm(realm)
+  }
+
+  // 在指定的领域中执行代码，不改变当前领域
+  pub fn run_in_realm(&mut self, realm: RealmId, code: String) -> JSIResult<Value> {
+    self.context.run_in_realm(realm, code)
+  }
+
+  // 设置 console 的输出目标，默认输出到 stdout 和 stderr
+  pub fn set_console_sink<S: ConsoleSink + 'static>(&mut self, sink: S) {
+    self.context.console.set_sink(Box::new(sink));
+  }
+
+  // 以 Node.js util.inspect 的格式显示值，用于调试
+  pub fn inspect(&self, value: &Value) -> String {
+    inspect::inspect(value, &InspectOptions::default())
+  }
+
   pub fn run(&mut self, code: String) -> JSIResult<Value> {
       return self.context.run(code)
   }
@@ -42,6 +140,108 @@ impl JSI {
     return self.context.parse(code)
   }
 
+  // 解析代码并收集所有的语法错误，用于编辑器和批量检查脚本
+  pub fn parse_with_recovery(&mut self, code: String) -> (Program, Vec<JSIError>) {
+    return self.context.parse_with_recovery(code)
+  }
+
+  // 创建宿主方法，length 为方法的 length 属性，闭包的参数依次为调用上下文（包含 this）、实参和 new.target
+  pub fn create_function<F>(&mut self, name: String, length: usize, fun: F) -> Value
+  where F: Fn(&mut CallContext, Vec<Value>, Value) -> JSIResult<Value> + 'static {
+    host_function(&mut self.context, name, length as f64, Rc::new(fun))
+  }
+
+  // 注册全局的宿主方法
+  pub fn register_function<F>(&mut self, name: String, fun: F)
+  where F: Fn(&mut CallContext, Vec<Value>, Value) -> JSIResult<Value> + 'static {
+    let function = self.create_function(name.clone(), 0, fun);
+    self.context.define_global_value(name, function);
+  }
+
+  // 注册参数和返回值带类型的全局方法，参数通过 FromJs 转换，转换失败时抛出 TypeError
+  pub fn register_typed_function<Args, F: TypedFunction<Args>>(&mut self, name: String, fun: F) {
+    let length = fun.length();
+    let function = host_function(&mut self.context, name.clone(), length as f64, fun.into_host_function());
+    self.context.define_global_value(name, function);
+  }
+
+  // 注册 Rust 结构体绑定的 class，返回构造方法
+  pub fn register_class<T: 'static>(&mut self, class: NativeClass<T>) -> Value {
+    let name = class.get_name();
+    let constructor = class.build(&mut self.context);
+    self.context.define_global_value(name, constructor.clone());
+    constructor
+  }
+
+  // 由 Rust 值创建 class 的实例
+  pub fn create_instance<T: 'static>(&mut self, class: &Value, data: T) -> JSIResult<Value> {
+    native_class::create_instance(&mut self.context, class, data)
+  }
+
+  // JS 值转换为 Rust 值
+  pub fn convert_from_js<T: FromJs>(&mut self, value: &Value) -> JSIResult<T> {
+    T::from_js(&mut self.context, value)
+  }
+
+  // Rust 值转换为 JS 值
+  pub fn convert_to_js<T: IntoJs>(&mut self, value: T) -> Value {
+    value.into_js(&mut self.context)
+  }
+
+  // 任意 Serialize 类型转换为 JS 值
+  #[cfg(feature = "serde")]
+  pub fn serialize_to_js<T: serde::Serialize + ?Sized>(&mut self, value: &T) -> JSIResult<Value> {
+    serde_value::to_value(&mut self.context, value)
+  }
+
+  // JS 值反序列化为 Rust 值
+  #[cfg(feature = "serde")]
+  pub fn deserialize_from_js<T: serde::de::DeserializeOwned>(&mut self, value: &Value) -> JSIResult<T> {
+    serde_value::from_value(&mut self.context, value)
+  }
+
+  // 在对象上挂载宿主方法
+  pub fn define_function<F>(&mut self, target: &Value, name: String, fun: F) -> JSIResult<()>
+  where F: Fn(&mut CallContext, Vec<Value>, Value) -> JSIResult<Value> + 'static {
+    if !target.is_object() {
+      return Err(JSIError::new(JSIErrorType::TypeError, format!("Cannot define property {} on non-object", name), 0, 0))
+    }
+    let function = self.create_function(name.clone(), 0, fun);
+    let object = target.to_object(&mut self.context);
+    object.borrow_mut().define_property(name, builtins::object::Property {
+      enumerable: true,
+      value: function,
+    });
+    Ok(())
+  }
+
+  // 获取全局变量，不存在时返回 undefined
+  pub fn get_global(&mut self, name: String) -> Value {
+    self.context.get_global_value(name)
+  }
+
+  pub fn set_global(&mut self, name: String, value: Value) {
+    self.context.define_global_value(name, value);
+  }
+
+  // 调用 JS 方法，等同于 function.call(this, ...args)
+  pub fn call(&mut self, function: &Value, this: Value, args: Vec<Value>) -> JSIResult<Value> {
+    if let Value::Function(function_define) = function {
+      self.context.begin_execution();
+      let result = self.context.call_function_object(Rc::clone(function_define), Some(this), None, args);
+      return self.context.finish_execution(result)
+    }
+    let name = function.to_string(&mut self.context);
+    Err(JSIError::new(JSIErrorType::TypeError, format!("{} is not a function", name), 0, 0))
+  }
+
+  // 调用构造函数，等同于 new ctor(...args)
+  pub fn construct(&mut self, ctor: &Value, args: Vec<Value>) -> JSIResult<Value> {
+    self.context.begin_execution();
+    let result = self.context.construct(ctor, args);
+    self.context.finish_execution(result)
+  }
+
   pub fn dump_byte_code(&mut self, code: String) -> JSIResult<String> {
     return self.context.dump_byte_code(code)
   }
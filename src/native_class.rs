@@ -0,0 +1,189 @@
+// 把 Rust 结构体绑定为 JS 的 class
+// 实例的 Rust 值作为宿主数据保存在 JS 对象上，方法、getter、setter 定义在 prototype 上
+use std::{any::Any, cell::RefCell, rc::Rc};
+
+use crate::{ast_node::{CallContext, ClassType, HostFunction}, builtins::{function::host_function, object::{create_object, Accessor, HostData, Property}}, constants::PROTO_PROPERTY_NAME, context::Context, convert::{FromJs, IntoJs}, error::{JSIError, JSIErrorType, JSIResult}, value::Value};
+
+type NativeConstructor<T> = Rc<dyn Fn(&mut CallContext, Vec<Value>) -> JSIResult<T>>;
+type NativeMethod<T> = Rc<dyn Fn(&mut T, &mut CallContext, Vec<Value>) -> JSIResult<Value>>;
+type NativeGetter<T> = Rc<dyn Fn(&T, &mut Context) -> Value>;
+type NativeSetter<T> = Rc<dyn Fn(&mut T, &mut Context, &Value) -> JSIResult<()>>;
+
+pub struct NativeClass<T: 'static> {
+  name: String,
+  length: usize,
+  constructor: Option<NativeConstructor<T>>,
+  methods: Vec<(String, usize, NativeMethod<T>)>,
+  getters: Vec<(String, NativeGetter<T>)>,
+  setters: Vec<(String, NativeSetter<T>)>,
+}
+
+impl<T: 'static> NativeClass<T> {
+  pub fn new(name: &str) -> NativeClass<T> {
+    NativeClass {
+      name: name.to_string(),
+      length: 0,
+      constructor: None,
+      methods: vec![],
+      getters: vec![],
+      setters: vec![],
+    }
+  }
+
+  pub fn get_name(&self) -> String {
+    self.name.clone()
+  }
+
+  // 构造方法，没有构造方法时 JS 中不能 new，只能通过 create_instance 创建实例
+  pub fn constructor<F>(mut self, length: usize, fun: F) -> Self
+  where F: Fn(&mut CallContext, Vec<Value>) -> JSIResult<T> + 'static {
+    self.length = length;
+    self.constructor = Some(Rc::new(fun));
+    self
+  }
+
+  pub fn method<F>(mut self, name: &str, length: usize, fun: F) -> Self
+  where F: Fn(&mut T, &mut CallContext, Vec<Value>) -> JSIResult<Value> + 'static {
+    self.methods.push((name.to_string(), length, Rc::new(fun)));
+    self
+  }
+
+  pub fn getter<R: IntoJs, F>(mut self, name: &str, fun: F) -> Self
+  where F: Fn(&T) -> R + 'static {
+    self.getters.push((name.to_string(), Rc::new(move |data, ctx| fun(data).into_js(ctx))));
+    self
+  }
+
+  // setter 的参数通过 FromJs 转换，转换失败时抛出 TypeError
+  pub fn setter<A: FromJs, F>(mut self, name: &str, fun: F) -> Self
+  where F: Fn(&mut T, A) -> JSIResult<()> + 'static {
+    self.setters.push((name.to_string(), Rc::new(move |data, ctx, value| {
+      let value = A::from_js(ctx, value)?;
+      fun(data, value)
+    })));
+    self
+  }
+
+  // 创建构造方法，返回的构造方法需要挂载到全局或者其他对象上
+  pub fn build(self, ctx: &mut Context) -> Value {
+    let class_name = self.name.clone();
+    let constructor = self.constructor.clone();
+    let constructor_fun: HostFunction = Rc::new(move |call_ctx, args, new_target| {
+      if let Value::Undefined = new_target {
+        return Err(JSIError::new(JSIErrorType::TypeError, format!("Class constructor {} cannot be invoked without 'new'", class_name), 0, 0));
+      }
+      let constructor = match &constructor {
+        Some(constructor) => constructor,
+        None => return Err(JSIError::new(JSIErrorType::TypeError, format!("{} is not a constructor", class_name), 0, 0)),
+      };
+      let data = (constructor)(call_ctx, args)?;
+      let this = call_ctx.this.to_object(call_ctx.ctx);
+      this.borrow_mut().host_data = Some(HostData(Rc::new(RefCell::new(data))));
+      Ok(Value::Undefined)
+    });
+    let class = host_function(ctx, self.name.clone(), self.length as f64, constructor_fun);
+    let prototype = match &class {
+      Value::Function(function) => function.borrow().prototype.clone().unwrap(),
+      _ => unreachable!(),
+    };
+
+    for (name, length, method) in self.methods.into_iter() {
+      let class_name = self.name.clone();
+      let method_name = name.clone();
+      let fun: HostFunction = Rc::new(move |call_ctx, args, _| {
+        let data = get_host_data(&call_ctx.this, &class_name, &method_name)?;
+        let mut data = data.try_borrow_mut().map_err(|_| already_borrowed_error(&class_name))?;
+        let data = data.downcast_mut::<T>().ok_or_else(|| incompatible_receiver_error(&class_name, &method_name))?;
+        (method)(data, call_ctx, args)
+      });
+      let value = host_function(ctx, name.clone(), length as f64, fun);
+      // class 的方法不可枚举
+      prototype.borrow_mut().define_property(name, Property { enumerable: false, value });
+    }
+
+    let mut accessors: Vec<(String, Accessor)> = vec![];
+    for (name, getter) in self.getters.into_iter() {
+      let class_name = self.name.clone();
+      let getter_name = name.clone();
+      let fun: HostFunction = Rc::new(move |call_ctx, _, _| {
+        let data = get_host_data(&call_ctx.this, &class_name, &getter_name)?;
+        let data = data.try_borrow().map_err(|_| already_borrowed_error(&class_name))?;
+        let data = data.downcast_ref::<T>().ok_or_else(|| incompatible_receiver_error(&class_name, &getter_name))?;
+        Ok((getter)(data, call_ctx.ctx))
+      });
+      let value = host_function(ctx, format!("get {}", name), 0f64, fun);
+      accessors.push((name, Accessor { get: Some(value), set: None, enumerable: false }));
+    }
+    for (name, setter) in self.setters.into_iter() {
+      let class_name = self.name.clone();
+      let setter_name = name.clone();
+      let fun: HostFunction = Rc::new(move |call_ctx, args, _| {
+        let data = get_host_data(&call_ctx.this, &class_name, &setter_name)?;
+        let mut data = data.try_borrow_mut().map_err(|_| already_borrowed_error(&class_name))?;
+        let data = data.downcast_mut::<T>().ok_or_else(|| incompatible_receiver_error(&class_name, &setter_name))?;
+        (setter)(data, call_ctx.ctx, args.first().unwrap_or(&Value::Undefined))?;
+        Ok(Value::Undefined)
+      });
+      let value = host_function(ctx, format!("set {}", name), 1f64, fun);
+      match accessors.iter_mut().find(|(accessor_name, _)| accessor_name == &name) {
+        Some((_, accessor)) => accessor.set = Some(value),
+        None => accessors.push((name, Accessor { get: None, set: Some(value), enumerable: false })),
+      }
+    }
+    for (name, accessor) in accessors.into_iter() {
+      prototype.borrow_mut().define_accessor(name, accessor);
+    }
+    class
+  }
+}
+
+fn get_host_data(this: &Value, class_name: &str, name: &str) -> JSIResult<Rc<RefCell<dyn Any>>> {
+  let object = this.to_weak_rc_object().and_then(|object| object.upgrade());
+  if let Some(object) = object {
+    if let Some(HostData(data)) = &object.borrow().host_data {
+      return Ok(Rc::clone(data));
+    }
+  }
+  Err(incompatible_receiver_error(class_name, name))
+}
+
+fn incompatible_receiver_error(class_name: &str, name: &str) -> JSIError {
+  JSIError::new(JSIErrorType::TypeError, format!("Method {}.prototype.{} called on incompatible receiver", class_name, name), 0, 0)
+}
+
+// 方法执行过程中再次访问同一个实例的宿主数据
+fn already_borrowed_error(class_name: &str) -> JSIError {
+  JSIError::new(JSIErrorType::TypeError, format!("{} instance is already in use", class_name), 0, 0)
+}
+
+// 由 Rust 值创建 class 的实例，用于宿主方法返回新的实例
+pub fn create_instance<T: 'static>(ctx: &mut Context, class: &Value, data: T) -> JSIResult<Value> {
+  let prototype = match class {
+    Value::Function(function) => function.borrow().prototype.clone(),
+    _ => None,
+  };
+  let prototype = match prototype {
+    Some(prototype) => prototype,
+    None => return Err(JSIError::new(JSIErrorType::TypeError, String::from("Class must be a constructor"), 0, 0)),
+  };
+  let object = create_object(ctx, ClassType::Object, None);
+  {
+    let mut object_mut = object.borrow_mut();
+    object_mut.set_inner_property_value(PROTO_PROPERTY_NAME.to_string(), Value::RefObject(Rc::downgrade(&prototype)));
+    object_mut.host_data = Some(HostData(Rc::new(RefCell::new(data))));
+  }
+  Ok(Value::Object(object))
+}
+
+// 读取实例上的宿主数据，类型不匹配时返回 TypeError
+pub fn with_host_data<T: 'static, R, F: FnOnce(&mut T) -> R>(value: &Value, fun: F) -> JSIResult<R> {
+  let object = value.to_weak_rc_object().and_then(|object| object.upgrade());
+  let data = object.and_then(|object| object.borrow().host_data.clone());
+  if let Some(HostData(data)) = data {
+    let mut data = data.try_borrow_mut().map_err(|_| JSIError::new(JSIErrorType::TypeError, String::from("Host data is already in use"), 0, 0))?;
+    if let Some(data) = data.downcast_mut::<T>() {
+      return Ok(fun(data));
+    }
+  }
+  Err(JSIError::new(JSIErrorType::TypeError, String::from("Value does not hold the requested host data"), 0, 0))
+}
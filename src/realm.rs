@@ -0,0 +1,16 @@
+// 领域（realm）：全局对象、全局作用域以及内置对象
+// 同一个 Context 中的多个领域共享堆和执行限制，对象可以直接在领域之间传递
+// 和浏览器中的 iframe 一样，不同领域的内置对象互不相同，如 instanceof Array 只对当前领域的数组成立
+use std::{cell::RefCell, collections::HashMap, rc::Rc};
+
+use crate::{builtins::object::Object, scope::Scope};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct RealmId(pub(crate) usize);
+
+#[derive(Clone)]
+pub(crate) struct Realm {
+  pub global: Rc<RefCell<Object>>,
+  pub scope: Rc<RefCell<Scope>>,
+  pub intrinsics: Rc<HashMap<String, Rc<RefCell<Object>>>>,
+}
@@ -1,18 +1,20 @@
-use std::{collections::HashMap, cell::RefCell, rc::Rc};
+use std::{collections::HashMap, cell::RefCell, mem::size_of, rc::Rc};
 
-use crate::{bytecode::ByteCode, value::{Value, ValueInfo}};
+use crate::{bytecode::ByteCode, heap::{value_payload_size, HeapAllocation}, value::{Value, ValueInfo}};
 // 上下文环境
 #[derive(Debug, Clone)]
 pub struct Scope {
   pub id: i32,
   pub parent: Option<Rc<RefCell<Scope>>>,
+  // 调用时的来源作用域，退出作用域后断开
   pub from: Option<Rc<RefCell<Scope>>>,
-  pub childs: Vec<Rc<RefCell<Scope>>>,
   pub labels: Vec<String>,
   // 当前上下文的 this
   pub this: Option<Value>,
   variables: HashMap<String, VariableInfo>,
   pub function_call_args: Vec<ValueInfo>,
+  // 堆内存占用记录，执行时创建的作用域才有
+  pub heap_allocation: Option<HeapAllocation>,
 }
 
 
@@ -27,22 +29,46 @@ impl Scope {
   pub fn new() -> Scope {
     Scope {
       id: 0,
-      childs: vec![],
       parent: None,
       from: None,
       this: None,
       labels: vec![],
       variables: HashMap::new(),
       function_call_args: vec![],
+      heap_allocation: None,
     }
   }
 
+  // 新增变量时追加占用的内存，变量值为字符串时计入字符串的内容
+  fn insert_variable(&mut self, name: String, variable: VariableInfo) {
+    if let Some(allocation) = &mut self.heap_allocation {
+      if !self.variables.contains_key(&name) {
+        allocation.grow(size_of::<String>() + size_of::<VariableInfo>() + name.len());
+      }
+      allocation.replace_payload(self.variables.get(&name).map(|old| &old.value), &variable.value);
+    }
+    self.variables.insert(name, variable);
+  }
+
   pub fn set_value(&mut self, name: String, value: Value, is_const: bool) {
-    self.variables.insert(name, VariableInfo{value, is_const, bytecode: vec![]});
+    self.insert_variable(name, VariableInfo{value, is_const, bytecode: vec![]});
+  }
+
+  pub fn remove_value(&mut self, name: &str) {
+    if let Some(variable) = self.variables.remove(name) {
+      if let Some(allocation) = &mut self.heap_allocation {
+        allocation.shrink(size_of::<String>() + size_of::<VariableInfo>() + name.len() + value_payload_size(&variable.value));
+      }
+    }
+  }
+
+  // 作用域中保存的变量值，用于垃圾回收遍历
+  pub fn variable_values(&self) -> impl Iterator<Item = &Value> {
+    self.variables.values().map(|variable| &variable.value)
   }
 
   pub fn set_bytecode(&mut self, name: String, value: Value, is_const: bool, bytecode: Vec<ByteCode>) {
-    self.variables.insert(name, VariableInfo{value, is_const, bytecode});
+    self.insert_variable(name, VariableInfo{value, is_const, bytecode});
   }
 }
 
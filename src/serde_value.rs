@@ -0,0 +1,500 @@
+// serde 支持，需要开启 serde feature
+// 任意 Serialize 类型可以转换为 JS 的对象、数组，JS 值也可以反序列化为 Rust 结构体
+use std::{cell::RefCell, fmt, rc::Rc};
+
+use serde::{de::{self, value::{MapDeserializer, SeqDeserializer}, DeserializeOwned, IntoDeserializer, Visitor}, forward_to_deserialize_any, ser::{self, Serialize}};
+
+use crate::{ast_node::ClassType, builtins::{array::{create_array_from_values, get_iterable_values}, object::{create_object, Object, Property}}, context::Context, error::{JSIError, JSIErrorType, JSIResult}, value::Value};
+
+impl ser::Error for JSIError {
+  fn custom<T: fmt::Display>(msg: T) -> Self {
+    JSIError::new(JSIErrorType::TypeError, msg.to_string(), 0, 0)
+  }
+}
+
+impl de::Error for JSIError {
+  fn custom<T: fmt::Display>(msg: T) -> Self {
+    JSIError::new(JSIErrorType::TypeError, msg.to_string(), 0, 0)
+  }
+}
+
+// Rust 值序列化为 JS 值
+pub fn to_value<T: Serialize + ?Sized>(ctx: &mut Context, value: &T) -> JSIResult<Value> {
+  value.serialize(&mut ValueSerializer { ctx })
+}
+
+// JS 值反序列化为 Rust 值
+pub fn from_value<T: DeserializeOwned>(ctx: &mut Context, value: &Value) -> JSIResult<T> {
+  let node = ValueNode::from_value(ctx, value, &mut vec![])?;
+  T::deserialize(node)
+}
+
+fn create_object_from_entries(ctx: &mut Context, entries: Vec<(String, Value)>) -> Value {
+  let object = create_object(ctx, ClassType::Object, None);
+  {
+    let mut object_mut = object.borrow_mut();
+    for (key, value) in entries {
+      object_mut.define_property(key, Property { enumerable: true, value });
+    }
+  }
+  Value::Object(object)
+}
+
+// enum 的变体按照 { variant: value } 的形式表示，和 serde_json 一致
+fn wrap_variant(ctx: &mut Context, variant: Option<&'static str>, value: Value) -> Value {
+  match variant {
+    Some(variant) => create_object_from_entries(ctx, vec![(variant.to_string(), value)]),
+    None => value,
+  }
+}
+
+struct ValueSerializer<'a> {
+  ctx: &'a mut Context,
+}
+
+struct SeqSerializer<'a, 'b> {
+  ser: &'b mut ValueSerializer<'a>,
+  variant: Option<&'static str>,
+  values: Vec<Value>,
+}
+
+struct MapSerializer<'a, 'b> {
+  ser: &'b mut ValueSerializer<'a>,
+  variant: Option<&'static str>,
+  entries: Vec<(String, Value)>,
+  next_key: Option<String>,
+}
+
+impl<'a, 'b> ser::Serializer for &'b mut ValueSerializer<'a> {
+  type Ok = Value;
+  type Error = JSIError;
+  type SerializeSeq = SeqSerializer<'a, 'b>;
+  type SerializeTuple = SeqSerializer<'a, 'b>;
+  type SerializeTupleStruct = SeqSerializer<'a, 'b>;
+  type SerializeTupleVariant = SeqSerializer<'a, 'b>;
+  type SerializeMap = MapSerializer<'a, 'b>;
+  type SerializeStruct = MapSerializer<'a, 'b>;
+  type SerializeStructVariant = MapSerializer<'a, 'b>;
+
+  fn serialize_bool(self, v: bool) -> JSIResult<Value> {
+    Ok(Value::Boolean(v))
+  }
+
+  fn serialize_i8(self, v: i8) -> JSIResult<Value> {
+    self.serialize_f64(v as f64)
+  }
+
+  fn serialize_i16(self, v: i16) -> JSIResult<Value> {
+    self.serialize_f64(v as f64)
+  }
+
+  fn serialize_i32(self, v: i32) -> JSIResult<Value> {
+    self.serialize_f64(v as f64)
+  }
+
+  fn serialize_i64(self, v: i64) -> JSIResult<Value> {
+    self.serialize_f64(v as f64)
+  }
+
+  fn serialize_u8(self, v: u8) -> JSIResult<Value> {
+    self.serialize_f64(v as f64)
+  }
+
+  fn serialize_u16(self, v: u16) -> JSIResult<Value> {
+    self.serialize_f64(v as f64)
+  }
+
+  fn serialize_u32(self, v: u32) -> JSIResult<Value> {
+    self.serialize_f64(v as f64)
+  }
+
+  fn serialize_u64(self, v: u64) -> JSIResult<Value> {
+    self.serialize_f64(v as f64)
+  }
+
+  fn serialize_f32(self, v: f32) -> JSIResult<Value> {
+    self.serialize_f64(v as f64)
+  }
+
+  fn serialize_f64(self, v: f64) -> JSIResult<Value> {
+    if v.is_nan() {
+      return Ok(Value::NAN);
+    }
+    Ok(Value::Number(v))
+  }
+
+  fn serialize_char(self, v: char) -> JSIResult<Value> {
+    Ok(Value::String(v.to_string()))
+  }
+
+  fn serialize_str(self, v: &str) -> JSIResult<Value> {
+    Ok(Value::String(v.to_string()))
+  }
+
+  fn serialize_bytes(self, v: &[u8]) -> JSIResult<Value> {
+    let values = v.iter().map(|byte| Value::Number(*byte as f64)).collect();
+    Ok(create_array_from_values(self.ctx, values))
+  }
+
+  fn serialize_none(self) -> JSIResult<Value> {
+    Ok(Value::Null)
+  }
+
+  fn serialize_some<T: Serialize + ?Sized>(self, value: &T) -> JSIResult<Value> {
+    value.serialize(self)
+  }
+
+  fn serialize_unit(self) -> JSIResult<Value> {
+    Ok(Value::Null)
+  }
+
+  fn serialize_unit_struct(self, _: &'static str) -> JSIResult<Value> {
+    Ok(Value::Null)
+  }
+
+  fn serialize_unit_variant(self, _: &'static str, _: u32, variant: &'static str) -> JSIResult<Value> {
+    Ok(Value::String(variant.to_string()))
+  }
+
+  fn serialize_newtype_struct<T: Serialize + ?Sized>(self, _: &'static str, value: &T) -> JSIResult<Value> {
+    value.serialize(self)
+  }
+
+  fn serialize_newtype_variant<T: Serialize + ?Sized>(self, _: &'static str, _: u32, variant: &'static str, value: &T) -> JSIResult<Value> {
+    let value = value.serialize(&mut *self)?;
+    Ok(wrap_variant(self.ctx, Some(variant), value))
+  }
+
+  fn serialize_seq(self, len: Option<usize>) -> JSIResult<Self::SerializeSeq> {
+    Ok(SeqSerializer { ser: self, variant: None, values: Vec::with_capacity(len.unwrap_or(0)) })
+  }
+
+  fn serialize_tuple(self, len: usize) -> JSIResult<Self::SerializeTuple> {
+    self.serialize_seq(Some(len))
+  }
+
+  fn serialize_tuple_struct(self, _: &'static str, len: usize) -> JSIResult<Self::SerializeTupleStruct> {
+    self.serialize_seq(Some(len))
+  }
+
+  fn serialize_tuple_variant(self, _: &'static str, _: u32, variant: &'static str, len: usize) -> JSIResult<Self::SerializeTupleVariant> {
+    Ok(SeqSerializer { ser: self, variant: Some(variant), values: Vec::with_capacity(len) })
+  }
+
+  fn serialize_map(self, _: Option<usize>) -> JSIResult<Self::SerializeMap> {
+    Ok(MapSerializer { ser: self, variant: None, entries: vec![], next_key: None })
+  }
+
+  fn serialize_struct(self, _: &'static str, _: usize) -> JSIResult<Self::SerializeStruct> {
+    self.serialize_map(None)
+  }
+
+  fn serialize_struct_variant(self, _: &'static str, _: u32, variant: &'static str, _: usize) -> JSIResult<Self::SerializeStructVariant> {
+    Ok(MapSerializer { ser: self, variant: Some(variant), entries: vec![], next_key: None })
+  }
+}
+
+impl<'a, 'b> SeqSerializer<'a, 'b> {
+  fn push<T: Serialize + ?Sized>(&mut self, value: &T) -> JSIResult<()> {
+    let value = value.serialize(&mut *self.ser)?;
+    self.values.push(value);
+    Ok(())
+  }
+
+  fn finish(self) -> JSIResult<Value> {
+    let array = create_array_from_values(self.ser.ctx, self.values);
+    Ok(wrap_variant(self.ser.ctx, self.variant, array))
+  }
+}
+
+impl<'a, 'b> ser::SerializeSeq for SeqSerializer<'a, 'b> {
+  type Ok = Value;
+  type Error = JSIError;
+
+  fn serialize_element<T: Serialize + ?Sized>(&mut self, value: &T) -> JSIResult<()> {
+    self.push(value)
+  }
+
+  fn end(self) -> JSIResult<Value> {
+    self.finish()
+  }
+}
+
+impl<'a, 'b> ser::SerializeTuple for SeqSerializer<'a, 'b> {
+  type Ok = Value;
+  type Error = JSIError;
+
+  fn serialize_element<T: Serialize + ?Sized>(&mut self, value: &T) -> JSIResult<()> {
+    self.push(value)
+  }
+
+  fn end(self) -> JSIResult<Value> {
+    self.finish()
+  }
+}
+
+impl<'a, 'b> ser::SerializeTupleStruct for SeqSerializer<'a, 'b> {
+  type Ok = Value;
+  type Error = JSIError;
+
+  fn serialize_field<T: Serialize + ?Sized>(&mut self, value: &T) -> JSIResult<()> {
+    self.push(value)
+  }
+
+  fn end(self) -> JSIResult<Value> {
+    self.finish()
+  }
+}
+
+impl<'a, 'b> ser::SerializeTupleVariant for SeqSerializer<'a, 'b> {
+  type Ok = Value;
+  type Error = JSIError;
+
+  fn serialize_field<T: Serialize + ?Sized>(&mut self, value: &T) -> JSIResult<()> {
+    self.push(value)
+  }
+
+  fn end(self) -> JSIResult<Value> {
+    self.finish()
+  }
+}
+
+impl<'a, 'b> MapSerializer<'a, 'b> {
+  fn push_field<T: Serialize + ?Sized>(&mut self, key: &str, value: &T) -> JSIResult<()> {
+    let value = value.serialize(&mut *self.ser)?;
+    self.entries.push((key.to_string(), value));
+    Ok(())
+  }
+
+  fn finish(self) -> JSIResult<Value> {
+    let object = create_object_from_entries(self.ser.ctx, self.entries);
+    Ok(wrap_variant(self.ser.ctx, self.variant, object))
+  }
+}
+
+impl<'a, 'b> ser::SerializeMap for MapSerializer<'a, 'b> {
+  type Ok = Value;
+  type Error = JSIError;
+
+  fn serialize_key<T: Serialize + ?Sized>(&mut self, key: &T) -> JSIResult<()> {
+    // 对象的 key 只能是字符串，数字和布尔值按字符串处理
+    let key = match key.serialize(&mut *self.ser)? {
+      key @ (Value::String(_) | Value::Number(_) | Value::Boolean(_)) => key.to_string(self.ser.ctx),
+      _ => return Err(JSIError::new(JSIErrorType::TypeError, String::from("Object key must be a string"), 0, 0)),
+    };
+    self.next_key = Some(key);
+    Ok(())
+  }
+
+  fn serialize_value<T: Serialize + ?Sized>(&mut self, value: &T) -> JSIResult<()> {
+    let key = self.next_key.take().unwrap_or_default();
+    self.push_field(&key, value)
+  }
+
+  fn end(self) -> JSIResult<Value> {
+    self.finish()
+  }
+}
+
+impl<'a, 'b> ser::SerializeStruct for MapSerializer<'a, 'b> {
+  type Ok = Value;
+  type Error = JSIError;
+
+  fn serialize_field<T: Serialize + ?Sized>(&mut self, key: &'static str, value: &T) -> JSIResult<()> {
+    self.push_field(key, value)
+  }
+
+  fn end(self) -> JSIResult<Value> {
+    self.finish()
+  }
+}
+
+impl<'a, 'b> ser::SerializeStructVariant for MapSerializer<'a, 'b> {
+  type Ok = Value;
+  type Error = JSIError;
+
+  fn serialize_field<T: Serialize + ?Sized>(&mut self, key: &'static str, value: &T) -> JSIResult<()> {
+    self.push_field(key, value)
+  }
+
+  fn end(self) -> JSIResult<Value> {
+    self.finish()
+  }
+}
+
+// 反序列化前先把 JS 值展开成不依赖 Context 的树
+enum ValueNode {
+  Undefined,
+  Null,
+  Boolean(bool),
+  Number(f64),
+  String(String),
+  Array(Vec<ValueNode>),
+  Object(Vec<(String, ValueNode)>),
+}
+
+impl ValueNode {
+  fn from_value(ctx: &mut Context, value: &Value, visiting: &mut Vec<*const RefCell<Object>>) -> JSIResult<ValueNode> {
+    if let Some(primitive) = value.to_primitive_value(ctx) {
+      return ValueNode::from_value(ctx, &primitive, visiting);
+    }
+    match value {
+      Value::Null => Ok(ValueNode::Null),
+      Value::Boolean(boolean) => Ok(ValueNode::Boolean(*boolean)),
+      Value::Number(number) => Ok(ValueNode::Number(*number)),
+      Value::NAN => Ok(ValueNode::Number(f64::NAN)),
+      Value::String(str) => Ok(ValueNode::String(str.clone())),
+      Value::Utf16String(units) => Ok(ValueNode::String(String::from_utf16_lossy(units))),
+      Value::Array(array) => {
+        let values = get_iterable_values(ctx, value).unwrap_or_default();
+        ValueNode::from_object(ctx, array, visiting, |ctx, visiting| {
+          let nodes = values.iter().map(|value| ValueNode::from_value(ctx, value, visiting)).collect::<JSIResult<Vec<ValueNode>>>()?;
+          Ok(ValueNode::Array(nodes))
+        })
+      },
+      Value::Object(object) | Value::Promise(object) => {
+        let entries = {
+          let object = object.borrow();
+          object.property_list.iter().filter_map(|key| {
+            object.property.get(key).filter(|property| property.enumerable).map(|property| (key.clone(), property.value.clone()))
+          }).collect::<Vec<(String, Value)>>()
+        };
+        ValueNode::from_object(ctx, object, visiting, |ctx, visiting| {
+          let mut nodes = vec![];
+          for (key, value) in entries.iter() {
+            // 和 JSON.stringify 一样忽略值为 undefined 和方法的属性
+            match ValueNode::from_value(ctx, value, visiting)? {
+              ValueNode::Undefined => {},
+              node => nodes.push((key.clone(), node)),
+            }
+          }
+          Ok(ValueNode::Object(nodes))
+        })
+      },
+      Value::RefObject(object) => {
+        match object.upgrade() {
+          Some(object) => ValueNode::from_value(ctx, &Value::Object(object), visiting),
+          None => Ok(ValueNode::Undefined),
+        }
+      },
+      _ => Ok(ValueNode::Undefined),
+    }
+  }
+
+  // 记录正在访问的对象，遇到循环引用时报错
+  fn from_object<F>(ctx: &mut Context, object: &Rc<RefCell<Object>>, visiting: &mut Vec<*const RefCell<Object>>, convert: F) -> JSIResult<ValueNode>
+  where F: FnOnce(&mut Context, &mut Vec<*const RefCell<Object>>) -> JSIResult<ValueNode> {
+    let pointer = Rc::as_ptr(object);
+    if visiting.contains(&pointer) {
+      return Err(JSIError::new(JSIErrorType::TypeError, String::from("Converting circular structure"), 0, 0));
+    }
+    visiting.push(pointer);
+    let node = convert(ctx, visiting);
+    visiting.pop();
+    node
+  }
+}
+
+impl<'de> IntoDeserializer<'de, JSIError> for ValueNode {
+  type Deserializer = ValueNode;
+
+  fn into_deserializer(self) -> ValueNode {
+    self
+  }
+}
+
+impl<'de> de::Deserializer<'de> for ValueNode {
+  type Error = JSIError;
+
+  fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> JSIResult<V::Value> {
+    match self {
+      ValueNode::Undefined | ValueNode::Null => visitor.visit_unit(),
+      ValueNode::Boolean(boolean) => visitor.visit_bool(boolean),
+      ValueNode::Number(number) => {
+        // 整数交给 visit_i64 / visit_u64，方便反序列化为整数类型
+        if number.fract() == 0.0 && number >= i64::MIN as f64 && number < i64::MAX as f64 {
+          if number >= 0.0 {
+            return visitor.visit_u64(number as u64);
+          }
+          return visitor.visit_i64(number as i64);
+        }
+        visitor.visit_f64(number)
+      },
+      ValueNode::String(str) => visitor.visit_string(str),
+      ValueNode::Array(nodes) => {
+        let mut deserializer = SeqDeserializer::new(nodes.into_iter());
+        let value = visitor.visit_seq(&mut deserializer)?;
+        deserializer.end()?;
+        Ok(value)
+      },
+      ValueNode::Object(entries) => {
+        let mut deserializer = MapDeserializer::new(entries.into_iter());
+        let value = visitor.visit_map(&mut deserializer)?;
+        deserializer.end()?;
+        Ok(value)
+      },
+    }
+  }
+
+  fn deserialize_option<V: Visitor<'de>>(self, visitor: V) -> JSIResult<V::Value> {
+    match self {
+      ValueNode::Undefined | ValueNode::Null => visitor.visit_none(),
+      _ => visitor.visit_some(self),
+    }
+  }
+
+  fn deserialize_newtype_struct<V: Visitor<'de>>(self, _: &'static str, visitor: V) -> JSIResult<V::Value> {
+    visitor.visit_newtype_struct(self)
+  }
+
+  fn deserialize_enum<V: Visitor<'de>>(self, _: &'static str, _: &'static [&'static str], visitor: V) -> JSIResult<V::Value> {
+    match self {
+      ValueNode::String(variant) => visitor.visit_enum(variant.into_deserializer()),
+      ValueNode::Object(mut entries) if entries.len() == 1 => {
+        let (variant, value) = entries.remove(0);
+        visitor.visit_enum(EnumNode { variant, value })
+      },
+      _ => Err(JSIError::new(JSIErrorType::TypeError, String::from("Expected string or object with a single key for enum"), 0, 0)),
+    }
+  }
+
+  forward_to_deserialize_any! {
+    bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+    bytes byte_buf unit unit_struct seq tuple tuple_struct map struct identifier ignored_any
+  }
+}
+
+struct EnumNode {
+  variant: String,
+  value: ValueNode,
+}
+
+impl<'de> de::EnumAccess<'de> for EnumNode {
+  type Error = JSIError;
+  type Variant = ValueNode;
+
+  fn variant_seed<V: de::DeserializeSeed<'de>>(self, seed: V) -> JSIResult<(V::Value, ValueNode)> {
+    let variant = seed.deserialize(self.variant.into_deserializer())?;
+    Ok((variant, self.value))
+  }
+}
+
+impl<'de> de::VariantAccess<'de> for ValueNode {
+  type Error = JSIError;
+
+  fn unit_variant(self) -> JSIResult<()> {
+    Ok(())
+  }
+
+  fn newtype_variant_seed<T: de::DeserializeSeed<'de>>(self, seed: T) -> JSIResult<T::Value> {
+    seed.deserialize(self)
+  }
+
+  fn tuple_variant<V: Visitor<'de>>(self, _: usize, visitor: V) -> JSIResult<V::Value> {
+    de::Deserializer::deserialize_any(self, visitor)
+  }
+
+  fn struct_variant<V: Visitor<'de>>(self, _: &'static [&'static str], visitor: V) -> JSIResult<V::Value> {
+    de::Deserializer::deserialize_any(self, visitor)
+  }
+}
@@ -4,7 +4,7 @@ use crate::ast_node::{Statement, IdentifierLiteral, ClassType, CallContext, Expr
 use crate::ast_token::Token;
 use crate::builtins::boolean::create_boolean;
 use crate::builtins::function::get_builtin_function_name;
-use crate::builtins::number::create_number;
+use crate::builtins::number::{create_number, number_to_string};
 use crate::builtins::object::{create_object, Object, Property};
 use crate::builtins::string::create_string;
 use crate::bytecode::ByteCode;
@@ -47,6 +47,22 @@ impl ValueInfo {
           },
           _ => {
             let object = reference.to_object(ctx);
+            // 访问器属性调用 setter
+            let accessor = object.borrow().get_accessor(&name);
+            if let Some(accessor) = accessor {
+              if let Some(Value::Function(setter)) = accessor.set {
+                ctx.call_function_object(setter, Some(reference.clone()), None, vec![value])?;
+                return Ok(None);
+              }
+              return Err(JSIError::new(JSIErrorType::TypeError, format!("Cannot set property {} which has only a getter", name), 0, 0));
+            }
+            // 冻结的对象，严格模式下抛出错误，否则忽略
+            if object.borrow().frozen {
+              if ctx.strict {
+                return Err(JSIError::new(JSIErrorType::TypeError, format!("Cannot assign to read only property '{}' of object", name), 0, 0));
+              }
+              return Ok(None);
+            }
             object.borrow_mut().define_property( name.clone(), Property {
               enumerable: true,
               value: value,
@@ -68,6 +84,8 @@ impl ValueInfo {
 pub enum Value {
   // 5种基本数据类型
   String(String),
+  // 包含单独代理项（lone surrogate）的字符串，无法用 UTF-8 表示，以 UTF-16 编码单元存储
+  Utf16String(Vec<u16>),
   Number(f64),
   Boolean(bool),
   Null,
@@ -88,7 +106,7 @@ pub enum Value {
   // 定义的 scope 得是强引用
   Scope(Rc<RefCell<Scope>>),
   // 中断
-  Interrupt(Token,Expression),
+  Interrupt(Token,Box<Expression>),
   // bytecode
   ByteCode(Vec<ByteCode>),
 }
@@ -115,6 +133,7 @@ impl PartialEq for Value {
   fn eq(&self, other: &Value) -> bool {
       match (self, other) {
           (Value::String(a), Value::String(b)) => *a == *b,
+          (Value::Utf16String(a), Value::Utf16String(b)) => *a == *b,
           (Value::Number(a), Value::Number(b)) => *a == *b,
           (Value::Boolean(a), Value::Boolean(b)) => *a == *b,
           (Value::Null, Value::Null) | (Value::Undefined, Value::Undefined) => true,
@@ -148,6 +167,7 @@ impl Clone for Value {
         Value::BooleanObj(Rc::clone(rc_value))
       },
       Value::String(str) => Value::String(str.clone()),
+      Value::Utf16String(units) => Value::Utf16String(units.clone()),
       Value::Number(num) => Value::Number(*num),
       Value::Boolean(bool) => Value::Boolean(*bool),
       Value::Null => Value::Null,
@@ -169,9 +189,106 @@ impl Clone for Value {
   }
 }
 
+const PROPERTY_KEY_SURROGATE_MARK: char = '\u{FDD0}';
+const PROPERTY_KEY_SURROGATE_BASE: u32 = 0xF0000;
+
+fn utf16_to_property_key(units: &[u16]) -> String {
+  let mut key = String::with_capacity(units.len());
+  for item in char::decode_utf16(units.iter().cloned()) {
+    match item {
+      // 字符串本身含有的 U+FDD0 写两次，和代理项的编码区分开
+      Ok(PROPERTY_KEY_SURROGATE_MARK) => {
+        key.push(PROPERTY_KEY_SURROGATE_MARK);
+        key.push(PROPERTY_KEY_SURROGATE_MARK);
+      },
+      Ok(char) => key.push(char),
+      Err(err) => {
+        let unit = err.unpaired_surrogate() as u32;
+        key.push(PROPERTY_KEY_SURROGATE_MARK);
+        key.push(char::from_u32(PROPERTY_KEY_SURROGATE_BASE + unit).unwrap());
+      },
+    }
+  }
+  key
+}
+
+fn surrogate_from_key_char(char: char) -> Option<u16> {
+  let code = (char as u32).checked_sub(PROPERTY_KEY_SURROGATE_BASE)?;
+  if (0xD800..=0xDFFF).contains(&code) {
+    Some(code as u16)
+  } else {
+    None
+  }
+}
+
 impl Value {
+  // 由 UTF-16 编码单元创建字符串，合法的 UTF-16 使用 Value::String 存储
+  pub fn from_utf16(units: Vec<u16>) -> Value {
+    match String::from_utf16(&units) {
+      Ok(str) => Value::String(str),
+      Err(_) => Value::Utf16String(units),
+    }
+  }
+
+  // 转换为字符串的 UTF-16 编码单元，字符串的长度、索引都以编码单元计算
+  pub fn to_utf16(&self, ctx: &mut Context) -> Vec<u16> {
+    match self {
+      Value::String(str) => str.encode_utf16().collect(),
+      Value::Utf16String(units) => units.clone(),
+      Value::StringObj(obj) => {
+        let value = obj.borrow().get_inner_property_value(String::from("value"));
+        match value {
+          Some(value) => value.to_utf16(ctx),
+          None => vec![],
+        }
+      },
+      _ => self.to_string(ctx).encode_utf16().collect(),
+    }
+  }
+
+  // 转换为属性键，单独的代理项无法存放在 String 中，
+  // 编码为 U+FDD0 加上 U+F0000 + 编码单元，避免不同的代理项得到同一个键；
+  // 字符串中原有的 U+FDD0 编码为两个 U+FDD0，不会和代理项的编码混淆
+  pub fn to_property_key(&self, ctx: &mut Context) -> String {
+    match self {
+      Value::Utf16String(units) => utf16_to_property_key(units),
+      Value::StringObj(_) => utf16_to_property_key(&self.to_utf16(ctx)),
+      _ => {
+        let key = self.to_string(ctx);
+        if key.contains(PROPERTY_KEY_SURROGATE_MARK) {
+          key.replace(PROPERTY_KEY_SURROGATE_MARK, "\u{FDD0}\u{FDD0}")
+        } else {
+          key
+        }
+      },
+    }
+  }
+
+  // 由属性键还原字符串，to_property_key 的逆过程
+  pub fn from_property_key(key: String) -> Value {
+    if !key.contains(PROPERTY_KEY_SURROGATE_MARK) {
+      return Value::String(key)
+    }
+    let mut units: Vec<u16> = vec![];
+    let mut chars = key.chars().peekable();
+    while let Some(char) = chars.next() {
+      if char == PROPERTY_KEY_SURROGATE_MARK {
+        if chars.peek() == Some(&PROPERTY_KEY_SURROGATE_MARK) {
+          chars.next();
+        } else if let Some(unit) = chars.peek().and_then(|next| surrogate_from_key_char(*next)) {
+          chars.next();
+          units.push(unit);
+          continue;
+        }
+      }
+      let mut buf = [0u16; 2];
+      units.extend_from_slice(char.encode_utf16(&mut buf));
+    }
+    Value::from_utf16(units)
+  }
+
   pub fn is_string(&self) -> bool {
-    if let Value::String(_) = self {
+    if let Value::String(_) | Value::Utf16String(_) = self {
       return true
     }
     if let Value::StringObj(_) = self {
@@ -213,7 +330,9 @@ impl Value {
     }
     match self_value {
       Value::String(str) => str.clone(),
-      Value::Number(number) => number.to_string(),
+      // 单独的代理项无法转换为 UTF-8，替换为 U+FFFD
+      Value::Utf16String(units) => String::from_utf16_lossy(units),
+      Value::Number(number) => number_to_string(*number),
       Value::Boolean(bool) => {
         if *bool {
           String::from("true")
@@ -293,6 +412,8 @@ impl Value {
           }
         }
       },
+      // 含有单独代理项的字符串不可能是合法的数字
+      Value::Utf16String(_) => Some(f64::NAN),
       Value::Number(number) => Some(*number),
       Value::Boolean(bool) => {
         if *bool {
@@ -310,6 +431,41 @@ impl Value {
       }
     }
   }
+  // ToNumber：对象依次通过 valueOf、toString 转换为原始值，转换时抛出的错误向上传递
+  pub fn to_number_or_throw(&self, ctx: &mut Context) -> JSIResult<f64> {
+    if let Some(number) = self.to_number(ctx) {
+      return Ok(number)
+    }
+    let obj = match self.to_weak_rc_object().and_then(|obj| obj.upgrade()) {
+      Some(obj) => obj,
+      None => return Ok(f64::NAN),
+    };
+    for method in ["valueOf", "toString"] {
+      let fun = obj.borrow().get_value(String::from(method));
+      if !matches!(fun, Value::Function(_)) {
+        continue;
+      }
+      let call_ctx = &mut CallContext {
+        ctx,
+        this: self.clone(),
+        reference: None,
+        func_name: String::from(method),
+      };
+      let value = Object::call(call_ctx, String::from(method), vec![])?;
+      if value.is_primitive_value() {
+        return Ok(value.to_number(ctx).unwrap_or(f64::NAN))
+      }
+    }
+    Err(JSIError::new(JSIErrorType::TypeError, String::from("Cannot convert object to primitive value"), 0, 0))
+  }
+  // ToIntegerOrInfinity：NaN 转换为 0，其他值向零取整
+  pub fn to_integer_or_infinity(&self, ctx: &mut Context) -> JSIResult<f64> {
+    let number = self.to_number_or_throw(ctx)?;
+    if number.is_nan() {
+      return Ok(0f64)
+    }
+    Ok(number.trunc())
+  }
   pub fn to_boolean(&self, ctx: &mut Context) -> bool {
     let mut self_value = self;
     let primitive_value = self.to_primitive_value(ctx);
@@ -415,7 +571,7 @@ impl Value {
             return Rc::clone(obj);
           }
         }
-        Rc::new(RefCell::new(Object::new(ClassType::Object,None)))
+        Rc::new(RefCell::new(Object::new(&ctx.object_ids, ClassType::Object,None)))
       }
     }
     
@@ -423,8 +579,8 @@ impl Value {
 
   pub fn to_object_value(&self, ctx: &mut Context) -> Value {
     match self {
-      Value::String(string) => {
-        create_string(ctx, Value::String(string.to_owned()))
+      Value::String(_) | Value::Utf16String(_) => {
+        create_string(ctx, self.clone())
       },
       Value::Number(number) => {
         create_number(ctx, Value::Number(number.to_owned()))
@@ -441,6 +597,7 @@ impl Value {
   pub fn is_primitive_value(&self) -> bool {
     match self {
       Value::String(_) => true,
+      Value::Utf16String(_) => true,
       Value::StringObj(_) => true,
       Value::Number(_) => true,
       Value::NumberObj(_) => true,
@@ -501,6 +658,9 @@ impl Value {
           return Some(Value::Boolean(value.to_boolean(ctx)));
         },
         ValueType::String => {
+          if let Value::Utf16String(_) = value {
+            return Some(value);
+          }
           return Some(Value::String(value.to_string(ctx)));
         },
         _ => {}
@@ -535,6 +695,7 @@ impl Value {
       Value::Number(_) => String::from("number"),
       Value::NAN => String::from("number"),
       Value::String(_) => String::from("string"),
+      Value::Utf16String(_) => String::from("string"),
       Value::Undefined => String::from("undefined"),
       Value::Function(_) => String::from("function"),
       _ => String::from("object")
@@ -548,6 +709,7 @@ impl Value {
       Value::Array(_) => ValueType::Array,
       Value::Promise(_) => ValueType::Object,
       Value::String(_) => ValueType::String,
+      Value::Utf16String(_) => ValueType::String,
       Value::StringObj(_) => ValueType::String,
       Value::Number(_) => ValueType::Number,
       Value::NumberObj(_) => ValueType::Number,
@@ -595,6 +757,9 @@ impl Value {
     }
     match (self_value, other_value) {
         (Value::String(a), Value::String(b)) => *a == *b,
+        (Value::Utf16String(_), Value::String(_) | Value::Utf16String(_)) | (Value::String(_), Value::Utf16String(_)) => {
+          self_value.to_utf16(ctx) == other_value.to_utf16(ctx)
+        },
         (Value::Number(a), Value::Number(b)) => *a == *b,
         (Value::Boolean(a), Value::Boolean(b)) => *a == *b,
         (Value::Null, Value::Null) | (Value::Undefined, Value::Undefined) => true,
@@ -612,6 +777,36 @@ impl Value {
     }
   }
 
+  // SameValue：NaN 与 NaN 相等，+0 与 -0 不相等
+  pub fn same_value(&self, ctx: &mut Context, other: &Value) -> bool {
+    if let (Some(a), Some(b)) = (self.as_number(), other.as_number()) {
+      return (a.is_nan() && b.is_nan()) || (a == b && a.is_sign_negative() == b.is_sign_negative());
+    }
+    self.same_value_zero(ctx, other)
+  }
+
+  // SameValueZero：NaN 与 NaN 相等，+0 与 -0 相等
+  pub fn same_value_zero(&self, ctx: &mut Context, other: &Value) -> bool {
+    if let (Some(a), Some(b)) = (self.as_number(), other.as_number()) {
+      return (a.is_nan() && b.is_nan()) || a == b;
+    }
+    // 对象按照引用比较
+    match (self.to_weak_rc_object(), other.to_weak_rc_object()) {
+      (Some(a), Some(b)) => a.ptr_eq(&b),
+      (None, None) => self.is_equal_to(ctx, other, true),
+      _ => false,
+    }
+  }
+
+  // 数字原始值
+  fn as_number(&self) -> Option<f64> {
+    match self {
+      Value::Number(number) => Some(*number),
+      Value::NAN => Some(f64::NAN),
+      _ => None,
+    }
+  }
+
   // 匿名方法，需要绑定name
   pub fn bind_name(&mut self, name: String) {
     match self {
@@ -623,7 +818,8 @@ impl Value {
               if func.is_anonymous {
                 let mut new_func = func.clone();
                 new_func.name = IdentifierLiteral {
-                  literal: name.clone()
+                  literal: name.clone(),
+                  span: func.name.span,
                 };
                 value = Box::new(Statement::Function(new_func));
                 function_define.set_value(Some(value));
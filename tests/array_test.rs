@@ -681,3 +681,204 @@ fn run_array_big() {
   assert_eq!(result, Value::String(String::from("objLength:4294967295,arr:y")));
 
 }
+
+#[test]
+fn run_array_from_and_of() {
+  let mut jsi = JSI::new();
+  let result = jsi.run(String::from("\
+    let a = Array.from('abc').join('');
+    let b = Array.from({ length: 2, 0: 'x', 1: 'y' }).join('');
+    let c = Array.from([1, 2], function(x) { return x * 10; }).join();
+    let d = Array.of(7, 8).length;
+    `res:${a}:${b}:${c}:${d}:${Array.isArray([])}`
+  ")).unwrap();
+  assert_eq!(result, Value::String(String::from("res:abc:xy:10,20:2:true")));
+}
+
+#[test]
+fn run_array_reduce() {
+  let mut jsi = JSI::new();
+  let result = jsi.run(String::from("\
+    let sum = [1, 2, 3, 4].reduce(function(a, b) { return a + b; });
+    let str = [1, 2, 3].reduceRight(function(a, b) { return a + b; }, '');
+    let msg = '';
+    try {
+      [].reduce(function(a, b) { return a + b; });
+    } catch (e) {
+      msg = e.message;
+    }
+    `res:${sum}:${str}:${msg}`
+  ")).unwrap();
+  assert_eq!(result, Value::String(String::from("res:10:321:Reduce of empty array with no initial value")));
+}
+
+#[test]
+fn run_array_some_every() {
+  let mut jsi = JSI::new();
+  let result = jsi.run(String::from("\
+    let arr = [1, 2, 3];
+    let some = arr.some(function(x) { return x > 2; });
+    let every = arr.every(function(x) { return x > 2; });
+    `res:${some},${every}`
+  ")).unwrap();
+  assert_eq!(result, Value::String(String::from("res:true,false")));
+}
+
+#[test]
+fn run_array_flat() {
+  let mut jsi = JSI::new();
+  let result = jsi.run(String::from("\
+    let arr = [1, [2, [3, [4]]]];
+    let a = arr.flat().length;
+    let b = arr.flat(Infinity).join();
+    let c = [1, 2].flatMap(function(x) { return [x, x * 2]; }).join();
+    `res:${a}:${b}:${c}`
+  ")).unwrap();
+  assert_eq!(result, Value::String(String::from("res:3:1,2,3,4:1,2,2,4")));
+}
+
+#[test]
+fn run_array_at_last_index_of_find_last() {
+  let mut jsi = JSI::new();
+  let result = jsi.run(String::from("\
+    let arr = [1, 2, 3, 2];
+    let odd = function(x) { return x % 2 == 1; };
+    [arr.at(-1), arr.at(9) === undefined, arr.lastIndexOf(2), arr.lastIndexOf(2, -2), arr.findLast(odd), arr.findLastIndex(odd)].join()
+  ")).unwrap();
+  assert_eq!(result, Value::String(String::from("2,true,3,1,3,2")));
+}
+
+#[test]
+fn run_array_copy_within() {
+  let mut jsi = JSI::new();
+  let result = jsi.run(String::from("\
+    let a = [1, 2, 3, 4, 5].copyWithin(0, 3).join();
+    let b = [1, 2, 3, 4, 5].copyWithin(1, 0, 3).join();
+    `res:${a}:${b}`
+  ")).unwrap();
+  assert_eq!(result, Value::String(String::from("res:4,5,3,4,5:1,1,2,3,5")));
+}
+
+#[test]
+fn run_array_iterators() {
+  let mut jsi = JSI::new();
+  let result = jsi.run(String::from("\
+    let out = [];
+    for (let entry of ['a', 'b'].entries()) {
+      out.push(entry[0] + entry[1]);
+    }
+    let it = ['x'].keys();
+    let first = it.next();
+    let second = it.next();
+    `res:${out.join()}:${first.value},${first.done},${second.done}:${Array.from([3, 4].values()).join()}`
+  ")).unwrap();
+  assert_eq!(result, Value::String(String::from("res:0a,1b:0,false,true:3,4")));
+}
+
+#[test]
+fn run_array_change_by_copy() {
+  let mut jsi = JSI::new();
+  let result = jsi.run(String::from("\
+    let arr = [3, 1, 10, 2];
+    let a = arr.toSorted().join();
+    let b = arr.toSorted(function(x, y) { return x - y; }).join();
+    let c = arr.toReversed().join();
+    let d = arr.toSpliced(1, 2, 'x').join();
+    let e = arr.with(-1, 9).join();
+    let msg = '';
+    try {
+      arr.with(4, 0);
+    } catch (err) {
+      msg = err.message;
+    }
+    `res:${a}:${b}:${c}:${d}:${e}:${arr.join()}:${msg}`
+  ")).unwrap();
+  assert_eq!(result, Value::String(String::from("res:1,10,2,3:1,2,3,10:2,10,1,3:3,x,2:3,1,10,9:3,1,10,2:Invalid index : 4")));
+}
+
+#[test]
+fn run_array_callback_errors() {
+  let mut jsi = JSI::new();
+  let result = jsi.run(String::from("\
+    let out = [];
+    let boom = function() { throw new Error('boom'); };
+    try { [1, 2].reduce(boom); } catch (e) { out.push('reduce:' + e.message); }
+    try { [1, 2].some(boom); } catch (e) { out.push('some:' + e.message); }
+    try { Array.from([1], boom); } catch (e) { out.push('from:' + e.message); }
+    try { [1].forEach(function() { throw new TypeError('x'); }); } catch (e) { out.push(e.name); }
+    out.join()
+  ")).unwrap();
+  assert_eq!(result, Value::String(String::from("reduce:boom,some:boom,from:boom,TypeError")));
+  // 回调中的错误不能被吞掉
+  assert!(jsi.run(String::from("[1, 2].map(function() { throw new Error('boom'); })")).is_err());
+  let result = jsi.run(String::from("\
+    function f() { return [1].map(f); }
+    let name;
+    try { f(); } catch (e) { name = e.name; }
+    name
+  ")).unwrap();
+  assert_eq!(result, Value::String(String::from("RangeError")));
+}
+
+#[test]
+fn run_array_length_range_error() {
+  let mut jsi = JSI::new();
+  let result = jsi.run(String::from("\
+    let out = [];
+    let big = { length: 1e10 };
+    try { Array.from(big); } catch (e) { out.push(e.name); }
+    try { Array.prototype.toSorted.call(big); } catch (e) { out.push(e.name); }
+    try { Array.prototype.toReversed.call(big); } catch (e) { out.push(e.name); }
+    try { Array.prototype.toSpliced.call(big, 0, 0); } catch (e) { out.push(e.name); }
+    try { Array.prototype.with.call(big, 0, 1); } catch (e) { out.push(e.name); }
+    out.join()
+  ")).unwrap();
+  assert_eq!(result, Value::String(String::from("RangeError,RangeError,RangeError,RangeError,RangeError")));
+}
+
+#[test]
+fn run_array_length_to_number_errors() {
+  let mut jsi = JSI::new();
+  let result = jsi.run(String::from("\
+    let out = [];
+    let boom = { valueOf: function() { throw new SyntaxError('boom'); } };
+    try { Array.from({ length: boom }); } catch (e) { out.push(e.name); }
+    try { [1, 2].at(boom); } catch (e) { out.push(e.name); }
+    try { [1, 2].with(boom, 1); } catch (e) { out.push(e.name); }
+    out.push(Array.from({ length: { valueOf: function() { return 2; } } }).length);
+    out.push([1, 2, 3].at([1]));
+    out.join()
+  ")).unwrap();
+  assert_eq!(result, Value::String(String::from("SyntaxError,SyntaxError,SyntaxError,2,2")));
+}
+
+#[test]
+fn run_array_find_last_large_holes() {
+  let mut jsi = JSI::new();
+  let result = jsi.run(String::from("\
+    let arr = new Array(1501);
+    arr[0] = 1;
+    let visited = 0;
+    arr.findLast(function() { visited++; return false; });
+    [visited, arr.findLastIndex(function(value) { return value === undefined; })].join()
+  ")).unwrap();
+  assert_eq!(result, Value::String(String::from("1501,1500")));
+}
+
+#[test]
+fn run_array_flat_stack_overflow() {
+  let mut jsi = JSI::new();
+  let result = jsi.run(String::from("\
+    let messages = [];
+    let cyclic = [1];
+    cyclic.push(cyclic);
+    try {
+      cyclic.flat(Infinity);
+    } catch (e) {
+      messages.push(e.name + ': ' + e.message);
+    }
+    messages.push(cyclic.flat(1).length);
+    messages.join()
+  ")).unwrap();
+  assert_eq!(result, Value::String(String::from("RangeError: Maximum call stack size exceeded,3")));
+}
@@ -1,4 +1,7 @@
-use jsi::{JSI,ast_node::{Statement, ExpressionStatement, Expression, IdentifierLiteral, PrefixUnaryExpression, AssignExpression, BinaryExpression, FunctionDeclaration, BlockStatement, ReturnStatement, Keywords}, ast_token::Token, value::Value};
+use jsi::{JSI,ast_node::{Statement, ExpressionStatement, Expression, IdentifierLiteral, PrefixUnaryExpression, AssignExpression, BinaryExpression, FunctionDeclaration, BlockStatement, ReturnStatement, Keywords, Span}, ast_token::Token, value::Value};
+
+mod common;
+use common::without_spans;
 
 #[test]
 // test262: https://github.com/tc39/test262/blob/main/test/language/asi/S7.9.2_A1_T5.js
@@ -6,19 +9,23 @@ fn asi_increment_after_identifier() {
   let mut jsi_vm = JSI::new();
   let program = jsi_vm.parse(String::from("a = b\n
   ++c")).unwrap();
-  assert_eq!(program.body, vec![
+  assert_eq!(without_spans(program.body), vec![
     Statement::Expression(ExpressionStatement { // a + b
       expression: Expression::Assign(AssignExpression {
-        left: Box::new(Expression::Identifier(IdentifierLiteral { literal: String::from("a") })),
+        left: Box::new(Expression::Identifier(IdentifierLiteral { literal: String::from("a"), span: Span::default() })),
         operator: Token::Assign,
-        right: Box::new(Expression::Identifier(IdentifierLiteral { literal: String::from("b") })),
+        right: Box::new(Expression::Identifier(IdentifierLiteral { literal: String::from("b"), span: Span::default() })),
+        span: Span::default(),
       }),
+      span: Span::default(),
     }),
     Statement::Expression(ExpressionStatement { // ++c
       expression: Expression::PrefixUnary(PrefixUnaryExpression {
-        operand: Box::new(Expression::Identifier(IdentifierLiteral { literal: String::from("c") })),
+        operand: Box::new(Expression::Identifier(IdentifierLiteral { literal: String::from("c"), span: Span::default() })),
         operator: Token::Increment,
-      })
+        span: Span::default(),
+      }),
+      span: Span::default(),
     })
   ]);
 }
@@ -31,29 +38,34 @@ fn asi_after_keyword_return() {
     return\n
     a+b\n
   }")).unwrap();
-  assert_eq!(program.body, vec![
+  assert_eq!(without_spans(program.body), vec![
     Statement::Function(FunctionDeclaration { // a + b
       is_anonymous: false,
       is_arrow: false,
       is_async: false,
-      name: IdentifierLiteral { literal: String::from("test") },
+      name: IdentifierLiteral { literal: String::from("test"), span: Span::default() },
       parameters: vec![],
       body: BlockStatement {
         statements: vec![
           Statement::Return(ReturnStatement {
-            expression: Expression::Keyword(Keywords::Undefined)
+            expression: Expression::Keyword(Keywords::Undefined, Span::default()),
+            span: Span::default(),
           }),
           Statement::Expression(ExpressionStatement { // ++c
             expression: Expression::Binary(BinaryExpression {
-              left: Box::new(Expression::Identifier(IdentifierLiteral { literal: String::from("a") })),
+              left: Box::new(Expression::Identifier(IdentifierLiteral { literal: String::from("a"), span: Span::default() })),
               operator: Token::Plus,
-              right: Box::new(Expression::Identifier(IdentifierLiteral { literal: String::from("b") })),
-            })
+              right: Box::new(Expression::Identifier(IdentifierLiteral { literal: String::from("b"), span: Span::default() })),
+              span: Span::default(),
+            }),
+            span: Span::default(),
           })
-        ]
+        ],
+        span: Span::default(),
       },
       declarations: vec![],
       bytecode: vec![],
+      span: Span::default(),
     }),
   ]);
 }
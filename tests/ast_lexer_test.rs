@@ -1,4 +1,7 @@
-use jsi::{JSI,ast_node::{Statement, ExpressionStatement, Expression, BinaryExpression, NumberLiteral, IdentifierLiteral, PostfixUnaryExpression, PrefixUnaryExpression, AssignExpression, GroupExpression, ConditionalExpression}, ast_token::Token};
+use jsi::{JSI,ast_node::{Statement, ExpressionStatement, Expression, BinaryExpression, NumberLiteral, IdentifierLiteral, PostfixUnaryExpression, PrefixUnaryExpression, AssignExpression, GroupExpression, ConditionalExpression, Span}, ast_token::Token};
+
+mod common;
+use common::without_spans;
 
 struct TokenCheck {
   pub oper: String,
@@ -40,12 +43,14 @@ fn ast_lexer_binary_token() {
     code.push_str(token.oper.as_str());
     code.push_str(" 1;");
     let program = jsi_vm.parse(code).unwrap();
-    assert_eq!(program.body, vec![Statement::Expression(ExpressionStatement {
+    assert_eq!(without_spans(program.body), vec![Statement::Expression(ExpressionStatement {
       expression: Expression::Binary(BinaryExpression {
-        left: Box::new(Expression::Number(NumberLiteral{ literal: String::from("1"), value: 1f64 })),
+        left: Box::new(Expression::Number(NumberLiteral{ literal: String::from("1"), value: 1f64, span: Span::default() })),
         operator: token.token.clone(),
-        right: Box::new(Expression::Number(NumberLiteral{ literal: String::from("1"), value: 1f64 })),
-      })
+        right: Box::new(Expression::Number(NumberLiteral{ literal: String::from("1"), value: 1f64, span: Span::default() })),
+        span: Span::default(),
+      }),
+      span: Span::default(),
     })]);
   }
 }
@@ -77,12 +82,14 @@ fn ast_lexer_assign_token() {
     code.push_str(token.oper.as_str());
     code.push_str("1;");
     let program = jsi_vm.parse(code).unwrap();
-    assert_eq!(program.body, vec![Statement::Expression(ExpressionStatement {
+    assert_eq!(without_spans(program.body), vec![Statement::Expression(ExpressionStatement {
       expression: Expression::Assign(AssignExpression {
-        left: Box::new(Expression::Identifier(IdentifierLiteral{ literal: String::from("a") })),
+        left: Box::new(Expression::Identifier(IdentifierLiteral{ literal: String::from("a"), span: Span::default() })),
         operator: token.token.clone(),
-        right: Box::new(Expression::Number(NumberLiteral{ literal: String::from("1"), value: 1f64 })),
-      })
+        right: Box::new(Expression::Number(NumberLiteral{ literal: String::from("1"), value: 1f64, span: Span::default() })),
+        span: Span::default(),
+      }),
+      span: Span::default(),
     })]);
   }
 }
@@ -107,11 +114,13 @@ fn ast_lexer_prefix_unary_token() {
     code.push_str(token.oper.as_str());
     code.push_str(" i;");
     let program = jsi_vm.parse(code).unwrap();
-    assert_eq!(program.body, vec![Statement::Expression(ExpressionStatement {
+    assert_eq!(without_spans(program.body), vec![Statement::Expression(ExpressionStatement {
       expression: Expression::PrefixUnary(PrefixUnaryExpression {
-        operand: Box::new(Expression::Identifier(IdentifierLiteral{ literal: String::from("i") })),
+        operand: Box::new(Expression::Identifier(IdentifierLiteral{ literal: String::from("i"), span: Span::default() })),
         operator: token.token.clone(),
-      })
+        span: Span::default(),
+      }),
+      span: Span::default(),
     })]);
   }
 }
@@ -129,11 +138,13 @@ fn ast_lexer_postfix_unary_token() {
     code.push_str(token.oper.as_str());
     code.push_str(";");
     let program = jsi_vm.parse(code).unwrap();
-    assert_eq!(program.body, vec![Statement::Expression(ExpressionStatement {
+    assert_eq!(without_spans(program.body), vec![Statement::Expression(ExpressionStatement {
       expression: Expression::PostfixUnary(PostfixUnaryExpression {
-        operand: Box::new(Expression::Identifier(IdentifierLiteral{ literal: String::from("i") })),
+        operand: Box::new(Expression::Identifier(IdentifierLiteral{ literal: String::from("i"), span: Span::default() })),
         operator: token.token.clone(),
-      })
+        span: Span::default(),
+      }),
+      span: Span::default(),
     })]);
   }
 }
@@ -152,16 +163,19 @@ fn ast_lexer_associativity_right_exponentiation() {
     code.push_str(token.oper.as_str());
     code.push_str(" 2;");
     let program = jsi_vm.parse(code).unwrap();
-    assert_eq!(program.body, vec![Statement::Expression(ExpressionStatement {
+    assert_eq!(without_spans(program.body), vec![Statement::Expression(ExpressionStatement {
       expression: Expression::Binary(BinaryExpression { // 向右结合 2 op (3 op 2)
-        left: Box::new(Expression::Number(NumberLiteral {  literal: String::from("2"), value: 2f64 })), // 2,
+        left: Box::new(Expression::Number(NumberLiteral {  literal: String::from("2"), value: 2f64, span: Span::default() })), // 2,
         operator: token.token.clone(),
         right: Box::new(Expression::Binary(BinaryExpression { // 3 op 2
-          left: Box::new(Expression::Number(NumberLiteral {  literal: String::from("3"), value: 3f64 })), // 3
+          left: Box::new(Expression::Number(NumberLiteral {  literal: String::from("3"), value: 3f64, span: Span::default() })), // 3
           operator: token.token.clone(),
-          right: Box::new(Expression::Number(NumberLiteral {  literal: String::from("2"), value: 2f64 })), // 2
+          right: Box::new(Expression::Number(NumberLiteral {  literal: String::from("2"), value: 2f64, span: Span::default() })), // 2,
+          span: Span::default(),
         })),
-      })
+        span: Span::default(),
+      }),
+      span: Span::default(),
     })]);
   }
 
@@ -190,31 +204,37 @@ fn ast_lexer_associativity_right_exponentiation() {
     code.push_str(token.oper.as_str());
     code.push_str(" 2;");
     let program = jsi_vm.parse(code).unwrap();
-    assert_eq!(program.body, vec![Statement::Expression(ExpressionStatement {
+    assert_eq!(without_spans(program.body), vec![Statement::Expression(ExpressionStatement {
       expression: Expression::Assign(AssignExpression { // 向右结合 a op (b op 2)
-        left: Box::new(Expression::Identifier(IdentifierLiteral{ literal: String::from("a") })), // a
+        left: Box::new(Expression::Identifier(IdentifierLiteral{ literal: String::from("a"), span: Span::default() })), // a
         operator: token.token.clone(),
         right: Box::new(Expression::Assign(AssignExpression { // b op 2
-          left: Box::new(Expression::Identifier(IdentifierLiteral{ literal: String::from("b") })), // b
+          left: Box::new(Expression::Identifier(IdentifierLiteral{ literal: String::from("b"), span: Span::default() })), // b
           operator: token.token.clone(),
-          right: Box::new(Expression::Number(NumberLiteral {  literal: String::from("2"), value: 2f64 })), // 2
+          right: Box::new(Expression::Number(NumberLiteral {  literal: String::from("2"), value: 2f64, span: Span::default() })), // 2,
+          span: Span::default(),
         })),
-      })
+        span: Span::default(),
+      }),
+      span: Span::default(),
     })]);
   }
   // TODO: single oper
   // 三目运算符
   let program = jsi_vm.parse(String::from("1 ? 2 ? 3: 4: 5;")).unwrap();
-  assert_eq!(program.body, vec![Statement::Expression(ExpressionStatement {
+  assert_eq!(without_spans(program.body), vec![Statement::Expression(ExpressionStatement {
     expression: Expression::Conditional(ConditionalExpression {
-      condition: Box::new(Expression::Number(NumberLiteral {  literal: String::from("1"), value: 1f64 })),
+      condition: Box::new(Expression::Number(NumberLiteral {  literal: String::from("1"), value: 1f64, span: Span::default() })),
       when_true: Box::new(Expression::Conditional(ConditionalExpression {
-        condition: Box::new(Expression::Number(NumberLiteral {  literal: String::from("2"), value: 2f64 })),
-        when_true: Box::new(Expression::Number(NumberLiteral {  literal: String::from("3"), value: 3f64 })),
-        when_false: Box::new(Expression::Number(NumberLiteral {  literal: String::from("4"), value: 4f64 }))
+        condition: Box::new(Expression::Number(NumberLiteral {  literal: String::from("2"), value: 2f64, span: Span::default() })),
+        when_true: Box::new(Expression::Number(NumberLiteral {  literal: String::from("3"), value: 3f64, span: Span::default() })),
+        when_false: Box::new(Expression::Number(NumberLiteral {  literal: String::from("4"), value: 4f64, span: Span::default() })),
+        span: Span::default(),
       })),
-      when_false: Box::new(Expression::Number(NumberLiteral {  literal: String::from("5"), value: 5f64 }))
-    })
+      when_false: Box::new(Expression::Number(NumberLiteral {  literal: String::from("5"), value: 5f64, span: Span::default() })),
+      span: Span::default(),
+    }),
+    span: Span::default(),
   })]);
 }
 
@@ -251,16 +271,19 @@ fn ast_lexer_associativity_left_exponentiation() {
     code.push_str(token.oper.as_str());
     code.push_str(" 2;");
     let program = jsi_vm.parse(code).unwrap();
-    assert_eq!(program.body, vec![Statement::Expression(ExpressionStatement {
+    assert_eq!(without_spans(program.body), vec![Statement::Expression(ExpressionStatement {
       expression: Expression::Binary(BinaryExpression { // 向左结合 (2 op 3) op 2
         left: Box::new(Expression::Binary(BinaryExpression { // 2 op 3
-          left: Box::new(Expression::Number(NumberLiteral {  literal: String::from("2"), value: 2f64 })), // 2
+          left: Box::new(Expression::Number(NumberLiteral {  literal: String::from("2"), value: 2f64, span: Span::default() })), // 2
           operator: token.token.clone(),
-          right: Box::new(Expression::Number(NumberLiteral {  literal: String::from("3"), value: 3f64 })), // 3
+          right: Box::new(Expression::Number(NumberLiteral {  literal: String::from("3"), value: 3f64, span: Span::default() })), // 3,
+          span: Span::default(),
         })),
         operator: token.token.clone(),
-        right: Box::new(Expression::Number(NumberLiteral {  literal: String::from("2"), value: 2f64 })), // 2,
-      })
+        right: Box::new(Expression::Number(NumberLiteral {  literal: String::from("2"), value: 2f64, span: Span::default() })), // 2,
+        span: Span::default(),
+      }),
+      span: Span::default(),
     })]);
   }
   // TODO: queal
@@ -270,16 +293,19 @@ fn ast_lexer_associativity_left_exponentiation() {
 fn ast_lexer_priority_between_exponentiation_shift() {
   let mut jsi_vm = JSI::new();
   let program = jsi_vm.parse(String::from("2 ** 3 >> 1;")).unwrap();
-  assert_eq!(program.body, vec![Statement::Expression(ExpressionStatement {
+  assert_eq!(without_spans(program.body), vec![Statement::Expression(ExpressionStatement {
     expression: Expression::Binary(BinaryExpression {
       left: Box::new(Expression::Binary(BinaryExpression { // 2 ** 3
-        left: Box::new(Expression::Number(NumberLiteral {  literal: String::from("2"), value: 2f64 })), // 2
+        left: Box::new(Expression::Number(NumberLiteral {  literal: String::from("2"), value: 2f64, span: Span::default() })), // 2
         operator: Token::Exponentiation, // **
-        right: Box::new(Expression::Number(NumberLiteral {  literal: String::from("3"), value: 3f64 })), // 3
+        right: Box::new(Expression::Number(NumberLiteral {  literal: String::from("3"), value: 3f64, span: Span::default() })), // 3,
+        span: Span::default(),
       })),
       operator: Token::ShiftRight, // >>
-      right: Box::new(Expression::Number(NumberLiteral {  literal: String::from("1"), value: 1f64 })), // 1,
-    })
+      right: Box::new(Expression::Number(NumberLiteral {  literal: String::from("1"), value: 1f64, span: Span::default() })), // 1,
+      span: Span::default(),
+    }),
+    span: Span::default(),
   })]);
 }
 
@@ -288,26 +314,31 @@ fn ast_lexer_priority_between_exponentiation_shift() {
 fn ast_lexer_complex() {
   let mut jsi_vm = JSI::new();
   let program = jsi_vm.parse(String::from("(1 + 2) * 3 - 4 ** 2 >> (1 * 4 -3 + 1 == 2 ? 1 : 2);")).unwrap(); // return value is 4
-  assert_eq!(program.body, vec![Statement::Expression(ExpressionStatement {
+  assert_eq!(without_spans(program.body), vec![Statement::Expression(ExpressionStatement {
     expression: Expression::Binary(BinaryExpression {
       left: Box::new(Expression::Binary(BinaryExpression { // (1 + 2) * 3 - 4 ** 2
         left: Box::new(Expression::Binary(BinaryExpression { // (1 + 2) * 3
           left: Box::new(Expression::Group(GroupExpression { // (1 + 2)
             expression:  Box::new(Expression::Binary(BinaryExpression { // 1 + 2
-              left: Box::new(Expression::Number(NumberLiteral {  literal: String::from("1"), value: 1f64 })), // 1
+              left: Box::new(Expression::Number(NumberLiteral {  literal: String::from("1"), value: 1f64, span: Span::default() })), // 1
               operator: Token::Plus, // +
-              right: Box::new(Expression::Number(NumberLiteral {  literal: String::from("2"), value: 2f64 })), // 2
-            }))
+              right: Box::new(Expression::Number(NumberLiteral {  literal: String::from("2"), value: 2f64, span: Span::default() })), // 2,
+              span: Span::default(),
+            })),
+            span: Span::default(),
           })),
           operator: Token::Multiply, // *
-          right: Box::new(Expression::Number(NumberLiteral {  literal: String::from("3"), value: 3f64 })), // 3
+          right: Box::new(Expression::Number(NumberLiteral {  literal: String::from("3"), value: 3f64, span: Span::default() })), // 3,
+          span: Span::default(),
         })),
         operator: Token::Subtract, // -
         right: Box::new(Expression::Binary(BinaryExpression { // 4 ** 2
-          left: Box::new(Expression::Number(NumberLiteral {  literal: String::from("4"), value: 4f64 })), // 4
+          left: Box::new(Expression::Number(NumberLiteral {  literal: String::from("4"), value: 4f64, span: Span::default() })), // 4
           operator: Token::Exponentiation, // -
-          right: Box::new(Expression::Number(NumberLiteral {  literal: String::from("2"), value: 2f64 })), // 2
+          right: Box::new(Expression::Number(NumberLiteral {  literal: String::from("2"), value: 2f64, span: Span::default() })), // 2,
+          span: Span::default(),
         })),
+        span: Span::default(),
       })),
       operator: Token::ShiftRight, // >>
       right: Box::new(Expression::Group(GroupExpression { // (1 * 4 -3 + 1 == 2 ? 1 : 2)
@@ -316,24 +347,32 @@ fn ast_lexer_complex() {
             left: Box::new(Expression::Binary(BinaryExpression { // 1 * 4 -3 + 1
               left: Box::new(Expression::Binary(BinaryExpression { // 1 * 4 -3
                 left: Box::new(Expression::Binary(BinaryExpression { // 1 * 4
-                  left: Box::new(Expression::Number(NumberLiteral {  literal: String::from("1"), value: 1f64 })), // 1
+                  left: Box::new(Expression::Number(NumberLiteral {  literal: String::from("1"), value: 1f64, span: Span::default() })), // 1
                   operator: Token::Multiply, // *
-                  right: Box::new(Expression::Number(NumberLiteral {  literal: String::from("4"), value: 4f64 })), // 4
+                  right: Box::new(Expression::Number(NumberLiteral {  literal: String::from("4"), value: 4f64, span: Span::default() })), // 4,
+                  span: Span::default(),
                 })),
                 operator: Token::Subtract, // -
-                right: Box::new(Expression::Number(NumberLiteral {  literal: String::from("3"), value: 3f64 })), // 3
+                right: Box::new(Expression::Number(NumberLiteral {  literal: String::from("3"), value: 3f64, span: Span::default() })), // 3,
+                span: Span::default(),
               })),
               operator: Token::Plus, // +
-              right: Box::new(Expression::Number(NumberLiteral {  literal: String::from("1"), value: 1f64 })), // 1
+              right: Box::new(Expression::Number(NumberLiteral {  literal: String::from("1"), value: 1f64, span: Span::default() })), // 1,
+              span: Span::default(),
             })),
             operator: Token::Equal, // =
-            right: Box::new(Expression::Number(NumberLiteral {  literal: String::from("2"), value: 2f64 })), // 2
+            right: Box::new(Expression::Number(NumberLiteral {  literal: String::from("2"), value: 2f64, span: Span::default() })), // 2,
+            span: Span::default(),
           })),
-          when_true: Box::new(Expression::Number(NumberLiteral {  literal: String::from("1"), value: 1f64 })), // 1
-          when_false: Box::new(Expression::Number(NumberLiteral {  literal: String::from("2"), value: 2f64 })), // 2
-        }))
+          when_true: Box::new(Expression::Number(NumberLiteral {  literal: String::from("1"), value: 1f64, span: Span::default() })), // 1
+          when_false: Box::new(Expression::Number(NumberLiteral {  literal: String::from("2"), value: 2f64, span: Span::default() })), // 2,
+          span: Span::default(),
+        })),
+        span: Span::default(),
       })),
-    })
+      span: Span::default(),
+    }),
+    span: Span::default(),
   })]);
 }
 
@@ -343,20 +382,24 @@ fn ast_lexer_complex() {
 fn ast_lexer_priority_bitwise() {
   let mut jsi_vm = JSI::new();
   let program = jsi_vm.parse(String::from("1 | 2 ^ 3 & 4;")).unwrap();
-  assert_eq!(program.body, vec![Statement::Expression(ExpressionStatement {
+  assert_eq!(without_spans(program.body), vec![Statement::Expression(ExpressionStatement {
     expression: Expression::Binary(BinaryExpression {
-      left: Box::new(Expression::Number(NumberLiteral { literal: String::from("1"), value: 1f64 })),
+      left: Box::new(Expression::Number(NumberLiteral { literal: String::from("1"), value: 1f64, span: Span::default() })),
       operator: Token::Or,
       right: Box::new(Expression::Binary(BinaryExpression {
-        left: Box::new(Expression::Number(NumberLiteral { literal: String::from("2"), value: 2f64 })),
+        left: Box::new(Expression::Number(NumberLiteral { literal: String::from("2"), value: 2f64, span: Span::default() })),
         operator: Token::ExclusiveOr,
         right: Box::new(Expression::Binary(BinaryExpression {
-          left: Box::new(Expression::Number(NumberLiteral { literal: String::from("3"), value: 3f64 })),
+          left: Box::new(Expression::Number(NumberLiteral { literal: String::from("3"), value: 3f64, span: Span::default() })),
           operator: Token::And,
-          right: Box::new(Expression::Number(NumberLiteral { literal: String::from("4"), value: 4f64 })),
+          right: Box::new(Expression::Number(NumberLiteral { literal: String::from("4"), value: 4f64, span: Span::default() })),
+          span: Span::default(),
         })),
+        span: Span::default(),
       })),
-    })
+      span: Span::default(),
+    }),
+    span: Span::default(),
   })]);
 }
 
@@ -366,16 +409,19 @@ fn ast_lexer_priority_bitwise() {
 fn ast_lexer_priority_shift_over_bitwise() {
   let mut jsi_vm = JSI::new();
   let program = jsi_vm.parse(String::from("1 | 2 << 3;")).unwrap();
-  assert_eq!(program.body, vec![Statement::Expression(ExpressionStatement {
+  assert_eq!(without_spans(program.body), vec![Statement::Expression(ExpressionStatement {
     expression: Expression::Binary(BinaryExpression {
-      left: Box::new(Expression::Number(NumberLiteral { literal: String::from("1"), value: 1f64 })),
+      left: Box::new(Expression::Number(NumberLiteral { literal: String::from("1"), value: 1f64, span: Span::default() })),
       operator: Token::Or,
       right: Box::new(Expression::Binary(BinaryExpression {
-        left: Box::new(Expression::Number(NumberLiteral { literal: String::from("2"), value: 2f64 })),
+        left: Box::new(Expression::Number(NumberLiteral { literal: String::from("2"), value: 2f64, span: Span::default() })),
         operator: Token::ShiftLeft,
-        right: Box::new(Expression::Number(NumberLiteral { literal: String::from("3"), value: 3f64 })),
+        right: Box::new(Expression::Number(NumberLiteral { literal: String::from("3"), value: 3f64, span: Span::default() })),
+        span: Span::default(),
       })),
-    })
+      span: Span::default(),
+    }),
+    span: Span::default(),
   })]);
 }
 
@@ -393,11 +439,13 @@ fn ast_lexer_hex_number_literal() {
   let mut jsi_vm = JSI::new();
   for (code, literal, value) in test_cases.iter() {
     let program = jsi_vm.parse(String::from(*code)).unwrap();
-    assert_eq!(program.body, vec![Statement::Expression(ExpressionStatement {
+    assert_eq!(without_spans(program.body), vec![Statement::Expression(ExpressionStatement {
       expression: Expression::Number(NumberLiteral { 
         literal: String::from(*literal), 
-        value: *value 
-      })
+        value: *value,
+        span: Span::default(), 
+      }),
+      span: Span::default(),
     })], "Failed for code: {}", code);
   }
 }
@@ -415,11 +463,13 @@ fn ast_lexer_binary_number_literal() {
   let mut jsi_vm = JSI::new();
   for (code, literal, value) in test_cases.iter() {
     let program = jsi_vm.parse(String::from(*code)).unwrap();
-    assert_eq!(program.body, vec![Statement::Expression(ExpressionStatement {
+    assert_eq!(without_spans(program.body), vec![Statement::Expression(ExpressionStatement {
       expression: Expression::Number(NumberLiteral { 
         literal: String::from(*literal), 
-        value: *value 
-      })
+        value: *value,
+        span: Span::default(), 
+      }),
+      span: Span::default(),
     })], "Failed for code: {}", code);
   }
 }
@@ -437,11 +487,13 @@ fn ast_lexer_decimal_number_literal() {
   let mut jsi_vm = JSI::new();
   for (code, literal, value) in test_cases.iter() {
     let program = jsi_vm.parse(String::from(*code)).unwrap();
-    assert_eq!(program.body, vec![Statement::Expression(ExpressionStatement {
+    assert_eq!(without_spans(program.body), vec![Statement::Expression(ExpressionStatement {
       expression: Expression::Number(NumberLiteral { 
         literal: String::from(*literal), 
-        value: *value 
-      })
+        value: *value,
+        span: Span::default(), 
+      }),
+      span: Span::default(),
     })], "Failed for code: {}", code);
   }
 }
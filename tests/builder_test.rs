@@ -0,0 +1,93 @@
+use std::rc::Rc;
+
+use jsi::{JSI, value::Value, error::JSIErrorType, ast_node::ClassType, builtins::{function::host_function, object::{create_object, Property}}};
+
+#[test]
+fn builder_without_global() {
+  let mut jsi_vm = JSI::builder().without_global("console").without_global("Function").without_global("Array").build();
+  let result = jsi_vm.run(String::from("[typeof console, typeof Function, typeof Array, typeof Object.keys].join(',')")).unwrap();
+  assert_eq!(result, Value::String(String::from("undefined,undefined,undefined,function")));
+  let err = jsi_vm.run(String::from("console.log(1)")).unwrap_err();
+  assert_eq!(err.error_type, JSIErrorType::ReferenceError);
+  // 移除全局变量后，内置方法仍然可以使用
+  let result = jsi_vm.run(String::from("[1, 2, 3].map(function(item) { return item * 2; }).join(',')")).unwrap();
+  assert_eq!(result, Value::String(String::from("2,4,6")));
+  // 移除 Function 后不能通过字符串生成代码
+  let result = jsi_vm.run(String::from("\
+let message;
+try {
+  (function(){}).constructor('return 1;');
+} catch (e) {
+  message = e.name + ': ' + e.message;
+}
+message")).unwrap();
+  assert_eq!(result, Value::String(String::from("EvalError: Code generation from strings disallowed for this context")));
+}
+
+#[test]
+fn builder_with_global() {
+  let mut jsi_vm = JSI::builder().with_global("console", |ctx| {
+    let log = host_function(ctx, String::from("log"), 1f64, Rc::new(|call_ctx, args, _| {
+      let value = args.first().cloned().unwrap_or(Value::Undefined);
+      call_ctx.ctx.define_global_value(String::from("logged"), value);
+      Ok(Value::Undefined)
+    }));
+    let console = create_object(ctx, ClassType::Object, None);
+    console.borrow_mut().define_property(String::from("log"), Property { enumerable: true, value: log });
+    Value::Object(console)
+  }).build();
+  let result = jsi_vm.run(String::from("console.log('hello'); [logged, typeof console.warn].join(',')")).unwrap();
+  assert_eq!(result, Value::String(String::from("hello,undefined")));
+  // 只替换 console 时仍然可以使用 Function 构造方法
+  let result = jsi_vm.run(String::from("Function('a', 'return a + 1;')(1)")).unwrap();
+  assert_eq!(result, Value::Number(2f64));
+}
+
+#[test]
+fn builder_freeze_intrinsics() {
+  let mut jsi_vm = JSI::builder().freeze_intrinsics(true).build();
+  // 非严格模式下修改冻结的对象会被忽略
+  jsi_vm.set_strict(false);
+  let result = jsi_vm.run(String::from("\
+let messages = [];
+Array.prototype.push = function() { return 'patched'; };
+messages.push(Array.prototype.push.name);
+Object.prototype.polluted = 1;
+String.fromCharCode = null;
+delete Array.prototype.map;
+messages.push(typeof ({}).polluted, typeof String.fromCharCode, typeof [].map);
+let list = [1];
+list.push(2);
+list.extra = 1;
+globalThis.custom = 1;
+messages.push(list.length, list.extra, globalThis.custom);
+messages.join(',')")).unwrap();
+  assert_eq!(result, Value::String(String::from("push,undefined,function,function,2,1,1")));
+
+  jsi_vm.set_strict(true);
+  for code in [
+    "Array.prototype.push = null",
+    "delete Array.prototype.map",
+    "Object.assign(Array.prototype, { map: null })",
+    "Object.setPrototypeOf(console, null)",
+  ] {
+    let err = jsi_vm.run(String::from(code)).unwrap_err();
+    assert_eq!(err.error_type, JSIErrorType::TypeError);
+  }
+  assert_eq!(jsi_vm.run(String::from("[1].concat([2]).length")).unwrap(), Value::Number(2f64));
+}
+
+#[test]
+fn builder_config_applies_to_new_realms() {
+  let mut jsi_vm = JSI::builder().freeze_intrinsics(true).without_global("console").with_global("host", |_| {
+    Value::String(String::from("host"))
+  }).build();
+  let plugin = jsi_vm.create_realm();
+  let result = jsi_vm.run_in_realm(plugin, String::from("[typeof console, host].join(',')")).unwrap();
+  assert_eq!(result, Value::String(String::from("undefined,host")));
+  let err = jsi_vm.run_in_realm(plugin, String::from("Array.prototype.foo = 1")).unwrap_err();
+  assert_eq!(err.error_type, JSIErrorType::TypeError);
+  // 新领域的内置对象是独立的，同样被冻结
+  let result = jsi_vm.run_in_realm(plugin, String::from("[typeof [].foo, Array === globalThis.Array].join(',')")).unwrap();
+  assert_eq!(result, Value::String(String::from("undefined,true")));
+}
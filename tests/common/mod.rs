@@ -0,0 +1,222 @@
+#![allow(dead_code)]
+
+use jsi::ast_node::{BlockStatement, CaseClause, ClassDeclaration, Expression, FunctionDeclaration, IdentifierLiteral, Parameter, Span, Statement};
+
+// 清除 AST 中的位置信息，用于只比较结构的测试，期望的节点使用 Span::default()
+pub fn without_spans(mut statements: Vec<Statement>) -> Vec<Statement> {
+  statements.iter_mut().for_each(clear_statement);
+  statements
+}
+
+pub fn expression_without_spans(mut expression: Expression) -> Expression {
+  clear_expression(&mut expression);
+  expression
+}
+
+fn clear_statement(statement: &mut Statement) {
+  match statement {
+    Statement::Block(block) => clear_block(block),
+    Statement::Break(statement) => {
+      statement.span = Span::default();
+      statement.label.iter_mut().for_each(clear_identifier);
+    },
+    Statement::Class(class) => clear_class(class),
+    Statement::Continue(statement) => {
+      statement.span = Span::default();
+      statement.label.iter_mut().for_each(clear_identifier);
+    },
+    Statement::Expression(statement) => {
+      statement.span = Span::default();
+      clear_expression(&mut statement.expression);
+    },
+    Statement::For(statement) | Statement::While(statement) => {
+      statement.span = Span::default();
+      clear_statement(&mut statement.initializer);
+      clear_expression(&mut statement.condition);
+      clear_expression(&mut statement.incrementor);
+      clear_statement(&mut statement.statement);
+    },
+    Statement::ForIn(statement) => {
+      statement.span = Span::default();
+      clear_expression(&mut statement.variable);
+      clear_expression(&mut statement.object);
+      clear_statement(&mut statement.statement);
+    },
+    Statement::ForOf(statement) => {
+      statement.span = Span::default();
+      clear_expression(&mut statement.variable);
+      clear_expression(&mut statement.object);
+      clear_statement(&mut statement.statement);
+    },
+    Statement::Function(function) => clear_function(function),
+    Statement::If(statement) => {
+      statement.span = Span::default();
+      clear_expression(&mut statement.condition);
+      clear_statement(&mut statement.then_statement);
+      clear_statement(&mut statement.else_statement);
+    },
+    Statement::Label(statement) => {
+      statement.span = Span::default();
+      clear_identifier(&mut statement.label);
+      clear_statement(&mut statement.statement);
+    },
+    Statement::Return(statement) => {
+      statement.span = Span::default();
+      clear_expression(&mut statement.expression);
+    },
+    Statement::Switch(statement) => {
+      statement.span = Span::default();
+      clear_expression(&mut statement.condition);
+      statement.clauses.iter_mut().for_each(clear_case_clause);
+    },
+    Statement::Throw(statement) => {
+      statement.span = Span::default();
+      clear_expression(&mut statement.expression);
+    },
+    Statement::Try(statement) => {
+      statement.span = Span::default();
+      clear_block(&mut statement.body);
+      if let Some(catch) = &mut statement.catch {
+        catch.span = Span::default();
+        catch.declaration.iter_mut().for_each(clear_identifier);
+        clear_block(&mut catch.body);
+      }
+      statement.finally.iter_mut().for_each(clear_block);
+    },
+    Statement::Var(statement) => {
+      statement.span = Span::default();
+      statement.list.iter_mut().for_each(clear_expression);
+    },
+    Statement::BuiltinFunction(_) | Statement::HostFunction(_) | Statement::Unknown => {},
+  }
+}
+
+fn clear_expression(expression: &mut Expression) {
+  match expression {
+    Expression::Var(expr) => {
+      expr.span = Span::default();
+      clear_expression(&mut expr.initializer);
+    },
+    Expression::Assign(expr) => {
+      expr.span = Span::default();
+      clear_expression(&mut expr.left);
+      clear_expression(&mut expr.right);
+    },
+    Expression::Binary(expr) => {
+      expr.span = Span::default();
+      clear_expression(&mut expr.left);
+      clear_expression(&mut expr.right);
+    },
+    Expression::Conditional(expr) => {
+      expr.span = Span::default();
+      clear_expression(&mut expr.condition);
+      clear_expression(&mut expr.when_true);
+      clear_expression(&mut expr.when_false);
+    },
+    Expression::PropertyAccess(expr) => {
+      expr.span = Span::default();
+      clear_expression(&mut expr.expression);
+      clear_identifier(&mut expr.name);
+    },
+    Expression::ElementAccess(expr) => {
+      expr.span = Span::default();
+      clear_expression(&mut expr.expression);
+      clear_expression(&mut expr.argument);
+    },
+    Expression::Call(expr) => {
+      expr.span = Span::default();
+      clear_expression(&mut expr.expression);
+      expr.arguments.iter_mut().for_each(clear_expression);
+    },
+    Expression::PrefixUnary(expr) => {
+      expr.span = Span::default();
+      clear_expression(&mut expr.operand);
+    },
+    Expression::PostfixUnary(expr) => {
+      expr.span = Span::default();
+      clear_expression(&mut expr.operand);
+    },
+    Expression::Group(expr) => {
+      expr.span = Span::default();
+      clear_expression(&mut expr.expression);
+    },
+    Expression::Identifier(expr) => clear_identifier(expr),
+    Expression::Number(expr) => expr.span = Span::default(),
+    Expression::String(expr) => expr.span = Span::default(),
+    Expression::Keyword(_, span) | Expression::Value(_, span) => *span = Span::default(),
+    Expression::Object(expr) => {
+      expr.span = Span::default();
+      for property in expr.properties.iter_mut() {
+        property.span = Span::default();
+        clear_expression(&mut property.name);
+        clear_expression(&mut property.initializer);
+      }
+    },
+    Expression::Array(expr) => {
+      expr.span = Span::default();
+      expr.elements.iter_mut().for_each(clear_expression);
+    },
+    Expression::Function(function) | Expression::Constructor(function) => clear_function(function),
+    Expression::New(expr) => {
+      expr.span = Span::default();
+      clear_expression(&mut expr.expression);
+      expr.arguments.iter_mut().for_each(clear_expression);
+    },
+    Expression::Sequence(expr) => {
+      expr.span = Span::default();
+      expr.expressions.iter_mut().for_each(clear_expression);
+    },
+    Expression::TemplateLiteral(expr) => {
+      expr.span = Span::default();
+      expr.spans.iter_mut().for_each(clear_expression);
+    },
+    Expression::ComputedPropertyName(expr) => {
+      expr.span = Span::default();
+      clear_expression(&mut expr.expression);
+    },
+    Expression::Class(class) => clear_class(class),
+    Expression::ClassMethod(method) => {
+      method.span = Span::default();
+      clear_identifier(&mut method.name);
+      clear_function(&mut method.method);
+    },
+    Expression::Unknown => {},
+  }
+}
+
+fn clear_identifier(identifier: &mut IdentifierLiteral) {
+  identifier.span = Span::default();
+}
+
+fn clear_block(block: &mut BlockStatement) {
+  block.span = Span::default();
+  block.statements.iter_mut().for_each(clear_statement);
+}
+
+fn clear_case_clause(clause: &mut CaseClause) {
+  clause.span = Span::default();
+  clause.condition.iter_mut().for_each(clear_expression);
+  clause.statements.iter_mut().for_each(clear_statement);
+}
+
+fn clear_parameter(parameter: &mut Parameter) {
+  parameter.span = Span::default();
+  clear_identifier(&mut parameter.name);
+  clear_expression(&mut parameter.initializer);
+}
+
+fn clear_function(function: &mut FunctionDeclaration) {
+  function.span = Span::default();
+  clear_identifier(&mut function.name);
+  function.parameters.iter_mut().for_each(clear_parameter);
+  clear_block(&mut function.body);
+}
+
+fn clear_class(class: &mut ClassDeclaration) {
+  class.span = Span::default();
+  clear_identifier(&mut class.name);
+  class.members.iter_mut().for_each(clear_expression);
+  if let Some(heritage) = &mut class.heritage {
+    clear_class(heritage);
+  }
+}
@@ -0,0 +1,100 @@
+use std::{cell::RefCell, rc::Rc};
+
+use jsi::{JSI, builtins::console::ConsoleLevel};
+
+fn capture_console(jsi_vm: &mut JSI) -> Rc<RefCell<Vec<(ConsoleLevel, String)>>> {
+  let output = Rc::new(RefCell::new(vec![]));
+  let sink_output = Rc::clone(&output);
+  jsi_vm.set_console_sink(move |level: ConsoleLevel, message: &str| {
+    sink_output.borrow_mut().push((level, message.to_string()));
+  });
+  output
+}
+
+#[test]
+fn console_levels_and_format() {
+  let mut jsi_vm = JSI::new();
+  let output = capture_console(&mut jsi_vm);
+  jsi_vm.run(String::from("\
+console.log('a', 1, true);
+console.info('%s has %d items', 'list', 3, 'extra');
+console.debug('%i%% done', 42.9);
+console.warn('%d', 'x');
+console.error('%o', 'value', '%s');
+console.log('100%', '%c styled');
+console.assert(1 === 1, 'not printed');
+console.assert(1 === 2, 'expected %s', 'equal');
+console.assert(false);")).unwrap();
+  assert_eq!(*output.borrow(), vec![
+    (ConsoleLevel::Log, String::from("a 1 true")),
+    (ConsoleLevel::Info, String::from("list has 3 items extra")),
+    (ConsoleLevel::Debug, String::from("42% done")),
+    (ConsoleLevel::Warn, String::from("NaN")),
+    (ConsoleLevel::Error, String::from("'value' %s")),
+    (ConsoleLevel::Log, String::from("100% %c styled")),
+    (ConsoleLevel::Error, String::from("Assertion failed: expected equal")),
+    (ConsoleLevel::Error, String::from("Assertion failed")),
+  ]);
+}
+
+#[test]
+fn console_group_count_time() {
+  let mut jsi_vm = JSI::new();
+  let output = capture_console(&mut jsi_vm);
+  jsi_vm.run(String::from("\
+console.group('outer');
+console.log('one');
+console.group();
+console.count();
+console.count('task');
+console.count();
+console.groupEnd();
+console.groupEnd();
+console.groupEnd();
+console.countReset();
+console.count();
+console.time('load');
+console.timeEnd('load');
+console.timeEnd('load');")).unwrap();
+  let output = output.borrow();
+  let messages: Vec<&str> = output.iter().map(|(_, message)| message.as_str()).collect();
+  assert_eq!(messages[..6], [
+    "outer",
+    "  one",
+    "    default: 1",
+    "    task: 1",
+    "    default: 2",
+    "default: 1",
+  ]);
+  assert!(messages[6].starts_with("load: ") && messages[6].ends_with("ms"));
+  assert_eq!(output[7], (ConsoleLevel::Warn, String::from("Warning: No such label 'load' for console.timeEnd()")));
+}
+
+#[test]
+fn console_trace_and_table() {
+  let mut jsi_vm = JSI::new();
+  let output = capture_console(&mut jsi_vm);
+  jsi_vm.run(String::from("\
+function inner() { console.trace('here'); }
+inner();
+console.table([{ a: 1, b: 'x' }, { a: 2, c: true }]);
+console.table(['p', 'q'], ['a']);
+console.table(1);")).unwrap();
+  let output = output.borrow();
+  assert_eq!(output[0], (ConsoleLevel::Trace, String::from("Trace: here\n    at inner (<anonymous>:1:20)\n    at <anonymous>:2:1")));
+  assert_eq!(output[1].1, "\
+┌─────────┬───┬─────┬──────┐
+│ (index) │ a │ b   │ c    │
+├─────────┼───┼─────┼──────┤
+│ 0       │ 1 │ 'x' │      │
+│ 1       │ 2 │     │ true │
+└─────────┴───┴─────┴──────┘");
+  assert_eq!(output[2].1, "\
+┌─────────┬───┬────────┐
+│ (index) │ a │ Values │
+├─────────┼───┼────────┤
+│ 0       │   │ 'p'    │
+│ 1       │   │ 'q'    │
+└─────────┴───┴────────┘");
+  assert_eq!(output[3].1, "1");
+}
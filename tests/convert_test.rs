@@ -0,0 +1,80 @@
+use std::collections::HashMap;
+
+use jsi::{JSI, value::Value, error::{JSIErrorType, JSIResult}};
+
+#[test]
+fn convert_primitive_from_js() {
+  let mut jsi_vm = JSI::new();
+  let value = jsi_vm.run(String::from("new Number(3)")).unwrap();
+  assert_eq!(jsi_vm.convert_from_js::<f64>(&value).unwrap(), 3f64);
+  assert_eq!(jsi_vm.convert_from_js::<i32>(&value).unwrap(), 3);
+  let value = jsi_vm.run(String::from("0 / 0")).unwrap();
+  assert!(jsi_vm.convert_from_js::<f64>(&value).unwrap().is_nan());
+  let value = jsi_vm.run(String::from("new String('abc')")).unwrap();
+  assert_eq!(jsi_vm.convert_from_js::<String>(&value).unwrap(), String::from("abc"));
+  let value = jsi_vm.run(String::from("!0")).unwrap();
+  assert_eq!(jsi_vm.convert_from_js::<bool>(&value).unwrap(), true);
+
+  let value = jsi_vm.run(String::from("1.5")).unwrap();
+  let result = jsi_vm.convert_from_js::<u8>(&value);
+  assert_eq!(result.unwrap_err().error_type, JSIErrorType::TypeError);
+  let result = jsi_vm.convert_from_js::<String>(&Value::Null);
+  assert_eq!(result.unwrap_err().message, String::from("Cannot convert null to string"));
+}
+
+#[test]
+fn convert_collection_from_js() {
+  let mut jsi_vm = JSI::new();
+  let value = jsi_vm.run(String::from("[1, 2, 3]")).unwrap();
+  assert_eq!(jsi_vm.convert_from_js::<Vec<i64>>(&value).unwrap(), vec![1, 2, 3]);
+  let value = jsi_vm.run(String::from("[1, null, undefined]")).unwrap();
+  assert_eq!(jsi_vm.convert_from_js::<Vec<Option<f64>>>(&value).unwrap(), vec![Some(1f64), None, None]);
+  let value = jsi_vm.run(String::from("['a', 1, true]")).unwrap();
+  assert_eq!(jsi_vm.convert_from_js::<(String, u32, bool)>(&value).unwrap(), (String::from("a"), 1, true));
+  assert!(jsi_vm.convert_from_js::<(String, u32)>(&value).is_err());
+
+  let value = jsi_vm.run(String::from("({ a: [1], b: [2, 3] })")).unwrap();
+  let map = jsi_vm.convert_from_js::<HashMap<String, Vec<u32>>>(&value).unwrap();
+  assert_eq!(map.get("a"), Some(&vec![1]));
+  assert_eq!(map.get("b"), Some(&vec![2, 3]));
+
+  let value = jsi_vm.run(String::from("['a', 2]")).unwrap();
+  let result = jsi_vm.convert_from_js::<Vec<String>>(&value);
+  assert_eq!(result.unwrap_err().message, String::from("Cannot convert number to string"));
+}
+
+#[test]
+fn convert_into_js() {
+  let mut jsi_vm = JSI::new();
+  let mut map: HashMap<String, (i32, Option<String>)> = HashMap::new();
+  map.insert(String::from("b"), (2, None));
+  map.insert(String::from("a"), (1, Some(String::from("x"))));
+  let value = jsi_vm.convert_to_js(map);
+  jsi_vm.set_global(String::from("data"), value);
+  let value = jsi_vm.convert_to_js(vec![1.5f64, f64::NAN]);
+  jsi_vm.set_global(String::from("list"), value);
+  let result = jsi_vm.run(String::from("\
+Object.keys(data).join(',') + ':' + data.a[1] + ':' + typeof data.b[1] + ':' + Array.isArray(list) + ':' + list[0] + ':' + isNaN(list[1])")).unwrap();
+  assert_eq!(result, Value::String(String::from("a,b:x:undefined:true:1.5:true")));
+}
+
+#[test]
+fn convert_typed_host_function() {
+  let mut jsi_vm = JSI::new();
+  jsi_vm.register_typed_function(String::from("add"), |a: f64, b: Option<f64>| -> JSIResult<f64> {
+    Ok(a + b.unwrap_or(10f64))
+  });
+  jsi_vm.register_typed_function(String::from("split"), |text: String, separator: String| -> JSIResult<Vec<String>> {
+    Ok(text.split(separator.as_str()).map(|item| item.to_string()).collect())
+  });
+  let result = jsi_vm.run(String::from("add(1, 2) + ':' + add(1) + ':' + add.length + ':' + split('a-b', '-').length")).unwrap();
+  assert_eq!(result, Value::String(String::from("3:11:2:2")));
+
+  let result = jsi_vm.run(String::from("\
+try {
+  add('1');
+} catch (e) {
+  e instanceof TypeError ? e.message : 'fail'
+}")).unwrap();
+  assert_eq!(result, Value::String(String::from("Cannot convert string to number")));
+}
@@ -0,0 +1,83 @@
+use jsi::{JSI, value::Value, error::JSIErrorType};
+
+#[test]
+fn embed_get_and_set_global() {
+  let mut jsi_vm = JSI::new();
+  jsi_vm.run(String::from("let config = 'prod'; var count = 1;")).unwrap();
+  assert_eq!(jsi_vm.get_global(String::from("config")), Value::String(String::from("prod")));
+  assert_eq!(jsi_vm.get_global(String::from("count")), Value::Number(1f64));
+  assert_eq!(jsi_vm.get_global(String::from("notExists")), Value::Undefined);
+
+  jsi_vm.set_global(String::from("limit"), Value::Number(3f64));
+  let result = jsi_vm.run(String::from("limit = limit + 1; limit + ':' + globalThis.limit")).unwrap();
+  assert_eq!(result, Value::String(String::from("4:3")));
+  assert_eq!(jsi_vm.get_global(String::from("limit")), Value::Number(4f64));
+}
+
+#[test]
+fn embed_call_function() {
+  let mut jsi_vm = JSI::new();
+  jsi_vm.run(String::from("\
+let prefix = 'hello ';
+function handler(name) {
+  return prefix + name + (this && this.suffix ? this.suffix : '');
+}")).unwrap();
+  let handler = jsi_vm.get_global(String::from("handler"));
+  let result = jsi_vm.call(&handler, Value::Undefined, vec![Value::String(String::from("a"))]).unwrap();
+  assert_eq!(result, Value::String(String::from("hello a")));
+
+  let this = jsi_vm.run(String::from("({ suffix: '!' })")).unwrap();
+  let result = jsi_vm.call(&handler, this, vec![Value::String(String::from("b"))]).unwrap();
+  assert_eq!(result, Value::String(String::from("hello b!")));
+
+  let result = jsi_vm.call(&Value::Number(1f64), Value::Undefined, vec![]);
+  if let Err(jsi_error) = result {
+    assert_eq!(jsi_error.error_type, JSIErrorType::TypeError);
+    assert_eq!(jsi_error.message, String::from("1 is not a function"));
+  } else {
+    assert!(false, "need type error");
+  }
+}
+
+#[test]
+fn embed_call_function_throw() {
+  let mut jsi_vm = JSI::new();
+  jsi_vm.run(String::from("function fail(message) {\n  throw new RangeError(message);\n}")).unwrap();
+  let fail = jsi_vm.get_global(String::from("fail"));
+  let result = jsi_vm.call(&fail, Value::Undefined, vec![Value::String(String::from("bad"))]);
+  if let Err(jsi_error) = result {
+    assert_eq!(jsi_error.to_string(), String::from("RangeError: bad (<anonymous>:2:3)"));
+  } else {
+    assert!(false, "need range error");
+  }
+  // 出错后仍然可以继续执行
+  let result = jsi_vm.run(String::from("typeof fail")).unwrap();
+  assert_eq!(result, Value::String(String::from("function")));
+}
+
+#[test]
+fn embed_construct() {
+  let mut jsi_vm = JSI::new();
+  jsi_vm.run(String::from("\
+function Point(x, y) {
+  this.x = x;
+  this.y = y;
+}
+Point.prototype.sum = function() {
+  return this.x + this.y;
+};")).unwrap();
+  let point_class = jsi_vm.get_global(String::from("Point"));
+  let point = jsi_vm.construct(&point_class, vec![Value::Number(1f64), Value::Number(2f64)]).unwrap();
+  jsi_vm.set_global(String::from("point"), point);
+  let result = jsi_vm.run(String::from("point.sum() + ':' + (point instanceof Point)")).unwrap();
+  assert_eq!(result, Value::String(String::from("3:true")));
+
+  let array_class = jsi_vm.get_global(String::from("Array"));
+  let array = jsi_vm.construct(&array_class, vec![Value::Number(3f64)]).unwrap();
+  jsi_vm.set_global(String::from("array"), array);
+  let result = jsi_vm.run(String::from("array.length")).unwrap();
+  assert_eq!(result, Value::Number(3f64));
+
+  let result = jsi_vm.construct(&Value::Number(1f64), vec![]);
+  assert!(result.is_err());
+}
@@ -1,4 +1,4 @@
-use jsi::{JSI, value::Value, error::JSIErrorType};
+use jsi::{JSI, value::Value, error::JSIErrorType, ast_token::{get_token_description, Token}};
 
 #[test]
 fn run_throw_new_error() {
@@ -181,4 +181,200 @@ fn run_error_test() {
     assert_eq!(jsi_error.error_type, JSIErrorType::SyntaxError);
     assert_eq!(jsi_error.message , String::from("Duplicate parameter name not allowed in this context"));
   }
-}
\ No newline at end of file
+}
+#[test]
+fn run_native_error_constructors() {
+  let mut jsi = JSI::new();
+  let result = jsi.run(String::from("\
+  let names = [RangeError, SyntaxError, ReferenceError, EvalError, URIError, TypeError].map(ErrorType => {
+    let err = new ErrorType('msg');
+    return err instanceof ErrorType && err instanceof Error && err.name + ':' + err.message;
+  });
+  names.join()
+  ")).unwrap();
+  assert_eq!(result, Value::String(String::from("RangeError:msg,SyntaxError:msg,ReferenceError:msg,EvalError:msg,URIError:msg,TypeError:msg")));
+}
+
+#[test]
+fn run_engine_error_instanceof() {
+  let mut jsi = JSI::new();
+  let result = jsi.run(String::from("\
+  let result = [];
+  try {
+    (1).toFixed(101);
+  } catch (e) {
+    result.push(e instanceof RangeError, e instanceof Error);
+  }
+  try {
+    notDefined;
+  } catch (e) {
+    result.push(e instanceof ReferenceError, e.name);
+  }
+  result.join()
+  ")).unwrap();
+  assert_eq!(result, Value::String(String::from("true,true,true,ReferenceError")));
+}
+
+#[test]
+fn run_error_to_string() {
+  let mut jsi = JSI::new();
+  let result = jsi.run(String::from("\
+  let err = new Error();
+  [new TypeError('bad value').toString(), err.toString(), err.message === '', Object.keys(new Error('x')).length].join()
+  ")).unwrap();
+  assert_eq!(result, Value::String(String::from("TypeError: bad value,Error,true,0")));
+}
+
+#[test]
+fn run_error_cause() {
+  let mut jsi = JSI::new();
+  let result = jsi.run(String::from("\
+  let inner = new Error('inner');
+  let outer = new Error('outer', { cause: inner });
+  [outer.cause === inner, new Error('x', {}).hasOwnProperty('cause'), new Error('y', { cause: undefined }).hasOwnProperty('cause')].join()
+  ")).unwrap();
+  assert_eq!(result, Value::String(String::from("true,false,true")));
+}
+
+#[test]
+fn run_aggregate_error() {
+  let mut jsi = JSI::new();
+  let result = jsi.run(String::from("\
+  let err = new AggregateError([new Error('a'), 'b'], 'failed', { cause: 'why' });
+  [err instanceof AggregateError, err instanceof Error, err.errors.length, err.errors[1], err.message, err.cause, err.toString()].join()
+  ")).unwrap();
+  assert_eq!(result, Value::String(String::from("true,true,2,b,failed,why,AggregateError: failed")));
+}
+
+#[test]
+fn run_error_stack_property() {
+  let mut jsi = JSI::new();
+  let result = jsi.run(String::from("\
+function thrower() {
+  throw new RangeError('bad');
+}
+let stack = '';
+try {
+  thrower();
+} catch (e) {
+  stack = e.stack;
+}
+stack")).unwrap();
+  assert_eq!(result, Value::String(String::from("RangeError: bad\n    at thrower (<anonymous>:2:9)\n    at <anonymous>:6:3")));
+}
+
+#[test]
+fn run_runtime_error_stack_property() {
+  let mut jsi = JSI::new();
+  let result = jsi.run(String::from("\
+function read(obj) {
+  return obj.value;
+}
+function run() {
+  return read(null);
+}
+let stack = '';
+try {
+  run();
+} catch (e) {
+  stack = e.stack;
+}
+stack")).unwrap();
+  assert_eq!(result, Value::String(String::from("TypeError: Cannot read properties of null (reading 'value')\n    at read (<anonymous>:2:10)\n    at run (<anonymous>:5:10)\n    at <anonymous>:9:3")));
+}
+
+#[test]
+fn run_uncaught_error_stack_frames() {
+  let mut jsi = JSI::new();
+  let result = jsi.run(String::from("\
+function check(value) {
+  if (value > 1) {
+    throw new Error('too large');
+  }
+}
+check(1);
+check(2);"));
+  if let Err(jsi_error) = result {
+    let frames: Vec<(String, i32, i32)> = jsi_error.stack.iter().map(|frame| (frame.function_name.clone(), frame.line, frame.column)).collect();
+    assert_eq!(frames, vec![(String::from("check"), 3, 5), (String::from(""), 7, 1)]);
+    assert_eq!((jsi_error.line, jsi_error.column), (3, 5));
+  } else {
+    assert!(false, "need error");
+  }
+}
+
+#[test]
+fn run_syntax_error_display() {
+  let mut jsi = JSI::new();
+  let result = jsi.run(String::from("let a = 1;\nlet b = (2 +;"));
+  if let Err(jsi_error) = result {
+    assert_eq!(jsi_error.message, String::from("Unexpected token ';'"));
+    assert_eq!(jsi_error.expected, Some(String::from("expression")));
+    assert_eq!(jsi_error.to_string(), String::from("SyntaxError: Unexpected token ';' (<anonymous>:2:13)"));
+  } else {
+    assert!(false, "need SyntaxError");
+  }
+}
+
+#[test]
+fn run_syntax_error_render() {
+  let mut jsi = JSI::new();
+  let code = String::from("foo(1,\n  2");
+  let result = jsi.run(code.clone());
+  if let Err(jsi_error) = result {
+    assert_eq!(jsi_error.render(&code), String::from("\
+SyntaxError: Unexpected end of input
+ --> <anonymous>:2:4
+  |
+2 |   2
+  |    ^ expected ')'"));
+  } else {
+    assert!(false, "need SyntaxError");
+  }
+}
+
+#[test]
+fn run_runtime_error_render() {
+  let mut jsi = JSI::new();
+  let code = String::from("let obj = null;\n\tobj.name;");
+  let result = jsi.run(code.clone());
+  if let Err(jsi_error) = result {
+    let error: &dyn std::error::Error = &jsi_error;
+    assert_eq!(error.to_string(), String::from("TypeError: Cannot read properties of null (reading 'name') (<anonymous>:2:2)"));
+    assert_eq!(jsi_error.render(&code), String::from("\
+TypeError: Cannot read properties of null (reading 'name')
+ --> <anonymous>:2:2
+  |
+2 | \tobj.name;
+  | \t^"));
+  } else {
+    assert!(false, "need TypeError");
+  }
+}
+
+#[test]
+fn run_thrown_value_display() {
+  let mut jsi = JSI::new();
+  let result = jsi.run(String::from("throw new RangeError('bad')"));
+  if let Err(jsi_error) = result {
+    assert_eq!(jsi_error.to_string(), String::from("RangeError: bad (<anonymous>:1:1)"));
+  } else {
+    assert!(false, "need error");
+  }
+  let result = jsi.run(String::from("throw 'oops'"));
+  if let Err(jsi_error) = result {
+    assert_eq!(jsi_error.to_string(), String::from("Uncaught oops (<anonymous>:1:1)"));
+  } else {
+    assert!(false, "need error");
+  }
+}
+
+#[test]
+fn run_token_description_source_text() {
+  assert_eq!(get_token_description(&Token::LessOrEqual), String::from("'<='"));
+  assert_eq!(get_token_description(&Token::StrictNotEqual), String::from("'!=='"));
+  assert_eq!(get_token_description(&Token::NullishCoalescingAssign), String::from("'??='"));
+  assert_eq!(get_token_description(&Token::OptionalChaining), String::from("'?.'"));
+  assert_eq!(get_token_description(&Token::Instanceof), String::from("'instanceof'"));
+  assert_eq!(get_token_description(&Token::Identifier), String::from("identifier"));
+}
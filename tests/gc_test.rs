@@ -0,0 +1,45 @@
+use jsi::{JSI, value::Value};
+
+#[test]
+fn gc_collect_cycles() {
+  let mut jsi_vm = JSI::new();
+  let base = jsi_vm.heap_stats().used;
+  jsi_vm.run(String::from("\
+for (let i = 0; i < 100; i++) {
+  let a = { i: i };
+  a.self = a;
+  let b = {};
+  b.fn = function() { return b; };
+  Promise.resolve(i).then(function(v) { return v + 1; });
+}")).unwrap();
+  let leaked = jsi_vm.heap_stats().used - base;
+  assert!(jsi_vm.gc() > 0);
+  let used = jsi_vm.heap_stats().used - base;
+  assert!(used < leaked / 10, "used {} leaked {}", used, leaked);
+  assert_eq!(jsi_vm.gc(), 0);
+}
+
+#[test]
+fn gc_keep_reachable() {
+  let mut jsi_vm = JSI::new();
+  jsi_vm.run(String::from("\
+function Point(x) { this.x = x; }
+Point.prototype.get = function() { return this.x; };
+let point = new Point(5);
+let node = { value: 1 };
+node.self = node;
+function counter() {
+  let count = 0;
+  return function() { return ++count; };
+}
+let next = counter();
+next();")).unwrap();
+  // 宿主持有的值也是存活的
+  let held = jsi_vm.run(String::from("let held = { value: 'held' }; held.self = held; held")).unwrap();
+  jsi_vm.run(String::from("held = null")).unwrap();
+  jsi_vm.gc();
+  let result = jsi_vm.run(String::from("[point.get(), node.self.value, next(), new Point(3).get()].join(',')")).unwrap();
+  assert_eq!(result, Value::String(String::from("5,1,2,3")));
+  jsi_vm.set_global(String::from("held"), held);
+  assert_eq!(jsi_vm.run(String::from("held.self.value")).unwrap(), Value::String(String::from("held")));
+}
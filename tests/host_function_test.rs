@@ -0,0 +1,79 @@
+use std::{cell::RefCell, rc::Rc};
+
+use jsi::{JSI, value::Value, error::{JSIError, JSIErrorType}};
+
+#[test]
+fn host_function_capture_state() {
+  let mut jsi_vm = JSI::new();
+  let counter = Rc::new(RefCell::new(0f64));
+  let counter_clone = Rc::clone(&counter);
+  jsi_vm.register_function(String::from("add"), move |_, args, _| {
+    let mut total = counter_clone.borrow_mut();
+    for arg in args.iter() {
+      if let Value::Number(number) = arg {
+        *total += number;
+      }
+    }
+    Ok(Value::Number(*total))
+  });
+  let result = jsi_vm.run(String::from("\
+add(1, 2);
+add(3);
+typeof add + ':' + add.name + ':' + globalThis.add(4)")).unwrap();
+  assert_eq!(result, Value::String(String::from("function:add:10")));
+  assert_eq!(*counter.borrow(), 10f64);
+}
+
+#[test]
+fn host_function_this_and_new_target() {
+  let mut jsi_vm = JSI::new();
+  jsi_vm.register_function(String::from("Point"), |call_ctx, args, new_target| {
+    if let Value::Undefined = new_target {
+      return Err(JSIError::new(JSIErrorType::TypeError, String::from("Point must be called with new"), 0, 0));
+    }
+    let this = call_ctx.this.to_object(call_ctx.ctx);
+    let mut this_mut = this.borrow_mut();
+    this_mut.define_property(String::from("x"), jsi::builtins::object::Property { enumerable: true, value: args[0].clone() });
+    this_mut.define_property(String::from("y"), jsi::builtins::object::Property { enumerable: true, value: args[1].clone() });
+    Ok(Value::Undefined)
+  });
+  let result = jsi_vm.run(String::from("\
+let p = new Point(1, 2);
+p.x + p.y + ':' + (p instanceof Point)")).unwrap();
+  assert_eq!(result, Value::String(String::from("3:true")));
+
+  let result = jsi_vm.run(String::from("\
+try {
+  Point(1, 2);
+} catch (e) {
+  e.message
+}"));
+  assert_eq!(result.unwrap(), Value::String(String::from("Point must be called with new")));
+}
+
+#[test]
+fn host_function_on_object() {
+  let mut jsi_vm = JSI::new();
+  let api = jsi_vm.run(String::from("let api = { base: 10 }; api")).unwrap();
+  jsi_vm.define_function(&api, String::from("plus"), |call_ctx, args, _| {
+    let base = call_ctx.this.to_object(call_ctx.ctx).borrow().get_property_value(String::from("base"));
+    match (base, &args[0]) {
+      (Value::Number(base), Value::Number(number)) => Ok(Value::Number(base + number)),
+      _ => Ok(Value::NAN),
+    }
+  }).unwrap();
+  let result = jsi_vm.run(String::from("api.plus(5) + [1, 2].map(x => api.plus(x)).join(',')")).unwrap();
+  assert_eq!(result, Value::String(String::from("1511,12")));
+
+  let result = jsi_vm.define_function(&Value::Number(1f64), String::from("plus"), |_, _, _| Ok(Value::Undefined));
+  assert!(result.is_err());
+}
+
+#[test]
+fn host_function_length() {
+  let mut jsi_vm = JSI::new();
+  let function = jsi_vm.create_function(String::from("pair"), 2, |_, args, _| Ok(Value::Number(args.len() as f64)));
+  jsi_vm.set_global(String::from("pair"), function);
+  let result = jsi_vm.run(String::from("pair.length + ':' + pair.name + ':' + pair(1, 2, 3)")).unwrap();
+  assert_eq!(result, Value::String(String::from("2:pair:3")));
+}
@@ -0,0 +1,61 @@
+use std::{cell::RefCell, rc::Rc};
+
+use jsi::{JSI, value::Value, inspect::{inspect, InspectOptions}, builtins::console::ConsoleLevel};
+
+fn inspect_code(jsi_vm: &mut JSI, code: &str) -> String {
+  let value = jsi_vm.run(String::from(code)).unwrap();
+  jsi_vm.inspect(&value)
+}
+
+#[test]
+fn inspect_values() {
+  let mut jsi_vm = JSI::new();
+  assert_eq!(inspect_code(&mut jsi_vm, "['a', \"it's\", 1, -0, NaN, true, null, undefined]"), "[ 'a', \"it's\", 1, -0, NaN, true, null, undefined ]");
+  assert_eq!(inspect_code(&mut jsi_vm, "({ a: 1, 'b-c': { d: [] }, e: {} })"), "{ a: 1, 'b-c': { d: [] }, e: {} }");
+  assert_eq!(inspect_code(&mut jsi_vm, "({ 'a-b': 1, 3: 4, 1: 2 })"), "{ '1': 2, '3': 4, 'a-b': 1 }");
+  assert_eq!(inspect_code(&mut jsi_vm, "[function named() {}, function() {}, Array]"), "[ [Function: named], [Function (anonymous)], [Function: Array] ]");
+  assert_eq!(inspect_code(&mut jsi_vm, "function Point(x) { this.x = x; } new Point(1)"), "Point { x: 1 }");
+  assert_eq!(inspect_code(&mut jsi_vm, "Object.create(null)"), "[Object: null prototype] {}");
+  assert_eq!(inspect_code(&mut jsi_vm, "[new String('s'), new Number(1)]"), "[ [String: 's'], [Number: 1] ]");
+  assert_eq!(inspect_code(&mut jsi_vm, "let map = new Map(); map.set('a', 1); map.set(2, [3]); map"), "Map(2) { 'a' => 1, 2 => [ 3 ] }");
+  assert_eq!(inspect_code(&mut jsi_vm, "new Error('failed')"), "Error: failed\n    at <anonymous>:1:1");
+  assert_eq!(inspect_code(&mut jsi_vm, "({ error: new TypeError('bad') })"), "{\n  error: TypeError: bad\n      at <anonymous>:1:11\n}");
+}
+
+#[test]
+fn inspect_promise_state() {
+  let mut jsi_vm = JSI::new();
+  assert_eq!(inspect_code(&mut jsi_vm, "Promise.resolve({ done: true })"), "Promise { { done: true } }");
+  assert_eq!(inspect_code(&mut jsi_vm, "new Promise(function() {})"), "Promise { <pending> }");
+  assert_eq!(inspect_code(&mut jsi_vm, "Promise.reject('no')"), "Promise { <rejected> 'no' }");
+}
+
+#[test]
+fn inspect_depth_and_circular() {
+  let mut jsi_vm = JSI::new();
+  let value = jsi_vm.run(String::from("({ a: { b: { c: { d: 1 } } }, list: [[[[1]]]] })")).unwrap();
+  assert_eq!(jsi_vm.inspect(&value), "{ a: { b: { c: [Object] } }, list: [ [ [Array] ] ] }");
+  assert_eq!(inspect(&value, &InspectOptions { depth: 0, ..InspectOptions::default() }), "{ a: [Object], list: [Array] }");
+  assert_eq!(inspect(&value, &InspectOptions { depth: 10, ..InspectOptions::default() }), "{ a: { b: { c: { d: 1 } } }, list: [ [ [ [ 1 ] ] ] ] }");
+
+  let value = jsi_vm.run(String::from("let node = { name: 'root', children: [] }; node.children.push({ parent: node }); node.self = node; node")).unwrap();
+  assert_eq!(jsi_vm.inspect(&value), "<ref *1> { name: 'root', children: [ { parent: [Circular *1] } ], self: [Circular *1] }");
+
+  let value = jsi_vm.run(String::from("({ first: 'aaaaaaaaaaaaaaaaaaaa', second: 'bbbbbbbbbbbbbbbbbbbb', third: 'cccccccccccccccccccc' })")).unwrap();
+  assert_eq!(jsi_vm.inspect(&value), "{\n  first: 'aaaaaaaaaaaaaaaaaaaa',\n  second: 'bbbbbbbbbbbbbbbbbbbb',\n  third: 'cccccccccccccccccccc'\n}");
+  let value = jsi_vm.run(String::from("let long = []; for (let i = 0; i < 102; i++) { long.push(0); } long")).unwrap();
+  assert!(jsi_vm.inspect(&value).ends_with("0,\n  ... 2 more items\n]"));
+}
+
+#[test]
+fn inspect_console_log() {
+  let mut jsi_vm = JSI::new();
+  let output = Rc::new(RefCell::new(vec![]));
+  let sink_output = Rc::clone(&output);
+  jsi_vm.set_console_sink(move |_: ConsoleLevel, message: &str| {
+    sink_output.borrow_mut().push(message.to_string());
+  });
+  jsi_vm.run(String::from("console.log('value:', { a: [1, 'x'] }, [], 'raw'); console.log('%o and %s', 'quoted', 'plain');")).unwrap();
+  assert_eq!(*output.borrow(), vec![String::from("value: { a: [ 1, 'x' ] } [] raw"), String::from("'quoted' and plain")]);
+  assert_eq!(inspect(&Value::String(String::from("a\nb")), &InspectOptions::default()), "'a\\nb'");
+}
@@ -0,0 +1,327 @@
+use std::{thread, time::{Duration, Instant}};
+
+use jsi::{JSI, value::Value, error::JSIErrorType};
+
+// debug 构建的栈帧较大，在栈空间足够的线程中执行
+fn run_with_large_stack<F: FnOnce() + Send + 'static>(fun: F) {
+  thread::Builder::new().stack_size(64 * 1024 * 1024).spawn(fun).unwrap().join().unwrap();
+}
+
+fn assert_terminated(result: Result<Value, jsi::error::JSIError>, message: &str) {
+  match result {
+    Err(jsi_error) => {
+      assert_eq!(jsi_error.error_type, JSIErrorType::Terminated);
+      assert_eq!(jsi_error.message, String::from(message));
+    },
+    Ok(value) => assert!(false, "need terminated error, got {:?}", value),
+  }
+}
+
+#[test]
+fn limits_operation_budget() {
+  let mut jsi_vm = JSI::new();
+  jsi_vm.set_operation_budget(Some(10000));
+  assert_terminated(jsi_vm.run(String::from("while(true){}")), "Operation budget exceeded");
+  // 终止错误不能被 catch，也不能被 Promise 吞掉
+  assert_terminated(jsi_vm.run(String::from("\
+let caught = false;
+try {
+  for (;;) {}
+} catch (e) {
+  caught = true;
+}")), "Operation budget exceeded");
+  assert_terminated(jsi_vm.run(String::from("new Promise(function() { while(true){} }); 1")), "Operation budget exceeded");
+  // 每次执行重新计数
+  let result = jsi_vm.run(String::from("let sum = 0; for (let i = 0; i < 100; i++) { sum += i } sum")).unwrap();
+  assert_eq!(result, Value::Number(4950f64));
+
+  jsi_vm.run(String::from("function spin() { while(true){} }")).unwrap();
+  let spin = jsi_vm.get_global(String::from("spin"));
+  assert_terminated(jsi_vm.call(&spin, Value::Undefined, vec![]), "Operation budget exceeded");
+}
+
+#[test]
+fn limits_operation_budget_bytecode() {
+  let mut jsi_vm = JSI::new();
+  jsi_vm.set_operation_budget(Some(100));
+  let code = String::from("let a = 0;") + &"a = a + 1;".repeat(100);
+  assert_terminated(jsi_vm.run_with_bytecode(code), "Operation budget exceeded");
+  let result = jsi_vm.run_with_bytecode(String::from("let b = '1'; b + 2")).unwrap();
+  assert_eq!(result, Value::String(String::from("12")));
+}
+
+#[test]
+fn limits_deadline() {
+  let mut jsi_vm = JSI::new();
+  jsi_vm.set_deadline(Some(Instant::now() + Duration::from_millis(50)));
+  assert_terminated(jsi_vm.run(String::from("while(true){}")), "Execution deadline exceeded");
+  jsi_vm.set_deadline(None);
+  assert_eq!(jsi_vm.run(String::from("1 + 1")).unwrap(), Value::Number(2f64));
+}
+
+#[test]
+fn limits_interrupt() {
+  let mut jsi_vm = JSI::new();
+  let handle = jsi_vm.interrupt_handle();
+  let interrupt_thread = thread::spawn(move || {
+    thread::sleep(Duration::from_millis(50));
+    handle.interrupt();
+  });
+  assert_terminated(jsi_vm.run(String::from("let i = 0; while(true){ i++ }")), "Execution interrupted");
+  interrupt_thread.join().unwrap();
+  // 中断只生效一次
+  assert_eq!(jsi_vm.run(String::from("i > 0")).unwrap(), Value::Boolean(true));
+}
+
+#[test]
+fn limits_inside_builtin() {
+  // 单个耗时的内置方法调用中也会检查执行限制
+  let mut jsi_vm = JSI::new();
+  jsi_vm.set_operation_budget(Some(100));
+  assert_terminated(jsi_vm.run(String::from("'x'.repeat(100000000)")), "Operation budget exceeded");
+  assert_terminated(jsi_vm.run(String::from("'x'.padStart(100000000, 'ab')")), "Operation budget exceeded");
+  jsi_vm.set_operation_budget(None);
+
+  jsi_vm.set_deadline(Some(Instant::now() + Duration::from_millis(50)));
+  assert_terminated(jsi_vm.run(String::from("new Array(50000000).fill(0)")), "Execution deadline exceeded");
+  jsi_vm.set_deadline(None);
+
+  let handle = jsi_vm.interrupt_handle();
+  let interrupt_thread = thread::spawn(move || {
+    thread::sleep(Duration::from_millis(50));
+    handle.interrupt();
+  });
+  assert_terminated(jsi_vm.run(String::from("let list = new Array(50000000); list.fill('x')")), "Execution interrupted");
+  interrupt_thread.join().unwrap();
+  assert_eq!(jsi_vm.run(String::from("[3, 1, 2].sort().join('-')")).unwrap(), Value::String(String::from("1-2-3")));
+}
+
+#[test]
+fn limits_array_like_length() {
+  // 读取数组类对象的元素时检查执行限制和堆内存限制
+  let mut jsi_vm = JSI::new();
+  jsi_vm.set_operation_budget(Some(1000));
+  assert_terminated(jsi_vm.run(String::from("Array.from({ length: 3e6 })")), "Operation budget exceeded");
+  assert_terminated(jsi_vm.run(String::from("Array.prototype.toReversed.call({ length: 3e6 })")), "Operation budget exceeded");
+  let mut jsi_vm = JSI::new();
+  let base = jsi_vm.heap_stats().used;
+  jsi_vm.set_heap_limit(Some(base + 1024 * 1024));
+  assert_terminated(jsi_vm.run(String::from("Array.from({ length: 4e9 })")), "Out of memory");
+  assert!(jsi_vm.heap_stats().peak < base + 1024 * 1024);
+}
+
+#[test]
+fn limits_call_depth() {
+  run_with_large_stack(|| {
+    let mut jsi_vm = JSI::new();
+    jsi_vm.set_max_stack_size(32 * 1024 * 1024);
+    let result = jsi_vm.run(String::from("\
+function f() { f() }
+let message;
+try {
+  f();
+} catch (e) {
+  message = e.name + ': ' + e.message;
+}
+message")).unwrap();
+    assert_eq!(result, Value::String(String::from("RangeError: Maximum call stack size exceeded")));
+    // 没有 catch 时返回 RangeError，之后可以继续执行
+    let err = jsi_vm.run(String::from("f()")).unwrap_err();
+    assert_eq!(err.error_type, JSIErrorType::RangeError);
+    assert_eq!(jsi_vm.run(String::from("let n = 0; function g() { n++; if (n < 100) g() } g(); n")).unwrap(), Value::Number(100f64));
+
+    jsi_vm.set_max_call_depth(10);
+    let result = jsi_vm.run(String::from("let depth = 0; function h() { depth++; h() } try { h() } catch (e) {} depth")).unwrap();
+    assert_eq!(result, Value::Number(9f64));
+  });
+}
+
+#[test]
+fn limits_nesting_depth() {
+  run_with_large_stack(|| {
+    let mut jsi_vm = JSI::new();
+    jsi_vm.set_max_stack_size(32 * 1024 * 1024);
+    for code in [
+      format!("{}1{}", "(".repeat(1000), ")".repeat(1000)),
+      format!("{}1{}", "[".repeat(1000), "]".repeat(1000)),
+      format!("{}1{}", "{".repeat(1000), "}".repeat(1000)),
+      format!("{}1", "!".repeat(1000)),
+      // 左结合的运算符链和访问、调用链同样计入嵌套深度
+      format!("1{}", "+1".repeat(1000)),
+      format!("0{}", " || 0".repeat(1000)),
+      format!("Object{}", ".prototype".repeat(1000)),
+      format!("String{}", "()".repeat(1000)),
+      format!("2{}", " ** 1".repeat(1000)),
+    ] {
+      let err = jsi_vm.run(code).unwrap_err();
+      assert_eq!(err.error_type, JSIErrorType::RangeError);
+      assert_eq!(err.message, String::from("Maximum call stack size exceeded"));
+    }
+    let result = jsi_vm.run(format!("{}1{}", "(".repeat(100), ")".repeat(100))).unwrap();
+    assert_eq!(result, Value::Number(1f64));
+    let result = jsi_vm.run(format!("1{}", "+1".repeat(100))).unwrap();
+    assert_eq!(result, Value::Number(101f64));
+
+    jsi_vm.set_max_nesting_depth(10);
+    assert!(jsi_vm.run(String::from("((((((((((1))))))))))")).is_err());
+    assert_eq!(jsi_vm.run(String::from("((1))")).unwrap(), Value::Number(1f64));
+  });
+}
+
+#[test]
+fn limits_default_stack() {
+  // 使用默认配置，在普通的测试线程（2MB 栈）中执行，超出栈空间时抛出 RangeError 而不是进程崩溃
+  let mut jsi_vm = JSI::new();
+  let err = jsi_vm.run(String::from("function f(){ f() } f()")).unwrap_err();
+  assert_eq!(err.error_type, JSIErrorType::RangeError);
+  assert_eq!(err.message, String::from("Maximum call stack size exceeded"));
+  let result = jsi_vm.run(String::from("\
+let message;
+try {
+  f();
+} catch (e) {
+  message = e.name;
+}
+message")).unwrap();
+  assert_eq!(result, Value::String(String::from("RangeError")));
+  let err = jsi_vm.run_with_bytecode(String::from("function g(){ g() } g()")).unwrap_err();
+  assert_eq!(err.error_type, JSIErrorType::RangeError);
+  // 能否解析取决于构建方式的栈帧大小，但不能导致进程崩溃
+  for code in [
+    format!("{}1{}", "[".repeat(100), "]".repeat(100)),
+    format!("{}1{}", "(".repeat(100), ")".repeat(100)),
+    format!("{}1", "!".repeat(100)),
+    format!("{}1{}", "{".repeat(100), "}".repeat(100)),
+  ] {
+    if let Err(err) = jsi_vm.run(code) {
+      assert_eq!(err.error_type, JSIErrorType::RangeError);
+    }
+  }
+  for code in [
+    format!("1{}", "+1".repeat(20000)),
+    format!("0{}", " || 0".repeat(20000)),
+    format!("Object{}", ".prototype".repeat(20000)),
+  ] {
+    let err = jsi_vm.run(code).unwrap_err();
+    assert_eq!(err.error_type, JSIErrorType::RangeError);
+  }
+  assert_eq!(jsi_vm.run(String::from("1 + 1")).unwrap(), Value::Number(2f64));
+}
+
+#[test]
+fn limits_heap() {
+  let mut jsi_vm = JSI::new();
+  let base = jsi_vm.heap_stats().used;
+  assert!(base > 0);
+  jsi_vm.set_heap_limit(Some(base + 1024 * 1024));
+  assert_terminated(jsi_vm.run(String::from("let s = 'x'; while (true) { s = s + s }")), "Out of memory");
+  assert_terminated(jsi_vm.run(String::from("\
+let list = [];
+try {
+  while (true) { list.push({ value: 1 }) }
+} catch (e) {}")), "Out of memory");
+  let stats = jsi_vm.heap_stats();
+  assert!(stats.used > base + 1024 * 1024);
+  assert!(stats.peak >= stats.used);
+  // 释放引用后可以继续执行，变量 s 持有的字符串同样计入占用
+  jsi_vm.run(String::from("list = null; s = null")).unwrap();
+  let stats = jsi_vm.heap_stats();
+  assert!(stats.used < base + 1024);
+  assert!(stats.peak > base + 1024 * 1024);
+  assert_eq!(jsi_vm.run(String::from("[1, 2, 3].join('')")).unwrap(), Value::String(String::from("123")));
+}
+
+#[test]
+fn limits_heap_scope_released_after_termination() {
+  let mut jsi_vm = JSI::new();
+  let base = jsi_vm.heap_stats().used;
+  jsi_vm.set_heap_limit(Some(base + 1024 * 1024));
+  assert_terminated(jsi_vm.run(String::from("\
+let list = [];
+try {
+  while (true) { list.push({ value: 1 }) }
+} catch (e) {}")), "Out of memory");
+  // 终止后退出的作用域已经释放，list 仍然占用接近上限的内存
+  let stats = jsi_vm.heap_stats();
+  assert!(stats.peak > base + 1024 * 1024);
+  assert!(stats.used > base + 1000 * 1024);
+  jsi_vm.run(String::from("list = null")).unwrap();
+  // 函数内的局部变量在终止后随作用域一起释放
+  assert_terminated(jsi_vm.run(String::from("\
+function fill() {
+  let local = [];
+  while (true) { local.push({ value: 1 }) }
+}
+fill()")), "Out of memory");
+  assert!(jsi_vm.heap_stats().used < base + 1024);
+}
+
+#[test]
+fn limits_heap_strings() {
+  let mut jsi_vm = JSI::new();
+  let base = jsi_vm.heap_stats().used;
+  // 对象属性和变量持有的字符串计入占用，释放后归还
+  jsi_vm.run(String::from("\
+let list = [];
+for (let i = 0; i < 4; i++) { list.push('x'.repeat(1024 * 1024)) }
+let s = 'y'.repeat(1024 * 1024);")).unwrap();
+  assert!(jsi_vm.heap_stats().used > base + 5 * 1024 * 1024);
+  jsi_vm.run(String::from("list = null; s = 'y'")).unwrap();
+  assert!(jsi_vm.heap_stats().used < base + 1024);
+
+  jsi_vm.set_heap_limit(Some(base + 4 * 1024 * 1024));
+  assert_terminated(jsi_vm.run(String::from("\
+let kept = [];
+for (let i = 0; i < 40; i++) { kept.push('x'.repeat(1024 * 1024)) }")), "Out of memory");
+  assert!(jsi_vm.heap_stats().used < base + 4 * 1024 * 1024);
+  jsi_vm.run(String::from("kept = null")).unwrap();
+  // 生成字符串的内置方法在分配前检查
+  for code in [
+    "'x'.repeat(5e7)",
+    "'x'.padStart(5e7)",
+    "'x'.padEnd(5e7, 'ab')",
+    "let big = 'x'.repeat(1024 * 1024); [big, big, big].join('')",
+    "let part = 'x'.repeat(1024 * 1024); part.concat(part, part)",
+  ] {
+    assert_terminated(jsi_vm.run(String::from(code)), "Out of memory");
+  }
+}
+
+#[test]
+fn limits_heap_bulk_builtins() {
+  let mut jsi_vm = JSI::new();
+  let base = jsi_vm.heap_stats().used;
+  jsi_vm.set_heap_limit(Some(base + 200 * 1024));
+  // 批量创建元素的内置方法在分配前检查，不会先分配再报告超限
+  for code in [
+    "Array.from({ length: 100000 })",
+    "new Array(100000).fill(1)",
+    "new Array(3000).fill('x'.repeat(100))",
+    "new Array(1000).fill(1).join('x'.repeat(300))",
+  ] {
+    assert_terminated(jsi_vm.run(String::from(code)), "Out of memory");
+    assert!(jsi_vm.heap_stats().peak < base + 200 * 1024, "{}", code);
+  }
+  // 最后一个操作超出限制时同样终止
+  jsi_vm.set_heap_limit(Some(jsi_vm.heap_stats().used + 16));
+  assert_terminated(jsi_vm.run(String::from("({ a: 1 })")), "Out of memory");
+}
+
+#[test]
+fn limits_heap_delete() {
+  let mut jsi_vm = JSI::new();
+  let base = jsi_vm.heap_stats().used;
+  // 删除属性时归还占用，反复添加、删除不会累积
+  jsi_vm.run(String::from("let o = {}; for (let i = 0; i < 20000; i++) { o.k = i; delete o.k }")).unwrap();
+  assert!(jsi_vm.heap_stats().used < base + 1024);
+  jsi_vm.set_heap_limit(Some(base + 512 * 1024));
+  let result = jsi_vm.run(String::from("\
+let p = {};
+for (let i = 0; i < 20000; i++) {
+  p['k' + i] = 'value' + i;
+  delete p['k' + i];
+}
+Object.keys(p).length")).unwrap();
+  assert_eq!(result, Value::Number(0f64));
+  assert!(jsi_vm.heap_stats().used < base + 2048);
+}
@@ -0,0 +1,66 @@
+use jsi::{JSI, value::Value};
+
+#[test]
+fn run_map_same_value_zero_keys() {
+  let mut jsi = JSI::new();
+  let result = jsi.run(String::from("\
+    let m = new Map();
+    let o = {};
+    m.set(NaN, 'nan');
+    m.set(-0, 'zero');
+    m.set('1', 'string');
+    m.set(1, 'number');
+    m.set(o, 'object');
+    m.set(0, 'zero2');
+    [m.get(NaN), m.get(0), m.get(-0), m.get('1'), m.get(1), m.get(o), m.has({}), m.size, Object.is(Array.from(m.keys())[1], -0)].join()
+  ")).unwrap();
+  assert_eq!(result, Value::String(String::from("nan,zero2,zero2,string,number,object,false,5,false")));
+}
+
+#[test]
+fn run_map_insertion_order() {
+  let mut jsi = JSI::new();
+  let result = jsi.run(String::from("\
+    let m = new Map([['a', 1], ['b', 2], ['c', 3]]);
+    m.set('a', 10);
+    m.delete('b');
+    m.set('b', 20);
+    let out = [];
+    m.forEach(function(value, key) { out.push(key + '=' + value); });
+    for (let i = 0; i < 100; i++) { m.set(i, i); }
+    for (let i = 0; i < 100; i++) { m.delete(i); }
+    out.push(Array.from(m.keys()).join(''));
+    m.clear();
+    out.push(m.size);
+    out.join()
+  ")).unwrap();
+  assert_eq!(result, Value::String(String::from("a=10,c=3,b=20,acb,0")));
+}
+
+#[test]
+fn run_map_size_getter() {
+  let mut jsi = JSI::new();
+  let result = jsi.run(String::from("\
+    let m = new Map([[1, 'a']]);
+    let out = [];
+    try { m.size = 10; } catch (e) { out.push(e.name); }
+    try { Map.prototype.size; } catch (e) { out.push(e.name); }
+    out.push(m.size, m.hasOwnProperty('size'), Object.keys(m).length);
+    out.join()
+  ")).unwrap();
+  assert_eq!(result, Value::String(String::from("TypeError,TypeError,1,false,0")));
+}
+
+#[test]
+fn run_map_many_entries() {
+  let mut jsi = JSI::new();
+  let result = jsi.run(String::from("\
+    let m = new Map();
+    for (let i = 0; i < 20000; i++) { m.set('k' + i, i); }
+    for (let i = 0; i < 20000; i += 2) { m.delete('k' + i); }
+    let sum = 0;
+    for (let i = 0; i < 20000; i++) { if (m.has('k' + i)) { sum += m.get('k' + i); } }
+    [m.size, sum].join()
+  ")).unwrap();
+  assert_eq!(result, Value::String(String::from("10000,100000000")));
+}
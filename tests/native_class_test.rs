@@ -0,0 +1,103 @@
+use jsi::{JSI, value::Value, native_class::{NativeClass, with_host_data, create_instance}, convert::FromJs, error::{JSIError, JSIErrorType}};
+
+struct Counter {
+  count: i32,
+  step: i32,
+}
+
+fn counter_class() -> NativeClass<Counter> {
+  NativeClass::new("Counter")
+    .constructor(1, |call_ctx, args| {
+      let step = Option::<i32>::from_js(call_ctx.ctx, args.first().unwrap_or(&Value::Undefined))?;
+      Ok(Counter { count: 0, step: step.unwrap_or(1) })
+    })
+    .method("increment", 0, |counter, _, _| {
+      counter.count += counter.step;
+      Ok(Value::Number(counter.count as f64))
+    })
+    .method("fork", 0, |counter, call_ctx, _| {
+      let class = call_ctx.ctx.get_global_value(String::from("Counter"));
+      create_instance(call_ctx.ctx, &class, Counter { count: counter.count, step: counter.step * 2 })
+    })
+    .getter("count", |counter| counter.count)
+    .getter("step", |counter| counter.step)
+    .setter("step", |counter, step: i32| {
+      if step == 0 {
+        return Err(JSIError::new(JSIErrorType::RangeError, String::from("step must not be 0"), 0, 0));
+      }
+      counter.step = step;
+      Ok(())
+    })
+}
+
+#[test]
+fn native_class_methods_and_accessors() {
+  let mut jsi_vm = JSI::new();
+  jsi_vm.register_class(counter_class());
+  let result = jsi_vm.run(String::from("\
+let counter = new Counter(2);
+counter.increment();
+counter.increment();
+counter.step = 5;
+counter.increment();
+[counter.count, counter.step, counter instanceof Counter, typeof counter.increment, Object.keys(counter).length, Counter.name, Counter.length].join(',')")).unwrap();
+  assert_eq!(result, Value::String(String::from("9,5,true,function,0,Counter,1")));
+
+  let counter = jsi_vm.get_global(String::from("counter"));
+  let count = with_host_data(&counter, |counter: &mut Counter| counter.count).unwrap();
+  assert_eq!(count, 9);
+  assert!(with_host_data(&counter, |value: &mut String| value.len()).is_err());
+}
+
+#[test]
+fn native_class_create_instance() {
+  let mut jsi_vm = JSI::new();
+  let class = jsi_vm.register_class(counter_class());
+  let counter = jsi_vm.create_instance(&class, Counter { count: 10, step: 3 }).unwrap();
+  jsi_vm.set_global(String::from("fromRust"), counter);
+  let result = jsi_vm.run(String::from("\
+fromRust.increment();
+let forked = fromRust.fork();
+forked.increment();
+[fromRust.count, forked.count, forked.step, forked instanceof Counter].join(',')")).unwrap();
+  assert_eq!(result, Value::String(String::from("13,19,6,true")));
+}
+
+#[test]
+fn native_class_errors() {
+  let mut jsi_vm = JSI::new();
+  jsi_vm.register_class(counter_class());
+  jsi_vm.register_class(NativeClass::<i32>::new("Opaque").getter("value", |value| *value));
+  let result = jsi_vm.run(String::from("\
+let messages = [];
+try { Counter(); } catch (e) { messages.push(e.message); }
+try { new Counter('a'); } catch (e) { messages.push(e.message); }
+try { new Counter().step = 0; } catch (e) { messages.push(e.name + ':' + e.message); }
+try { new Counter().count = 1; } catch (e) { messages.push(e.message); }
+try { Counter.prototype.increment.call({}); } catch (e) { messages.push(e.message); }
+try { new Opaque(); } catch (e) { messages.push(e.message); }
+messages.join('|')")).unwrap();
+  assert_eq!(result, Value::String(String::from("\
+Class constructor Counter cannot be invoked without 'new'|\
+Cannot convert string to number|\
+RangeError:step must not be 0|\
+Cannot set property count which has only a getter|\
+Method Counter.prototype.increment called on incompatible receiver|\
+Opaque is not a constructor")));
+}
+
+#[test]
+fn native_class_accessors_in_bytecode_and_builtins() {
+  let mut jsi_vm = JSI::new();
+  jsi_vm.register_class(counter_class());
+  let result = jsi_vm.run_with_bytecode(String::from("let counter = new Counter(5); counter.step")).unwrap();
+  assert_eq!(result, Value::Number(5f64));
+  let result = jsi_vm.run(String::from("\
+let other = new Counter(2);
+Object.assign(other, { step: 3 });
+other.increment();
+[other.count, Object.keys(other).length, 'step' in other, 'count' in other, 'missing' in other].join(',')")).unwrap();
+  assert_eq!(result, Value::String(String::from("3,0,true,true,false")));
+  let err = jsi_vm.run(String::from("Object.assign(new Counter(), { count: 1 })")).unwrap_err();
+  assert_eq!(err.error_type, JSIErrorType::TypeError);
+}
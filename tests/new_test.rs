@@ -1,11 +1,14 @@
 use jsi::{
     ast_node::{
         Expression, ExpressionStatement, IdentifierLiteral, NewExpression, NumberLiteral,
-        PropertyAccessExpression, Statement,
+        PropertyAccessExpression, Span, Statement,
     },
     JSI,
 };
 
+mod common;
+use common::without_spans;
+
 #[test]
 fn run_new_ast() {
     let mut jsi_vm = JSI::new();
@@ -19,22 +22,28 @@ fn run_new_ast() {
     println!("program {:?}", program);
 
     assert_eq!(
-        program.body,
+        without_spans(program.body),
         vec![Statement::Expression(ExpressionStatement {
             expression: Expression::PropertyAccess(PropertyAccessExpression {
                 expression: Box::new(Expression::New(NewExpression {
                     expression: Box::new(Expression::Identifier(IdentifierLiteral {
                         literal: String::from("a"),
+                        span: Span::default(),
                     })),
                     arguments: vec![Expression::Number(NumberLiteral {
                         literal: String::from("123"),
-                        value: 123.0
+                        value: 123.0,
+                        span: Span::default(),
                     })],
+                    span: Span::default(),
                 })),
                 name: IdentifierLiteral {
                     literal: String::from("b"),
+                    span: Span::default(),
                 },
-            })
+                span: Span::default(),
+            }),
+            span: Span::default(),
         })]
     );
 }
@@ -0,0 +1,70 @@
+use jsi::{JSI, value::Value};
+
+#[test]
+fn run_number_to_string() {
+  let mut jsi = JSI::new();
+  let result = jsi.run(String::from("\
+    [0.1 + 0.2, 1e21, 123e-20, 1e-7, 0.000001, -1.5e300, 100, -0].join(' ')
+  ")).unwrap();
+  assert_eq!(result, Value::String(String::from("0.30000000000000004 1e+21 1.23e-18 1e-7 0.000001 -1.5e+300 100 0")));
+}
+
+#[test]
+fn run_number_to_string_radix() {
+  let mut jsi = JSI::new();
+  let result = jsi.run(String::from("\
+    [(255).toString(16), (0.5).toString(2), (-255.5).toString(16), (8).toString(8)].join(' ')
+  ")).unwrap();
+  assert_eq!(result, Value::String(String::from("ff 0.1 -ff.8 10")));
+}
+
+#[test]
+fn run_number_to_fixed() {
+  let mut jsi = JSI::new();
+  let result = jsi.run(String::from("\
+    [(1.005).toFixed(2), (0.5).toFixed(0), (2.5).toFixed(0), (1.45).toFixed(1), (-1.5).toFixed(0), (1e21).toFixed(2), (123.456).toFixed(), (-0.0001).toFixed(2)].join(' ')
+  ")).unwrap();
+  assert_eq!(result, Value::String(String::from("1.00 1 3 1.4 -2 1e+21 123 -0.00")));
+}
+
+#[test]
+fn run_number_to_exponential() {
+  let mut jsi = JSI::new();
+  let result = jsi.run(String::from("\
+    [(123456).toExponential(2), (0).toExponential(), (1.5).toExponential(), (-12345).toExponential()].join(' ')
+  ")).unwrap();
+  assert_eq!(result, Value::String(String::from("1.23e+5 0e+0 1.5e+0 -1.2345e+4")));
+}
+
+#[test]
+fn run_number_to_precision() {
+  let mut jsi = JSI::new();
+  let result = jsi.run(String::from("\
+    [(123.456).toPrecision(4), (0.000123).toPrecision(2), (123456).toPrecision(2), (1.25).toPrecision(2), (0).toPrecision(3), (1e-7).toPrecision(1), (99.99).toPrecision(3)].join(' ')
+  ")).unwrap();
+  assert_eq!(result, Value::String(String::from("123.5 0.00012 1.2e+5 1.3 0.00 1e-7 100")));
+}
+
+#[test]
+fn run_number_format_range_error() {
+  let mut jsi = JSI::new();
+  let result = jsi.run(String::from("\
+    let msg = '';
+    try {
+      (1).toFixed(101);
+    } catch (e) {
+      msg = e.message;
+    }
+    msg
+  ")).unwrap();
+  assert_eq!(result, Value::String(String::from("toFixed() argument must be between 0 and 100")));
+}
+
+#[test]
+fn run_number_statics() {
+  let mut jsi = JSI::new();
+  let result = jsi.run(String::from("\
+    [Number.isInteger(5), Number.isInteger(5.5), Number.isSafeInteger(9007199254740992), Number.isFinite('5'), Number.isNaN(NaN), Number.isNaN('x'), Number.parseInt === parseInt, Number.parseFloat('1.5'), Number.EPSILON > 0, Number.MAX_SAFE_INTEGER].join(' ')
+  ")).unwrap();
+  assert_eq!(result, Value::String(String::from("true false false false true false true 1.5 true 9007199254740991")));
+}
@@ -1,4 +1,7 @@
-use jsi::{JSI, ast_node::{Expression, Statement, ObjectLiteral, PropertyAssignment, NumberLiteral, StringLiteral, Keywords, BinaryExpression, ComputedPropertyName}, ast_token::Token, value::Value};
+use jsi::{JSI, ast_node::{Expression, Statement, ObjectLiteral, PropertyAssignment, NumberLiteral, StringLiteral, Keywords, BinaryExpression, ComputedPropertyName, Span}, ast_token::Token, value::Value};
+
+mod common;
+use common::expression_without_spans;
 
 #[test]
 fn ast_base() {
@@ -14,27 +17,33 @@ fn ast_base() {
     },
     _ => Expression::Unknown,
   };
-  assert_eq!(expr, Expression::Object(ObjectLiteral {
+  assert_eq!(expression_without_spans(expr), Expression::Object(ObjectLiteral {
       properties: vec![
         PropertyAssignment{
-          name: Box::new(Expression::String(StringLiteral { literal: String::from("a"), value: String::from("a")})),
-          initializer: Box::new(Expression::Number(NumberLiteral { literal: String::from("123"), value: 123f64 })),
+          name: Box::new(Expression::String(StringLiteral { literal: String::from("a"), value: String::from("a"), span: Span::default() })),
+          initializer: Box::new(Expression::Number(NumberLiteral { literal: String::from("123"), value: 123f64, span: Span::default() })),
+          span: Span::default(),
         },
         PropertyAssignment{
-          name: Box::new(Expression::String(StringLiteral { literal: String::from("b"), value: String::from("b")})),
-          initializer: Box::new(Expression::String(StringLiteral { literal: String::from("'123'"), value: String::from("123") })),
+          name: Box::new(Expression::String(StringLiteral { literal: String::from("b"), value: String::from("b"), span: Span::default() })),
+          initializer: Box::new(Expression::String(StringLiteral { literal: String::from("'123'"), value: String::from("123"), span: Span::default() })),
+          span: Span::default(),
         },
         PropertyAssignment{
           name: Box::new(Expression::ComputedPropertyName(ComputedPropertyName {
             expression: Box::new(Expression::Binary(BinaryExpression {
-              left: Box::new(Expression::Number(NumberLiteral { literal: String::from("1"), value: 1f64 })),
+              left: Box::new(Expression::Number(NumberLiteral { literal: String::from("1"), value: 1f64, span: Span::default() })),
               operator: Token::Plus,
-              right: Box::new(Expression::String(StringLiteral { literal: String::from("'a'"), value: String::from("a") })),
-            }))
+              right: Box::new(Expression::String(StringLiteral { literal: String::from("'a'"), value: String::from("a"), span: Span::default() })),
+              span: Span::default(),
+            })),
+            span: Span::default(),
           })),
-          initializer: Box::new(Expression::Keyword(Keywords::False)),
+          initializer: Box::new(Expression::Keyword(Keywords::False, Span::default())),
+          span: Span::default(),
         }
-      ]
+      ],
+      span: Span::default(),
   }));
 }
 
@@ -52,20 +61,24 @@ fn ast_with_child_object() {
     },
     _ => Expression::Unknown,
   };
-  assert_eq!(expr, Expression::Object(ObjectLiteral {
+  assert_eq!(expression_without_spans(expr), Expression::Object(ObjectLiteral {
       properties: vec![
         PropertyAssignment{
-          name: Box::new(Expression::String(StringLiteral { literal: String::from("obj"), value: String::from("obj")})),
+          name: Box::new(Expression::String(StringLiteral { literal: String::from("obj"), value: String::from("obj"), span: Span::default() })),
           initializer: Box::new(Expression::Object(ObjectLiteral {
             properties: vec![
               PropertyAssignment{
-                name: Box::new(Expression::String(StringLiteral { literal: String::from("x"), value: String::from("x")})),
-                initializer: Box::new(Expression::Keyword(Keywords::False)),
+                name: Box::new(Expression::String(StringLiteral { literal: String::from("x"), value: String::from("x"), span: Span::default() })),
+                initializer: Box::new(Expression::Keyword(Keywords::False, Span::default())),
+                span: Span::default(),
               },
-            ]
+            ],
+            span: Span::default(),
           })),
+          span: Span::default(),
         },
-      ]
+      ],
+      span: Span::default(),
     }));
 }
 
@@ -281,3 +294,137 @@ fn run_object_delete_after_delete() {
   // 重复删除同一个属性，对象应该只有 b 属性
   assert_eq!(result, Value::Number(1f64));
 }
+
+#[test]
+fn run_object_property_order() {
+  let mut jsi = JSI::new();
+  let result = jsi.run(String::from("\
+    let obj = { b: 1, 2: 'x', a: 2, 1: 'y' };
+    let keys = [];
+    for (let key in obj) {
+      keys.push(key);
+    }
+    [Object.keys(obj).join(), Object.getOwnPropertyNames(obj).join(), keys.join()].join('|')
+  ")).unwrap();
+  assert_eq!(result, Value::String(String::from("1,2,b,a|1,2,b,a|1,2,b,a")));
+}
+
+#[test]
+fn run_object_entries_values() {
+  let mut jsi = JSI::new();
+  let result = jsi.run(String::from("\
+    let obj = { a: 1, b: 'x' };
+    let entries = Object.entries(obj).map(entry => entry[0] + '=' + entry[1]).join(';');
+    [entries, Object.values(obj).join(), Object.values('ab').join()].join('|')
+  ")).unwrap();
+  assert_eq!(result, Value::String(String::from("a=1;b=x|1,x|a,b")));
+}
+
+#[test]
+fn run_object_keys_null_throws() {
+  let mut jsi = JSI::new();
+  let result = jsi.run(String::from("\
+    let message = '';
+    try {
+      Object.entries(null);
+    } catch (e) {
+      message = e.message;
+    }
+    message
+  ")).unwrap();
+  assert_eq!(result, Value::String(String::from("Cannot convert undefined or null to object")));
+}
+
+#[test]
+fn run_object_assign() {
+  let mut jsi = JSI::new();
+  let result = jsi.run(String::from("\
+    let target = { a: 0 };
+    let result = Object.assign(target, { b: 1 }, null, undefined, { a: 2 });
+    [result === target, Object.keys(target).join(), target.a, target.b].join()
+  ")).unwrap();
+  assert_eq!(result, Value::String(String::from("true,a,b,2,1")));
+}
+
+#[test]
+fn run_object_from_entries() {
+  let mut jsi = JSI::new();
+  let result = jsi.run(String::from("\
+    let fromArray = Object.fromEntries([['a', 1], ['b', 2]]);
+    let fromMap = Object.fromEntries(new Map([['c', 3]]));
+    let fromIterator = Object.fromEntries(['x', 'y'].entries());
+    [fromArray.a + fromArray.b, fromMap.c, fromIterator[1]].join()
+  ")).unwrap();
+  assert_eq!(result, Value::String(String::from("3,3,y")));
+}
+
+#[test]
+fn run_object_set_prototype_of() {
+  let mut jsi = JSI::new();
+  let result = jsi.run(String::from("\
+    let proto = { greet: function() { return 'hi ' + this.name; } };
+    let obj = { name: 'js' };
+    let message = '';
+    Object.setPrototypeOf(obj, proto);
+    try {
+      Object.setPrototypeOf(proto, obj);
+    } catch (e) {
+      message = e.message;
+    }
+    [obj.greet(), proto.isPrototypeOf(obj), obj.isPrototypeOf(proto), message].join()
+  ")).unwrap();
+  assert_eq!(result, Value::String(String::from("hi js,true,false,Cyclic __proto__ value")));
+}
+
+#[test]
+fn run_object_is() {
+  let mut jsi = JSI::new();
+  let result = jsi.run(String::from("\
+    let obj = {};
+    [Object.is(NaN, NaN), Object.is(0, -0), Object.is(obj, obj), Object.is({}, {}), Object.is('a', 'a'), Object.is(null, undefined)].join()
+  ")).unwrap();
+  assert_eq!(result, Value::String(String::from("true,false,true,false,true,false")));
+}
+
+#[test]
+fn run_object_group_by() {
+  let mut jsi = JSI::new();
+  let result = jsi.run(String::from("\
+    let groups = Object.groupBy([1, 2, 3, 4, 5], num => num % 2 ? 'odd' : 'even');
+    let map = Map.groupBy([1, 2, 3, 4, 5], num => num % 2);
+    [Object.keys(groups).join(), groups.odd.join(), groups.even.join(), Object.getPrototypeOf(groups) === null, map.size, map.get(1).join()].join('|')
+  ")).unwrap();
+  assert_eq!(result, Value::String(String::from("odd,even|1,3,5|2,4|true|2|1,3,5")));
+}
+
+#[test]
+fn run_object_prototype_methods() {
+  let mut jsi = JSI::new();
+  let result = jsi.run(String::from("\
+    let obj = { a: 1 };
+    [obj.propertyIsEnumerable('a'), obj.propertyIsEnumerable('b'), [1].propertyIsEnumerable('length'), obj.toLocaleString()].join()
+  ")).unwrap();
+  assert_eq!(result, Value::String(String::from("true,false,false,[object Object]")));
+}
+
+#[test]
+fn run_object_id_per_context() {
+  let object_id = |value: &Value| match value {
+    Value::Object(object) | Value::Array(object) => object.borrow().get_id(),
+    _ => 0,
+  };
+  let code = "let list = [{ a: 1 }, { b: 2 }]; list";
+  let mut jsi_a = JSI::new();
+  let list_a = jsi_a.run(String::from(code)).unwrap();
+  // 其他解释器创建对象不影响当前解释器的 id
+  let mut jsi_other = JSI::new();
+  jsi_other.run(String::from("for (let i = 0; i < 10; i++) { let o = {}; }")).unwrap();
+  let mut jsi_b = JSI::new();
+  let list_b = jsi_b.run(String::from(code)).unwrap();
+  assert!(object_id(&list_a) > 0);
+  assert_eq!(object_id(&list_a), object_id(&list_b));
+  let first_a = jsi_a.run(String::from("list[0]")).unwrap();
+  let first_b = jsi_b.run(String::from("list[0]")).unwrap();
+  assert_eq!(object_id(&first_a), object_id(&first_b));
+  assert_ne!(object_id(&first_a), object_id(&list_a));
+}
@@ -23,4 +23,26 @@ fn run_fibonacci_performance() {
   println!("Fibonacci performance test completed in: {:?}", duration);
 
   assert_eq!(result, Value::String(String::from("run_00,run_11,run_21,run_32,run_43,run_55,run_68,run_713,run_821,run_934,run_1055,run_1189,run_12144,run_13233,run_14377")));
-}
\ No newline at end of file
+}
+
+#[test]
+fn run_string_index_performance() {
+  let mut jsi = JSI::new();
+  let start_time = std::time::Instant::now();
+  let result = jsi.run(String::from("\
+    let s = 'abcdefghij'.repeat(1000);
+    let u = '😀x'.repeat(1000);
+    let count = 0;
+    for (let i = 0; i < s.length; i++) {
+      if (s[i] === 'a') count++;
+    }
+    for (let i = 0; i < u.length; i++) {
+      if (u[i] === 'x') count++;
+    }
+    [count, s.length, u.length, u[0] + u[1] === '😀', u[3].charCodeAt(0)].join()
+  ")).unwrap();
+  let duration = start_time.elapsed();
+  println!("String index performance test completed in: {:?}", duration);
+
+  assert_eq!(result, Value::String(String::from("2000,10000,3000,true,55357")));
+}
@@ -0,0 +1,55 @@
+use jsi::{JSI, value::Value};
+
+#[test]
+fn realm_separate_globals() {
+  let mut jsi_vm = JSI::new();
+  let main = jsi_vm.current_realm();
+  let plugin = jsi_vm.create_realm();
+  assert_ne!(main, plugin);
+  assert_eq!(jsi_vm.current_realm(), main);
+  jsi_vm.run(String::from("let name = 'main'; Array.prototype.patched = true;")).unwrap();
+  let result = jsi_vm.run_in_realm(plugin, String::from("[typeof name, typeof [].patched, Array.prototype === globalThis.Array.prototype].join(',')")).unwrap();
+  assert_eq!(result, Value::String(String::from("undefined,undefined,true")));
+  jsi_vm.run_in_realm(plugin, String::from("let name = 'plugin';")).unwrap();
+  assert_eq!(jsi_vm.run(String::from("name")).unwrap(), Value::String(String::from("main")));
+
+  let previous = jsi_vm.enter_realm(plugin);
+  assert_eq!(previous, main);
+  assert_eq!(jsi_vm.get_global(String::from("name")), Value::String(String::from("plugin")));
+  jsi_vm.enter_realm(main);
+  assert_eq!(jsi_vm.get_global(String::from("name")), Value::String(String::from("main")));
+}
+
+#[test]
+fn realm_cross_realm_objects() {
+  let mut jsi_vm = JSI::new();
+  let plugin = jsi_vm.create_realm();
+  let list = jsi_vm.run_in_realm(plugin, String::from("\
+let count = 0;
+function makeList() { count++; return [1, 2]; }
+[3, 4]")).unwrap();
+  let make_list = jsi_vm.run_in_realm(plugin, String::from("makeList")).unwrap();
+  jsi_vm.set_global(String::from("foreignList"), list);
+  jsi_vm.set_global(String::from("makeList"), make_list);
+  // 和浏览器一样，其他领域的数组不是当前领域 Array 的实例，但仍然是数组
+  let result = jsi_vm.run(String::from("\
+let created = makeList();
+[
+  foreignList instanceof Array,
+  Array.isArray(foreignList),
+  foreignList instanceof Object,
+  created instanceof Array,
+  [] instanceof Array,
+  foreignList.map(function(item) { return item * 2; }).join('-'),
+].join(',')")).unwrap();
+  assert_eq!(result, Value::String(String::from("false,true,false,false,true,6-8")));
+  // 函数在定义时的领域中执行
+  let result = jsi_vm.run_in_realm(plugin, String::from("[count, typeof foreignList].join(',')")).unwrap();
+  assert_eq!(result, Value::String(String::from("1,undefined")));
+  // 将当前领域的对象传给其他领域
+  let main_object = jsi_vm.run(String::from("({ value: [1] })")).unwrap();
+  jsi_vm.enter_realm(plugin);
+  jsi_vm.set_global(String::from("hostObject"), main_object);
+  let result = jsi_vm.run(String::from("[hostObject.value instanceof Array, hostObject.value.concat([2]).length].join(',')")).unwrap();
+  assert_eq!(result, Value::String(String::from("false,2")));
+}
@@ -0,0 +1,57 @@
+use jsi::{JSI, ast_node::{Statement, Expression}, error::JSIErrorType};
+
+#[test]
+fn recovery_reports_all_syntax_errors() {
+  let mut jsi_vm = JSI::new();
+  let (program, errors) = jsi_vm.parse_with_recovery(String::from("\
+let a = ;
+let b = 2;
+foo(1, ;
+if (a { b }
+let c = 3;"));
+  let positions: Vec<(i32, i32)> = errors.iter().map(|error| (error.line, error.column)).collect();
+  assert_eq!(positions, vec![(1, 9), (3, 8), (4, 7)]);
+  assert!(errors.iter().all(|error| error.error_type == JSIErrorType::SyntaxError));
+  assert_eq!(errors[2].expected, Some(String::from("')'")));
+  // 出错的语句被跳过，其他语句正常解析
+  let names: Vec<String> = program.body.iter().filter_map(|statement| match statement {
+    Statement::Var(var) => match &var.list[0] {
+      Expression::Var(declaration) => Some(declaration.name.clone()),
+      _ => None,
+    },
+    _ => None,
+  }).collect();
+  assert_eq!(names, vec![String::from("b"), String::from("c")]);
+}
+
+#[test]
+fn recovery_inside_function_body() {
+  let mut jsi_vm = JSI::new();
+  let (program, errors) = jsi_vm.parse_with_recovery(String::from("\
+function add(x, y) {
+  let z = (x +;
+  return x + y;
+}
+add(1, 2);"));
+  assert_eq!(errors.len(), 1);
+  assert_eq!((errors[0].line, errors[0].column), (2, 15));
+  assert_eq!(program.body.len(), 2);
+  if let Statement::Function(func) = &program.body[0] {
+    assert_eq!(func.body.statements.len(), 1);
+  } else {
+    assert!(false, "need function declaration");
+  }
+}
+
+#[test]
+fn recovery_stray_brace_and_valid_code() {
+  let mut jsi_vm = JSI::new();
+  let (program, errors) = jsi_vm.parse_with_recovery(String::from("let a = 1;\n}\nlet b = 2;"));
+  assert_eq!(errors.len(), 1);
+  assert_eq!(errors[0].message, String::from("Unexpected token '}'"));
+  assert_eq!(program.body.len(), 2);
+
+  let (program, errors) = jsi_vm.parse_with_recovery(String::from("let a = 1;\na + 1;"));
+  assert!(errors.is_empty());
+  assert_eq!(program.body.len(), 2);
+}
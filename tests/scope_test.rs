@@ -0,0 +1,51 @@
+use jsi::{JSI, value::Value};
+
+#[test]
+fn scope_loop_constant_memory() {
+  let mut jsi_vm = JSI::new();
+  jsi_vm.run(String::from("let sum = 0;")).unwrap();
+  let base = jsi_vm.heap_stats();
+  let result = jsi_vm.run(String::from("\
+for (let i = 0; i < 1000000; i++) {
+  {
+    let double = i * 2;
+    sum += double;
+  }
+}
+sum")).unwrap();
+  assert_eq!(result, Value::Number(999999000000f64));
+  let stats = jsi_vm.heap_stats();
+  // 每次循环创建的作用域在退出时释放
+  assert!(stats.used - base.used < 1024, "used {} base {}", stats.used, base.used);
+  assert!(stats.peak - base.peak < 4096, "peak {} base {}", stats.peak, base.peak);
+}
+
+#[test]
+fn scope_restore_after_error() {
+  let mut jsi_vm = JSI::new();
+  let base = jsi_vm.heap_stats().used;
+  let result = jsi_vm.run(String::from("\
+function thrower(value) {
+  {
+    let inner = value;
+    throw inner;
+  }
+}
+let caught = 0;
+for (let i = 0; i < 1000; i++) {
+  try {
+    thrower(i);
+  } catch (e) {
+    caught += 1;
+  }
+}
+function makeGetter(value) {
+  return function() { return value; };
+}
+let getter = makeGetter(caught);
+var after = getter();
+[caught, after, typeof after].join(',')")).unwrap();
+  assert_eq!(result, Value::String(String::from("1000,1000,number")));
+  // 只保留函数和被闭包捕获的作用域
+  assert!(jsi_vm.heap_stats().used - base < 8192);
+}
@@ -0,0 +1,96 @@
+#![cfg(feature = "serde")]
+use std::collections::HashMap;
+
+use jsi::{JSI, value::Value, error::JSIErrorType};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Serialize, Deserialize, PartialEq)]
+enum Method {
+  Get,
+  Post(String),
+  Custom { name: String },
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq)]
+struct Request {
+  id: u32,
+  path: String,
+  method: Method,
+  tags: Vec<String>,
+  headers: HashMap<String, String>,
+  timeout: Option<f64>,
+  retry: (u8, bool),
+}
+
+#[derive(Debug, Deserialize, PartialEq)]
+struct Response {
+  status: u16,
+  body: String,
+  #[serde(default)]
+  cached: bool,
+}
+
+#[test]
+fn serde_serialize_to_js() {
+  let mut jsi_vm = JSI::new();
+  let mut headers = HashMap::new();
+  headers.insert(String::from("accept"), String::from("text/html"));
+  let request = Request {
+    id: 7,
+    path: String::from("/index"),
+    method: Method::Post(String::from("form")),
+    tags: vec![String::from("a"), String::from("b")],
+    headers,
+    timeout: None,
+    retry: (3, true),
+  };
+  let value = jsi_vm.serialize_to_js(&request).unwrap();
+  jsi_vm.set_global(String::from("request"), value);
+  let result = jsi_vm.run(String::from("\
+[request.id, request.path, request.method.Post, request.tags.join('|'), request.headers.accept, request.timeout === null, request.retry[0], Object.keys(request).join(',')].join(';')")).unwrap();
+  assert_eq!(result, Value::String(String::from("7;/index;form;a|b;text/html;true;3;id,path,method,tags,headers,timeout,retry")));
+
+  let value = jsi_vm.serialize_to_js(&Method::Get).unwrap();
+  assert_eq!(value, Value::String(String::from("Get")));
+}
+
+#[test]
+fn serde_deserialize_from_js() {
+  let mut jsi_vm = JSI::new();
+  let value = jsi_vm.run(String::from("({ status: 200, body: 'ok', extra: function() {} })")).unwrap();
+  let response: Response = jsi_vm.deserialize_from_js(&value).unwrap();
+  assert_eq!(response, Response { status: 200, body: String::from("ok"), cached: false });
+
+  let value = jsi_vm.run(String::from("\
+({
+  id: 1,
+  path: new String('/a'),
+  method: { Custom: { name: 'PURGE' } },
+  tags: [],
+  headers: { host: 'x' },
+  retry: [0, false],
+})")).unwrap();
+  let request: Request = jsi_vm.deserialize_from_js(&value).unwrap();
+  assert_eq!(request.path, String::from("/a"));
+  assert_eq!(request.method, Method::Custom { name: String::from("PURGE") });
+  assert_eq!(request.headers.get("host"), Some(&String::from("x")));
+  assert_eq!(request.timeout, None);
+  assert_eq!(request.retry, (0, false));
+}
+
+#[test]
+fn serde_round_trip_and_errors() {
+  let mut jsi_vm = JSI::new();
+  let value = jsi_vm.run(String::from("({ status: 'bad', body: 'ok' })")).unwrap();
+  let result: Result<Response, _> = jsi_vm.deserialize_from_js(&value);
+  assert_eq!(result.unwrap_err().error_type, JSIErrorType::TypeError);
+
+  let value = jsi_vm.run(String::from("let a = { status: 1 }; a.self = a; a")).unwrap();
+  let result: Result<HashMap<String, serde_json::Value>, _> = jsi_vm.deserialize_from_js(&value);
+  assert!(result.is_err());
+
+  let list = vec![(1, String::from("x")), (2, String::from("y"))];
+  let value = jsi_vm.serialize_to_js(&list).unwrap();
+  let back: Vec<(i32, String)> = jsi_vm.deserialize_from_js(&value).unwrap();
+  assert_eq!(back, list);
+}
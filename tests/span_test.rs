@@ -0,0 +1,93 @@
+use jsi::{JSI, ast_node::{Statement, Expression, Span}};
+
+fn span(start: usize, end: usize, line: usize, column: usize) -> Span {
+  Span { start, end, line, column }
+}
+
+#[test]
+fn span_statement_and_expression() {
+  let mut jsi_vm = JSI::new();
+  let program = jsi_vm.parse(String::from("let a = 1;\nfoo(a + 2, 'b');")).unwrap();
+  assert_eq!(program.body[0].span(), span(0, 9, 1, 1));
+  assert_eq!(program.body[1].span(), span(11, 26, 2, 1));
+  if let Statement::Expression(statement) = &program.body[1] {
+    if let Expression::Call(call) = &statement.expression {
+      assert_eq!(call.expression.span(), span(11, 14, 2, 1));
+      assert_eq!(call.arguments[0].span(), span(15, 20, 2, 5));
+      assert_eq!(call.arguments[1].span(), span(22, 25, 2, 12));
+      if let Expression::Binary(binary) = &call.arguments[0] {
+        assert_eq!(binary.right.span(), span(19, 20, 2, 9));
+      } else {
+        assert!(false, "need binary expression");
+      }
+      return;
+    }
+  }
+  assert!(false, "need call expression");
+}
+
+#[test]
+fn span_nested_function() {
+  let mut jsi_vm = JSI::new();
+  let program = jsi_vm.parse(String::from("function add(x, y) {\n  return x.value * y;\n}")).unwrap();
+  if let Statement::Function(func) = &program.body[0] {
+    assert_eq!(func.span, span(0, 44, 1, 1));
+    assert_eq!(func.name.span, span(9, 12, 1, 10));
+    assert_eq!(func.parameters[1].span, span(16, 17, 1, 17));
+    let statement = &func.body.statements[0];
+    assert_eq!(statement.span(), span(23, 41, 2, 3));
+    if let Statement::Return(return_statement) = statement {
+      assert_eq!(return_statement.expression.span(), span(30, 41, 2, 10));
+      if let Expression::Binary(binary) = &return_statement.expression {
+        assert_eq!(binary.left.span(), span(30, 37, 2, 10));
+        return;
+      }
+    }
+  }
+  assert!(false, "need function declaration");
+}
+
+#[test]
+fn span_keyword_and_value() {
+  let mut jsi_vm = JSI::new();
+  let program = jsi_vm.parse(String::from("f(true, null, '\\uD83D')")).unwrap();
+  if let Statement::Expression(statement) = &program.body[0] {
+    if let Expression::Call(call) = &statement.expression {
+      assert_eq!(call.arguments[0].span(), span(2, 6, 1, 3));
+      assert_eq!(call.arguments[1].span(), span(8, 12, 1, 9));
+      assert_eq!(call.arguments[2].span(), span(14, 22, 1, 15));
+      return;
+    }
+  }
+  assert!(false, "need call expression");
+}
+
+#[test]
+fn span_multibyte_source() {
+  // start、end 为字节偏移，column 按字符计算
+  let mut jsi_vm = JSI::new();
+  let program = jsi_vm.parse(String::from("let s = '中文'; s")).unwrap();
+  assert_eq!(program.body[1].span(), span(18, 19, 1, 15));
+}
+
+#[test]
+fn span_parse_error_position() {
+  let mut jsi_vm = JSI::new();
+  let result = jsi_vm.parse(String::from("let a = 1;\nlet b = (2 +;"));
+  if let Err(jsi_error) = result {
+    assert_eq!((jsi_error.line, jsi_error.column), (2, 13));
+  } else {
+    assert!(false, "need error");
+  }
+}
+
+#[test]
+fn span_runtime_error_position() {
+  let mut jsi_vm = JSI::new();
+  let result = jsi_vm.run(String::from("let a = 1;\nlet b = {};\n  b.c.d;"));
+  if let Err(jsi_error) = result {
+    assert_eq!((jsi_error.line, jsi_error.column), (3, 3));
+  } else {
+    assert!(false, "need error");
+  }
+}
@@ -341,3 +341,186 @@ fn run_string_split_no_match() {
   ")).unwrap();
   assert_eq!(result, Value::Number(1f64));
 }
+
+#[test]
+fn run_string_pad_and_repeat() {
+  let mut jsi = JSI::new();
+  let result = jsi.run(String::from("\
+    ['5'.padStart(3, '0'), 'ab'.padEnd(5, 'xy'), 'ab'.padStart(1), 'ab'.repeat(3)].join()
+  ")).unwrap();
+  assert_eq!(result, Value::String(String::from("005,abxyx,ab,ababab")));
+}
+
+#[test]
+fn run_string_pad_max_length_range_error() {
+  let mut jsi = JSI::new();
+  let result = jsi.run(String::from("\
+    let messages = [];
+    try {
+      'ab'.padStart(2147483648);
+    } catch (e) {
+      messages.push(e.name + ': ' + e.message);
+    }
+    try {
+      'ab'.padEnd(Infinity, 'x');
+    } catch (e) {
+      messages.push(e.name + ': ' + e.message);
+    }
+    messages.push('ab'.padEnd(Infinity, ''));
+    messages.join()
+  ")).unwrap();
+  assert_eq!(result, Value::String(String::from("RangeError: Invalid string length,RangeError: Invalid string length,ab")));
+}
+
+#[test]
+fn run_string_repeat_range_error() {
+  let mut jsi = JSI::new();
+  let result = jsi.run(String::from("\
+    let msg = '';
+    try {
+      'a'.repeat(-1);
+    } catch (e) {
+      msg = e.message;
+    }
+    msg
+  ")).unwrap();
+  assert_eq!(result, Value::String(String::from("Invalid count value: -1")));
+}
+
+#[test]
+fn run_string_trim_start_end() {
+  let mut jsi = JSI::new();
+  let result = jsi.run(String::from("\
+    '[' + '  x  '.trimStart() + '][' + '\\u00a0x\\ufeff'.trimEnd() + ']'
+  ")).unwrap();
+  assert_eq!(result, Value::String(String::from("[x  ][\u{a0}x]")));
+}
+
+#[test]
+fn run_string_code_units() {
+  let mut jsi = JSI::new();
+  let result = jsi.run(String::from("\
+    let emoji = '😀';
+    [emoji.codePointAt(0), emoji.codePointAt(1), emoji.charCodeAt(0), 'abc'.charCodeAt(5), 'abc'.at(-1)].join()
+  ")).unwrap();
+  assert_eq!(result, Value::String(String::from("128512,56832,55357,NaN,c")));
+}
+
+#[test]
+fn run_string_normalize_and_compare() {
+  let mut jsi = JSI::new();
+  let result = jsi.run(String::from("\
+    let nfd = 'caf\\u00e9'.normalize('NFD');
+    [nfd === 'cafe\\u0301', nfd.normalize() === 'caf\\u00e9', '\\ufb01'.normalize('NFKC'), 'a'.localeCompare('b'), 'b'.localeCompare('a'), 'a'.localeCompare('a')].join()
+  ")).unwrap();
+  assert_eq!(result, Value::String(String::from("true,true,fi,-1,1,0")));
+}
+
+#[test]
+fn run_string_substr() {
+  let mut jsi = JSI::new();
+  let result = jsi.run(String::from("\
+    ['abcdef'.substr(1, 2), 'abcdef'.substr(-3, 2), 'abcdef'.substr(4)].join()
+  ")).unwrap();
+  assert_eq!(result, Value::String(String::from("bc,de,ef")));
+}
+
+#[test]
+fn run_string_statics() {
+  let mut jsi = JSI::new();
+  let result = jsi.run(String::from("\
+    let emoji = String.fromCodePoint(128512) === '😀';
+    [String.fromCharCode(72, 105), emoji, String.raw({ raw: ['a', 'b', 'c'] }, 1, 2)].join()
+  ")).unwrap();
+  assert_eq!(result, Value::String(String::from("Hi,true,a1b2c")));
+}
+
+#[test]
+fn run_string_utf16_length_and_index() {
+  let mut jsi = JSI::new();
+  let result = jsi.run(String::from("\
+    let s = 'a😀b';
+    [s.length, s.charCodeAt(1), s.indexOf('b'), s.lastIndexOf('b'), s.slice(1, 3), s.substring(3, 1), s.charAt(1) + s.charAt(2), s[3]].join()
+  ")).unwrap();
+  assert_eq!(result, Value::String(String::from("4,55357,3,3,😀,😀,😀,b")));
+}
+
+#[test]
+fn run_string_utf16_no_panic_inside_surrogate_pair() {
+  let mut jsi = JSI::new();
+  let result = jsi.run(String::from("\
+    let s = '😀😀';
+    let half = s.slice(0, 1);
+    [half.length, half.charCodeAt(0), s.substring(1, 3) === '\\uDE00\\uD83D', s.split('').length].join()
+  ")).unwrap();
+  assert_eq!(result, Value::String(String::from("1,55357,true,4")));
+}
+
+#[test]
+fn run_string_lone_surrogate_escapes() {
+  let mut jsi = JSI::new();
+  let result = jsi.run(String::from("\
+    let high = '\\uD83D';
+    let low = '\\uDE00';
+    let pair = high + low;
+    [typeof high, high.length, high === '\\ud83d', pair === '😀', pair.length, `${high}\\uDE00` === '\\u{1F600}', String.fromCharCode(0xD83D, 0xDE00) === pair].join()
+  ")).unwrap();
+  assert_eq!(result, Value::String(String::from("string,1,true,true,2,true,true")));
+}
+
+#[test]
+fn run_string_lone_surrogate_bytecode() {
+  let mut jsi = JSI::new();
+  let result = jsi.run_with_bytecode(String::from("\
+    let high = '\\uD83D';
+    high + '\\uDE00' === '😀'
+  ")).unwrap();
+  assert_eq!(result, Value::Boolean(true));
+}
+
+#[test]
+fn run_string_lone_surrogate_property_key() {
+  let mut jsi = JSI::new();
+  let result = jsi.run(String::from("\
+    let o = {};
+    o['\\uD83D'] = 1;
+    o['\\uDC00'] = 2;
+    let keys = Object.keys(o);
+    [o['\\uD83D'], o['\\uDC00'], keys.length, keys[0] === '\\uD83D', '\\uDE00' in o, o.hasOwnProperty('\\uDC00')].join()
+  ")).unwrap();
+  assert_eq!(result, Value::String(String::from("1,2,2,true,false,true")));
+}
+
+#[test]
+fn run_string_property_key_marker_collision() {
+  let mut jsi = JSI::new();
+  // 含有 U+FDD0 的普通字符串和单独的代理项不能得到同一个键
+  let result = jsi.run(String::from("\
+    let o = {};
+    let plain = String.fromCodePoint(0xFDD0, 0xFD800);
+    o[plain] = 'real';
+    o['\\uFDD0'] = 'mark';
+    let keys = Object.keys(o);
+    [o['\\uD800'] === undefined, o[plain], keys[0].length, keys[0] === plain, keys[1] === '\\uFDD0', '\\uD800' in o, { [plain]: 1 }.hasOwnProperty(plain)].join()
+  ")).unwrap();
+  assert_eq!(result, Value::String(String::from("true,real,3,true,true,false,true")));
+}
+
+#[test]
+fn run_string_lone_surrogate_join_and_number() {
+  let mut jsi = JSI::new();
+  let result = jsi.run(String::from("\
+    [['\\uD83D', '\\uDE00'].join('') === '😀', ['\\uD83D', 'a'].join('\\uDE00').length, isNaN(Number('\\uD83D'))].join()
+  ")).unwrap();
+  assert_eq!(result, Value::String(String::from("true,3,true")));
+}
+
+#[test]
+fn run_template_literal_escapes() {
+  let mut jsi = JSI::new();
+  let result = jsi.run(String::from("\
+    let a = 1;
+    [`${a}${a}`, `\\u0041${a}`, `\\`${a}\\``].join('|')
+  ")).unwrap();
+  assert_eq!(result, Value::String(String::from("11|A1|`1`")));
+}